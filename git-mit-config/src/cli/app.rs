@@ -1,7 +1,11 @@
 use clap::Parser;
 use clap_complete::Shell;
 use mit_commit_message_lints::{
-    mit::lib::{non_clean_behaviour::BehaviourOption, rotation_option::RotationOption},
+    lints::{ConfigSource, LintSeverity},
+    mit::lib::{
+        expiry_mode::ExpiryMode, non_clean_behaviour::BehaviourOption,
+        rotation_option::RotationOption, signing_format::SigningFormat,
+    },
     scope::Scope,
 };
 
@@ -38,29 +42,30 @@ pub enum Action {
 pub enum Lint {
     /// Generate the config file for your current settings
     Generate {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
     },
     /// List the available lints
     Available {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
     },
     /// List the enabled lints
     Enabled {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
     },
     /// Get the status of a lint
     Status {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
+        /// The lint to get the status of, built-in or custom
         #[clap()]
-        lints: Vec<mit_lint::Lint>,
+        lints: Vec<String>,
     },
     /// Enable a lint
     Enable {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
         /// The lint to enable
         #[clap()]
@@ -68,19 +73,43 @@ pub enum Lint {
     },
     /// Disable a lint
     Disable {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
         /// The lint to disable
         #[clap()]
         lints: Vec<mit_lint::Lint>,
     },
+    /// Lint the messages of commits that have already been made
+    Check {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Use the lint configuration file from the working tree, or as it was
+        /// in each commit
+        #[clap(long, value_enum, value_parser, default_value = "tree")]
+        config_from: ConfigSource,
+        /// The commits to lint, either a single revision, or a range like
+        /// `origin/main..HEAD` or `origin/main...HEAD`
+        #[clap(default_value = "HEAD")]
+        range: String,
+    },
+    /// Set how serious a failure of a lint is
+    SetSeverity {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Whether the lint blocks the commit, only warns, or is not run
+        #[clap(index = 1)]
+        severity: LintSeverity,
+        /// The lint to set the severity of
+        #[clap(index = 2, required = true)]
+        lints: Vec<mit_lint::Lint>,
+    },
 }
 
 #[derive(clap::Subcommand, Ord, PartialOrd, Eq, PartialEq, Debug, Clone)]
 pub enum Mit {
     /// Update or add an initial in the mit configuration
     Set {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
         /// Initial of the mit to update or add
         #[clap()]
@@ -94,15 +123,106 @@ pub enum Mit {
         /// The signing key to use for this user
         #[clap()]
         signingkey: Option<String>,
+        /// The kind of signing key, if it can't be told from the key. SSH
+        /// public keys, and paths to them, are recognised
+        #[clap(long, value_enum, requires = "signingkey")]
+        signingformat: Option<SigningFormat>,
+        /// Save the author to the authors file rather than git config,
+        /// keeping the file's formatting and comments. If there are several
+        /// authors files, the last one is changed
+        #[clap(long)]
+        write: bool,
+        /// Paths to files or directories where mit initials, emails and names can
+        /// be found, separated like `PATH`, later ones taking precedence
+        #[clap(
+            short,
+            long,
+            env = "GIT_MIT_AUTHORS_CONFIG",
+            default_value = "$HOME/.config/git-mit/mit.toml"
+        )]
+        config: String,
+    },
+    /// Remove an initial from the mit configuration
+    Remove {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Initial of the mit to remove
+        #[clap()]
+        initials: String,
+        /// Remove the author from the authors file rather than git config,
+        /// keeping the file's formatting and comments. If there are several
+        /// authors files, the last one is changed
+        #[clap(long, conflicts_with = "exec")]
+        write: bool,
+        /// Paths to files or directories where mit initials, emails and names can
+        /// be found, separated like `PATH`, later ones taking precedence
+        #[clap(
+            short,
+            long,
+            env = "GIT_MIT_AUTHORS_CONFIG",
+            default_value = "$HOME/.config/git-mit/mit.toml"
+        )]
+        config: String,
+        /// Execute a command to generate the mit configuration, stdout will be
+        /// captured and used instead of the file, if both this and the file are
+        /// present, this takes precedence
+        #[clap(short, long, env = "GIT_MIT_AUTHORS_EXEC")]
+        exec: Option<String>,
+    },
+    /// Change the initial of a mit in the mit configuration
+    Rename {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Initial of the mit to rename
+        #[clap(index = 1)]
+        from: String,
+        /// The new initial for the mit
+        #[clap(index = 2)]
+        to: String,
+        /// Paths to files or directories where mit initials, emails and names can
+        /// be found, separated like `PATH`, later ones taking precedence
+        #[clap(
+            short,
+            long,
+            env = "GIT_MIT_AUTHORS_CONFIG",
+            default_value = "$HOME/.config/git-mit/mit.toml"
+        )]
+        config: String,
+        /// Execute a command to generate the mit configuration, stdout will be
+        /// captured and used instead of the file, if both this and the file are
+        /// present, this takes precedence
+        #[clap(short, long, env = "GIT_MIT_AUTHORS_EXEC")]
+        exec: Option<String>,
+    },
+    /// Update or add a group of initials that can be used in place of an
+    /// initial
+    SetGroup {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Name of the group to update or add
+        #[clap(index = 1)]
+        name: String,
+        /// Initials of the authors in the group
+        #[clap(index = 2, required = true)]
+        initials: Vec<String>,
+    },
+    /// Show who commits will be made by, when that expires, and what they
+    /// relate to
+    Status {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Print the status as JSON, for use in scripts and shell prompts
+        #[clap(long)]
+        json: bool,
     },
     /// Get the current behavior when the repository is mid-rebase or merge.
     NonCleanBehaviour {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
     },
     /// Set the current behavior when the repository is mid-rebase or merge.
     SetNonCleanBehaviour {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
         /// What to do for rebase, merge commits and similar
         ///
@@ -119,12 +239,12 @@ pub enum Mit {
     },
     /// Get the current rotation setting for primary author across commits
     Rotation {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
     },
     /// Set the rotation setting for primary author across commits
     SetRotation {
-        #[clap(long, value_enum, value_parser, default_value = "local")]
+        #[clap(long, value_parser, default_value = "local")]
         scope: Scope,
         /// Which rotation strategy to use
         #[clap(
@@ -135,9 +255,37 @@ pub enum Mit {
         )]
         rotation: RotationOption,
     },
+    /// Get whether committing keeps the session from expiring
+    ExpiryMode {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// Show the setting for the relates-to instead of the authors
+        #[clap(long)]
+        relates_to: bool,
+    },
+    /// Set whether committing keeps the session from expiring
+    SetExpiryMode {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+        /// How the session expires
+        ///
+        /// * 'fixed' expires when the timeout given to `git mit` runs out
+        /// * 'sliding' pushes the expiry back by that timeout on every commit
+        #[clap(
+            index = 1,
+            env = "GIT_MIT_SET_EXPIRY_MODE",
+            default_value = "sliding",
+            num_args = 1
+        )]
+        mode: ExpiryMode,
+        /// Apply the same setting to the relates-to
+        #[clap(long)]
+        relates_to: bool,
+    },
     /// Generate a file version of available authors
     Generate {
-        /// Path to a file where mit initials, emails and names can be found
+        /// Paths to files or directories where mit initials, emails and names can
+        /// be found, separated like `PATH`, later ones taking precedence
         #[clap(
             short,
             long,
@@ -153,7 +301,8 @@ pub enum Mit {
     },
     /// List available authors
     Available {
-        /// Path to a file where mit initials, emails and names can be found
+        /// Paths to files or directories where mit initials, emails and names can
+        /// be found, separated like `PATH`, later ones taking precedence
         #[clap(
             short,
             long,
@@ -175,7 +324,7 @@ pub enum Mit {
 pub enum RelatesTo {
     /// Use a template for the relates-to trailer
     Template {
-        #[clap(long, short, value_enum, value_parser, default_value = "local")]
+        #[clap(long, short, value_parser, default_value = "local")]
         scope: Scope,
         /// A `TinyTemplate` template with a single value variable that will be
         /// applied to the relates-to trailer
@@ -2,7 +2,7 @@ use std::convert::TryFrom;
 
 use miette::Result;
 use mit_commit_message_lints::{
-    console::style::author_table,
+    console::style::{self, author_table},
     external::Git2,
     mit::{Authors, GenericArgs, get_authors},
 };
@@ -16,7 +16,17 @@ pub fn run_available(config: &str, exec: Option<&str>) -> Result<()> {
     };
     let file_authors = get_authors(&args)?;
     let git_config = current_dir().and_then(Git2::try_from)?;
-    let authors = file_authors.merge(&Authors::try_from(&git_config)?);
+    let authors = file_authors.merge(&Authors::try_from(&git_config)?.with_source("git config"));
+
+    for (initial, sources) in authors.conflicts() {
+        style::warning(
+            &format!(
+                "{initial} is defined differently in {}",
+                sources.join(" and ")
+            ),
+            Some("The author from the last of these is used"),
+        );
+    }
 
     let output: String = author_table(&authors);
 
@@ -0,0 +1,55 @@
+use std::env::current_dir;
+
+use miette::{IntoDiagnostic, Report, Result};
+use mit_commit_message_lints::{
+    mit::{GenericArgs, get_authors, remove_config_authors, remove_file_authors},
+    scope::Scope,
+};
+
+use crate::{
+    errors::{AuthorNotFound, AuthorNotInFile, AuthorOnlyInFile},
+    get_vcs,
+};
+
+pub fn run(scope: Scope, initial: &str, config: &str, exec: Option<&str>) -> Result<()> {
+    let current_dir = current_dir().into_diagnostic()?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
+
+    if remove_config_authors(&mut vcs, initial)? {
+        Ok(())
+    } else {
+        Err(missing_author(initial, config, exec))
+    }
+}
+
+pub fn run_file(initial: &str, config: &str) -> Result<()> {
+    if remove_file_authors(config, initial)? {
+        Ok(())
+    } else {
+        Err(AuthorNotInFile {
+            initial: initial.to_string(),
+        }
+        .into())
+    }
+}
+
+/// Explain why an author couldn't be found in the git config
+pub fn missing_author(initial: &str, config: &str, exec: Option<&str>) -> Report {
+    let args = GenericArgs {
+        author_command: exec.as_ref().map(|x| x as _),
+        author_file: Some(config),
+    };
+    let in_file = get_authors(&args).is_ok_and(|authors| authors.authors.contains_key(initial));
+
+    if in_file {
+        AuthorOnlyInFile {
+            initial: initial.to_string(),
+        }
+        .into()
+    } else {
+        AuthorNotFound {
+            initial: initial.to_string(),
+        }
+        .into()
+    }
+}
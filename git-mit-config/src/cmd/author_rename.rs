@@ -0,0 +1,17 @@
+use std::env::current_dir;
+
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{mit::rename_config_authors, scope::Scope};
+
+use crate::{cmd::author_remove::missing_author, get_vcs};
+
+pub fn run(scope: Scope, from: &str, to: &str, config: &str, exec: Option<&str>) -> Result<()> {
+    let current_dir = current_dir().into_diagnostic()?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
+
+    if rename_config_authors(&mut vcs, from, to)? {
+        Ok(())
+    } else {
+        Err(missing_author(from, config, exec))
+    }
+}
@@ -2,7 +2,7 @@ use std::env::current_dir;
 
 use miette::{IntoDiagnostic, Result};
 use mit_commit_message_lints::{
-    mit::{Author, set_config_authors},
+    mit::{Author, SigningFormat, set_config_authors, set_file_authors},
     scope::Scope,
 };
 
@@ -10,22 +10,27 @@ use crate::get_vcs;
 
 pub fn run(
     scope: Scope,
+    file: Option<&str>,
     initial: &str,
     name: String,
     email: String,
     signingkey: Option<String>,
+    signingformat: Option<SigningFormat>,
 ) -> Result<()> {
+    let author = Author::new(
+        name.into(),
+        email.into(),
+        signingkey.map(std::convert::Into::into),
+    )
+    .with_signingformat(signingformat);
+
+    if let Some(file) = file {
+        return set_file_authors(file, initial, &author);
+    }
+
     let current_dir = current_dir().into_diagnostic()?;
-    let mut vcs = get_vcs(Scope::Local == scope, &current_dir)?;
-    set_config_authors(
-        &mut vcs,
-        initial,
-        &Author::new(
-            name.into(),
-            email.into(),
-            signingkey.map(std::convert::Into::into),
-        ),
-    )?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
+    set_config_authors(&mut vcs, initial, &author)?;
 
     Ok(())
 }
@@ -0,0 +1,18 @@
+use std::env::current_dir;
+
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{mit::set_config_group, scope::Scope};
+
+use crate::get_vcs;
+
+pub fn run(scope: Scope, name: &str, initials: &[String]) -> Result<()> {
+    let current_dir = current_dir().into_diagnostic()?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
+    set_config_group(
+        &mut vcs,
+        name,
+        &initials.iter().map(String::as_str).collect::<Vec<_>>(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use std::env::current_dir;
+
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{
+    console::style::{session_table, to_be_piped},
+    external::installed_hooks,
+    mit::get_session,
+    scope::Scope,
+};
+
+use crate::get_vcs;
+
+pub fn run(scope: Scope, json: bool) -> Result<()> {
+    let current_dir = current_dir().into_diagnostic()?;
+    let vcs = get_vcs(scope, &current_dir)?;
+    let session = get_session(&vcs)?.with_hooks(installed_hooks(&current_dir)?);
+
+    if json {
+        to_be_piped(&session.to_json()?);
+    } else {
+        println!("{}", session_table(&session));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use std::env::current_dir;
+
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{
+    mit::{get_config_expiry_mode, get_config_relates_to_expiry_mode},
+    scope::Scope,
+};
+
+use crate::get_vcs;
+
+pub fn run(scope: Scope, relates_to: bool) -> Result<()> {
+    let current_dir = current_dir().into_diagnostic()?;
+    let vcs = get_vcs(scope, &current_dir)?;
+
+    let mode = if relates_to {
+        get_config_relates_to_expiry_mode(&vcs)?
+    } else {
+        get_config_expiry_mode(&vcs)?
+    };
+
+    println!("{mode}");
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use std::env::current_dir;
+
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{
+    mit::{ExpiryMode, set_config_expiry_mode, set_config_relates_to_expiry_mode},
+    scope::Scope,
+};
+
+use crate::get_vcs;
+
+pub fn run(scope: Scope, mode: ExpiryMode, relates_to: bool) -> Result<()> {
+    let current_dir = current_dir().into_diagnostic()?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
+
+    set_config_expiry_mode(&mut vcs, mode)?;
+
+    if relates_to {
+        set_config_relates_to_expiry_mode(&mut vcs, mode)?;
+    }
+
+    Ok(())
+}
@@ -8,11 +8,15 @@ use crate::get_vcs;
 
 pub fn run(scope: Scope) -> Result<()> {
     let current_dir = current_dir().into_diagnostic()?;
-    let vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let vcs = get_vcs(scope, &current_dir)?;
     let toml = external::read_toml(current_dir)?;
 
-    let lints = read_from_toml_or_else_vcs(&toml, &vcs)?;
-    mit_commit_message_lints::console::style::lint_table(Lints::available(), &lints);
+    let config = read_from_toml_or_else_vcs(&toml, &vcs)?;
+    mit_commit_message_lints::console::style::lint_table(
+        Lints::available(),
+        config.severities(),
+        config.custom(),
+    );
 
     Ok(())
 }
@@ -0,0 +1,62 @@
+use miette::{Report, Result};
+use mit_commit::CommitMessage;
+use mit_commit_message_lints::{
+    console::style,
+    external,
+    lints::{ConfigSource, read_from_toml_or_else_vcs},
+    scope::Scope,
+};
+
+use crate::{
+    current_dir,
+    errors::{CommitLintFailed, LintCheckFailed},
+    get_vcs,
+};
+
+pub fn run(scope: Scope, config_from: ConfigSource, range: &str) -> Result<()> {
+    let current_dir = current_dir()?;
+    let vcs = get_vcs(scope, &current_dir)?;
+    let tree_toml = external::read_toml(current_dir.clone())?;
+    let commits = external::read_revision_range(current_dir, range)?;
+    let checked = commits.len();
+
+    // Plugins run commands, so only the checked out config can define them
+    let tree_config = read_from_toml_or_else_vcs(&tree_toml, &vcs)?;
+    style::untrusted_plugins(tree_config.plugins());
+
+    let mut failed = 0;
+    for commit in commits {
+        let config = match config_from {
+            ConfigSource::Tree => tree_config.clone(),
+            ConfigSource::Commit => read_from_toml_or_else_vcs(&commit.toml, &vcs)?
+                .with_plugins(tree_config.plugins().clone()),
+        };
+        let commit_message = CommitMessage::from(commit.message.as_str());
+        let (errors, warnings) = config.lint(&commit_message)?.into_parts();
+
+        for warning in warnings {
+            style::warning(
+                &format!("{}: {warning}", commit.id),
+                warning.help().map(|help| help.to_string()).as_deref(),
+            );
+        }
+
+        if !errors.is_empty() {
+            failed += 1;
+            eprintln!(
+                "{:?}",
+                Report::new(CommitLintFailed {
+                    id: commit.id,
+                    subject: commit_message.get_subject().to_string(),
+                    problems: errors,
+                })
+            );
+        }
+    }
+
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(LintCheckFailed { failed, checked }.into())
+    }
+}
@@ -8,7 +8,7 @@ use crate::get_vcs;
 
 pub fn run(scope: Scope, lints: Vec<Lint>) -> Result<()> {
     let current_dir = current_dir().into_diagnostic()?;
-    let mut vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
     let toml = external::read_toml(current_dir)?;
     if !toml.is_empty() {
         mit_commit_message_lints::console::style::warning(
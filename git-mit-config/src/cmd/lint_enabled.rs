@@ -5,11 +5,15 @@ use crate::{current_dir, get_vcs};
 
 pub fn run(matches: Scope) -> Result<()> {
     let current_dir = current_dir()?;
-    let vcs = get_vcs(matches == Scope::Local, &current_dir)?;
+    let vcs = get_vcs(matches, &current_dir)?;
     let toml = external::read_toml(current_dir)?;
 
-    let lints = read_from_toml_or_else_vcs(&toml, &vcs)?;
-    mit_commit_message_lints::console::style::lint_table(&lints, &lints);
+    let config = read_from_toml_or_else_vcs(&toml, &vcs)?;
+    mit_commit_message_lints::console::style::lint_table(
+        &config.lints(),
+        config.severities(),
+        config.custom(),
+    );
 
     Ok(())
 }
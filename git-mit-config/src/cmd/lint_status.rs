@@ -1,17 +1,35 @@
+use std::convert::TryFrom;
+
 use miette::Result;
 use mit_commit_message_lints::{external, lints::read_from_toml_or_else_vcs, scope::Scope};
-use mit_lint::Lint;
+use mit_lint::{Lint, Lints};
 
 use crate::{current_dir, get_vcs};
 
-pub fn run(scope: Scope, lints: Vec<Lint>) -> Result<()> {
+pub fn run(scope: Scope, lints: &[String]) -> Result<()> {
     let current_dir = current_dir()?;
-    let vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let vcs = get_vcs(scope, &current_dir)?;
     let toml = external::read_toml(current_dir)?;
 
     let config = read_from_toml_or_else_vcs(&toml, &vcs)?;
 
-    mit_commit_message_lints::console::style::lint_table(&lints.into(), &config);
+    let custom_names: Vec<&str> = lints
+        .iter()
+        .map(String::as_str)
+        .filter(|name| config.custom().get(name).is_some())
+        .collect();
+    let built_in = lints
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !custom_names.contains(name))
+        .map(Lint::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    mit_commit_message_lints::console::style::lint_table(
+        &Lints::from(built_in),
+        config.severities(),
+        &config.custom().filter(&custom_names),
+    );
 
     Ok(())
 }
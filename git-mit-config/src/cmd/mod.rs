@@ -1,11 +1,19 @@
 pub mod author_example;
 pub mod author_generate;
+pub mod author_remove;
+pub mod author_rename;
 pub mod author_set;
+pub mod author_set_group;
+pub mod author_status;
+pub mod expiry_mode;
+pub mod expiry_mode_set;
 pub mod lint_available;
+pub mod lint_check;
 pub mod lint_disable;
 pub mod lint_enable;
 pub mod lint_enabled;
 pub mod lint_generate;
+pub mod lint_set_severity;
 pub mod lint_status;
 pub mod non_clean_behaviour;
 pub mod non_clean_behaviour_set;
@@ -13,7 +13,7 @@ use crate::get_vcs;
 
 pub fn run(scope: Scope, behaviour: BehaviourOption) -> Result<()> {
     let current_dir = current_dir().into_diagnostic()?;
-    let mut vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
 
     set_config_non_clean_behaviour(&mut vcs, behaviour)?;
 
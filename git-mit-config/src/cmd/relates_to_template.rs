@@ -5,7 +5,7 @@ use crate::{current_dir, get_vcs};
 
 pub fn run(scope: Scope, template: &str) -> Result<()> {
     let current_dir = current_dir()?;
-    let mut vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
 
     vcs.set_str("mit.relate.template", template)?;
 
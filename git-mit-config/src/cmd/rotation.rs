@@ -7,7 +7,7 @@ use crate::get_vcs;
 
 pub fn run(scope: Scope) -> Result<()> {
     let current_dir = current_dir().into_diagnostic()?;
-    let vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let vcs = get_vcs(scope, &current_dir)?;
 
     let rotation = get_config_rotation(&vcs)?;
 
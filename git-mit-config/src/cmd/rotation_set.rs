@@ -10,7 +10,7 @@ use crate::get_vcs;
 
 pub fn run(scope: Scope, rotation: RotationOption) -> Result<()> {
     let current_dir = current_dir().into_diagnostic()?;
-    let mut vcs = get_vcs(scope == Scope::Local, &current_dir)?;
+    let mut vcs = get_vcs(scope, &current_dir)?;
 
     set_config_rotation(&mut vcs, rotation)?;
 
@@ -1,4 +1,5 @@
 use miette::Diagnostic;
+use mit_commit_message_lints::lints::LintProblem;
 use thiserror::Error;
 
 #[derive(Error, Debug, Diagnostic)]
@@ -39,4 +40,76 @@ pub enum LibGit2 {
         #[source]
         source: git2::Error,
     },
+    #[error("unable to find git's system configuration")]
+    #[diagnostic(
+        code(git_mit_config::errors::lib_git2::find_system_config),
+        help(
+            "does the system config exist? `git config --system --list --show-origin` shows where it is"
+        )
+    )]
+    FindSystemConfig {
+        #[source]
+        source: git2::Error,
+    },
+    #[error("unable to read the configuration file {path}")]
+    #[diagnostic(
+        code(git_mit_config::errors::lib_git2::read_config_file),
+        help("is the path right, and is it a git config file?")
+    )]
+    ReadConfigFile {
+        path: String,
+        #[source]
+        source: git2::Error,
+    },
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("commit {id} failed the lints: {subject}")]
+#[diagnostic(code(git_mit_config::errors::commit_lint_failed))]
+pub struct CommitLintFailed {
+    pub id: String,
+    pub subject: String,
+    #[related]
+    pub problems: Vec<LintProblem>,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("{failed} of {checked} commits failed the lints")]
+#[diagnostic(
+    code(git_mit_config::errors::lint_check_failed),
+    help("you can change the commit messages with `git rebase --interactive`")
+)]
+pub struct LintCheckFailed {
+    pub failed: usize,
+    pub checked: usize,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("no author with the initials {initial} is saved in git config")]
+#[diagnostic(
+    code(git_mit_config::errors::author_not_found),
+    help("to see the authors available run `git mit-config mit available`")
+)]
+pub struct AuthorNotFound {
+    pub initial: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("the author with the initials {initial} is only in your authors file")]
+#[diagnostic(
+    code(git_mit_config::errors::author_only_in_file),
+    help("authors in your authors file can only be changed by editing that file")
+)]
+pub struct AuthorOnlyInFile {
+    pub initial: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("no author with the initials {initial} is in your authors file")]
+#[diagnostic(
+    code(git_mit_config::errors::author_not_in_file),
+    help("to remove an author saved in git config leave out `--write`")
+)]
+pub struct AuthorNotInFile {
+    pub initial: String,
 }
@@ -26,15 +26,22 @@ use std::{
 };
 
 use clap_complete::generate;
-use git2::{Config, Repository};
+use git2::{Config, ConfigLevel, Repository};
 use miette::{IntoDiagnostic, Result};
-use mit_commit_message_lints::{console::error_handling::miette_install, external::Git2};
+use mit_commit_message_lints::{
+    console::error_handling::miette_install,
+    external::{Git2, checked_out_branch},
+    scope::Scope,
+};
 
 use crate::{
     cli::{app, app::CliArgs},
     cmd::{author_generate, author_set},
     errors::{
-        LibGit2::{DiscoverGitRepository, ReadConfigFromGitRepository, ReadUserConfigFromGit},
+        LibGit2::{
+            DiscoverGitRepository, FindSystemConfig, ReadConfigFile, ReadConfigFromGitRepository,
+            ReadUserConfigFromGit,
+        },
         UnrecognisedLintCommand,
     },
 };
@@ -61,80 +68,218 @@ fn main() -> Result<()> {
     }
 
     match cli_args.action {
-        Some(Action::Lint {
-            action: app::Lint::Available { scope },
-        }) => cmd::lint_available::run(scope),
-        Some(Action::Lint {
-            action: app::Lint::Enabled { scope },
-        }) => cmd::lint_enabled::run(scope),
-        Some(Action::Lint {
-            action: app::Lint::Status { scope, lints },
-        }) => cmd::lint_status::run(scope, lints),
-        Some(Action::Lint {
-            action: app::Lint::Enable { scope, lints },
-        }) => cmd::lint_enable::run(scope, lints),
-        Some(Action::Lint {
-            action: app::Lint::Disable { scope, lints },
-        }) => cmd::lint_disable::run(scope, lints),
-        Some(Action::Lint {
-            action: app::Lint::Generate { scope },
-        }) => cmd::lint_generate::run(scope),
-        Some(Action::Mit {
-            action:
-                app::Mit::Set {
-                    scope,
-                    initials,
-                    name,
-                    email,
-                    signingkey,
-                },
-        }) => author_set::run(scope, &initials, name, email, signingkey),
-        Some(Action::Mit {
-            action: app::Mit::Generate { config, exec },
-        }) => author_generate::run_generate(&config, exec.as_deref()),
-        Some(Action::Mit {
-            action: app::Mit::Available { config, exec },
-        }) => author_generate::run_available(&config, exec.as_deref()),
-        Some(Action::Mit {
-            action: app::Mit::Example,
-        }) => cmd::author_example::run(),
-        Some(Action::Mit {
-            action: app::Mit::NonCleanBehaviour { scope },
-        }) => cmd::non_clean_behaviour::run(scope),
-        Some(Action::Mit {
-            action: app::Mit::SetNonCleanBehaviour { scope, behaviour },
-        }) => cmd::non_clean_behaviour_set::run(scope, behaviour),
-        Some(Action::Mit {
-            action: app::Mit::Rotation { scope },
-        }) => cmd::rotation::run(scope),
-        Some(Action::Mit {
-            action: app::Mit::SetRotation { scope, rotation },
-        }) => cmd::rotation_set::run(scope, rotation),
-        Some(Action::RelatesTo {
-            action: app::RelatesTo::Template { scope, template },
-        }) => cmd::relates_to_template::run(scope, &template),
+        Some(Action::Lint { action }) => run_lint(action),
+        Some(Action::Mit { action }) => run_mit(action),
+        Some(Action::RelatesTo { action }) => run_relates_to(action),
         None => Err(UnrecognisedLintCommand {}.into()),
     }
 }
 
-fn get_vcs(local: bool, current_dir: &Path) -> Result<Git2> {
-    let (git_config, git_state) = if local {
-        Repository::discover(current_dir)
-            .map_err(|source| DiscoverGitRepository { source })
-            .and_then(|repo: Repository| {
-                repo.config()
-                    .map_err(|source| ReadConfigFromGitRepository { source })
-                    .map(|config| (config, Some(repo.state())))
-            })?
-    } else {
-        Config::open_default()
+fn run_lint(action: app::Lint) -> Result<()> {
+    match action {
+        app::Lint::Available { scope } => cmd::lint_available::run(scope),
+        app::Lint::Enabled { scope } => cmd::lint_enabled::run(scope),
+        app::Lint::Status { scope, lints } => cmd::lint_status::run(scope, &lints),
+        app::Lint::Enable { scope, lints } => cmd::lint_enable::run(scope, lints),
+        app::Lint::Disable { scope, lints } => cmd::lint_disable::run(scope, lints),
+        app::Lint::SetSeverity {
+            scope,
+            severity,
+            lints,
+        } => cmd::lint_set_severity::run(scope, severity, lints),
+        app::Lint::Check {
+            scope,
+            config_from,
+            range,
+        } => cmd::lint_check::run(scope, config_from, &range),
+        app::Lint::Generate { scope } => cmd::lint_generate::run(scope),
+    }
+}
+
+fn run_mit(action: app::Mit) -> Result<()> {
+    match action {
+        app::Mit::Set {
+            scope,
+            initials,
+            name,
+            email,
+            signingkey,
+            signingformat,
+            write,
+            config,
+        } => author_set::run(
+            scope,
+            write.then_some(config.as_str()),
+            &initials,
+            name,
+            email,
+            signingkey,
+            signingformat,
+        ),
+        app::Mit::Remove {
+            initials,
+            write: true,
+            config,
+            ..
+        } => cmd::author_remove::run_file(&initials, &config),
+        app::Mit::Remove {
+            scope,
+            initials,
+            config,
+            exec,
+            ..
+        } => cmd::author_remove::run(scope, &initials, &config, exec.as_deref()),
+        app::Mit::Rename {
+            scope,
+            from,
+            to,
+            config,
+            exec,
+        } => cmd::author_rename::run(scope, &from, &to, &config, exec.as_deref()),
+        app::Mit::SetGroup {
+            scope,
+            name,
+            initials,
+        } => cmd::author_set_group::run(scope, &name, &initials),
+        app::Mit::Status { scope, json } => cmd::author_status::run(scope, json),
+        app::Mit::Generate { config, exec } => {
+            author_generate::run_generate(&config, exec.as_deref())
+        }
+        app::Mit::Available { config, exec } => {
+            author_generate::run_available(&config, exec.as_deref())
+        }
+        app::Mit::Example => cmd::author_example::run(),
+        app::Mit::NonCleanBehaviour { scope } => cmd::non_clean_behaviour::run(scope),
+        app::Mit::SetNonCleanBehaviour { scope, behaviour } => {
+            cmd::non_clean_behaviour_set::run(scope, behaviour)
+        }
+        app::Mit::Rotation { scope } => cmd::rotation::run(scope),
+        app::Mit::SetRotation { scope, rotation } => cmd::rotation_set::run(scope, rotation),
+        app::Mit::ExpiryMode { scope, relates_to } => cmd::expiry_mode::run(scope, relates_to),
+        app::Mit::SetExpiryMode {
+            scope,
+            mode,
+            relates_to,
+        } => cmd::expiry_mode_set::run(scope, mode, relates_to),
+    }
+}
+
+fn run_relates_to(action: app::RelatesTo) -> Result<()> {
+    match action {
+        app::RelatesTo::Template { scope, template } => {
+            cmd::relates_to_template::run(scope, &template)
+        }
+    }
+}
+
+fn get_vcs(scope: Scope, current_dir: &Path) -> Result<Git2> {
+    match scope {
+        Scope::Global => Config::open_default()
             .map_err(|source| ReadUserConfigFromGit { source })
-            .map(|config| (config, None))?
-    };
+            .map(Git2::global)?,
+        Scope::System => {
+            let git_config =
+                Config::open_default().map_err(|source| ReadUserConfigFromGit { source })?;
+            let target = git_config
+                .open_level(ConfigLevel::System)
+                .map_err(|source| FindSystemConfig { source })?;
+
+            Ok(Git2::new(git_config, None)?.writing_to(target))
+        }
+        Scope::File(path) => {
+            // Changes go to the file, but it's only read if git would read
+            // it, like when it's included from another config
+            let git_config = Repository::discover(current_dir)
+                .and_then(|repo| repo.config())
+                .or_else(|_| Config::open_default())
+                .map_err(|source| ReadUserConfigFromGit { source })?;
+            let target = Config::open(&path).map_err(|source| ReadConfigFile {
+                path: path.to_string_lossy().to_string(),
+                source,
+            })?;
+
+            Ok(Git2::new(git_config, None)?.writing_to(target))
+        }
+        Scope::Local => {
+            let (git_config, git_state, branch) = Repository::discover(current_dir)
+                .map_err(|source| DiscoverGitRepository { source })
+                .and_then(|repo: Repository| {
+                    repo.config()
+                        .map_err(|source| ReadConfigFromGitRepository { source })
+                        .map(|config| (config, Some(repo.state()), checked_out_branch(&repo)))
+                })?;
+            let target = git_config
+                .open_level(ConfigLevel::Local)
+                .map_err(|source| ReadConfigFromGitRepository { source })?;
 
-    Git2::new(git_config, git_state)
+            Ok(Git2::new(git_config, git_state)?
+                .writing_to(target)
+                .with_branch(branch))
+        }
+        Scope::Worktree => {
+            Repository::discover(current_dir).map_err(|source| DiscoverGitRepository { source })?;
+            Git2::worktree(current_dir)
+        }
+    }
 }
 
 fn current_dir() -> Result<PathBuf> {
     env::current_dir().into_diagnostic()
 }
+
+#[cfg(test)]
+mod tests {
+    use git2::Repository;
+    use mit_commit_message_lints::{external::Vcs, scope::Scope};
+
+    use super::get_vcs;
+
+    #[test]
+    fn config_files_are_written_to_without_changing_what_is_read() {
+        let directory = std::env::temp_dir().join(format!(
+            "git-mit-config-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let repository = Repository::init(&directory).unwrap();
+        repository
+            .config()
+            .unwrap()
+            .set_str("mit.lint.subject-not-capitalized", "false")
+            .unwrap();
+        let file = directory.join("git-mit.config");
+        std::fs::write(&file, "[mit \"lint\"]\n\tsubject-not-capitalized = true\n").unwrap();
+
+        let mut vcs = get_vcs(Scope::File(file.clone()), &directory).unwrap();
+
+        assert_eq!(
+            vcs.get_bool("mit.lint.subject-not-capitalized").unwrap(),
+            Some(false),
+            "Expected the local config to win, as it does for git"
+        );
+
+        vcs.set_str("mit.lint.body-wider-than-72-characters", "true")
+            .unwrap();
+
+        assert!(
+            std::fs::read_to_string(&file)
+                .unwrap()
+                .contains("body-wider-than-72-characters = true"),
+            "Expected the change to be written to the file"
+        );
+        assert!(
+            repository
+                .config()
+                .unwrap()
+                .open_level(git2::ConfigLevel::Local)
+                .unwrap()
+                .get_str("mit.lint.body-wider-than-72-characters")
+                .is_err(),
+            "Expected the local config to be left alone"
+        );
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
@@ -14,7 +14,7 @@ const HOME_ENV_KEY: &str = "USERPROFILE";
 #[clap(author, version, about)]
 #[clap(bin_name = "git-mit-install")]
 pub struct CliArgs {
-    #[clap(short, long, default_value = "local", value_enum)]
+    #[clap(short, long, default_value = "local", value_parser)]
     pub scope: Scope,
 
     #[clap(long, value_enum, value_parser)]
@@ -7,4 +7,12 @@ pub enum GitMitInstallError {
         help("{0} already exists, you need to remove this before continuing")
     )]
     ExistingHook(String),
+    #[error("can't install git-mit using the config file {0}")]
+    #[diagnostic(
+        code(git_mit_install::errors::git_mit_install_error::file_scope),
+        help(
+            "git only reads the template directory from a config file if it's included from another one, use `--scope global` or `--scope system` instead"
+        )
+    )]
+    FileScope(String),
 }
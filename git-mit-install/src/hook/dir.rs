@@ -3,13 +3,31 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use git2::Config;
+use git2::{Config, ConfigLevel};
 use miette::{IntoDiagnostic, Result};
-pub fn create(global: bool, home_dir: &Path) -> Result<PathBuf> {
-    let hooks = if global {
-        setup_global_hooks_dir(home_dir)?
-    } else {
-        get_local_hooks_dir()?
+use mit_commit_message_lints::scope::Scope;
+
+use crate::errors::GitMitInstallError;
+
+pub fn create(scope: &Scope, home_dir: &Path) -> Result<PathBuf> {
+    let hooks = match scope {
+        Scope::Local | Scope::Worktree => get_local_hooks_dir()?,
+        Scope::Global => setup_template_hooks_dir(
+            Config::open_default().into_diagnostic()?,
+            &new_template_folder(home_dir),
+        )?,
+        Scope::System => {
+            let config = Config::open_default()
+                .and_then(|config| config.open_level(ConfigLevel::System))
+                .into_diagnostic()?;
+            let template_dir =
+                system_template_dir(&Config::find_system().into_diagnostic()?, home_dir);
+            setup_template_hooks_dir(config, &template_dir)?
+        }
+        // git wouldn't read the template directory from it unless it's included
+        Scope::File(path) => {
+            return Err(GitMitInstallError::FileScope(path.display().to_string()).into());
+        }
     };
 
     if !hooks.exists() {
@@ -22,18 +40,18 @@ fn get_local_hooks_dir() -> Result<PathBuf> {
     let current_dir = env::current_dir().into_diagnostic()?;
     let repository = git2::Repository::discover(current_dir).into_diagnostic()?;
     let config = repository.config().into_diagnostic()?;
-    let default_path = repository.path().join("hooks");
+    let default_path = repository.commondir().join("hooks");
     let buf = config.get_path("core.hooksPath").unwrap_or(default_path);
     Ok(buf)
 }
 
-fn setup_global_hooks_dir(home_dir: &Path) -> Result<PathBuf> {
-    let mut config = Config::open_default().into_diagnostic()?;
-
+/// Put the hooks in the template new repositories are made from, setting one
+/// up in the config if it doesn't have one yet
+fn setup_template_hooks_dir(mut config: Config, new_template_dir: &Path) -> Result<PathBuf> {
     let template_dir = if let Ok(template_dir) = git_template_dir(&mut config) {
         template_dir
     } else {
-        let template_dir = new_template_folder(home_dir);
+        let template_dir = new_template_dir.to_path_buf();
         config
             .set_str("init.templatedir", template_dir.to_string_lossy().as_ref())
             .into_diagnostic()?;
@@ -49,6 +67,18 @@ fn new_template_folder(home_dir: &Path) -> PathBuf {
     home_dir.join(".config").join("git").join("init-template")
 }
 
+/// The template for everyone goes next to the system config, as not everyone
+/// can read the home directory of whoever installed it
+fn system_template_dir(system_config: &Path, home_dir: &Path) -> PathBuf {
+    system_config
+        .parent()
+        .map_or_else(|| new_template_folder(home_dir), system_template_folder)
+}
+
+fn system_template_folder(system_config_dir: &Path) -> PathBuf {
+    system_config_dir.join("git-mit").join("init-template")
+}
+
 fn git_template_dir(config: &mut Config) -> Result<PathBuf> {
     config
         .snapshot()
@@ -56,3 +86,85 @@ fn git_template_dir(config: &mut Config) -> Result<PathBuf> {
         .get_path("init.templatedir")
         .into_diagnostic()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use git2::Config;
+    use mit_commit_message_lints::scope::Scope;
+
+    use super::{create, setup_template_hooks_dir, system_template_dir};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "git-mit-install-{name}-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn the_system_template_is_next_to_the_system_config() {
+        assert_eq!(
+            system_template_dir(Path::new("/etc/gitconfig"), Path::new("/home/billie")),
+            PathBuf::from("/etc/git-mit/init-template"),
+            "Expected the template to be beside the system config, not in a home directory"
+        );
+    }
+
+    #[test]
+    fn a_template_is_set_up_in_the_config_if_there_is_none() {
+        let dir = temp_dir("new-template");
+        let config_path = dir.join("gitconfig");
+        fs::write(&config_path, "").unwrap();
+        let template = dir.join("init-template");
+
+        let hooks =
+            setup_template_hooks_dir(Config::open(&config_path).unwrap(), &template).unwrap();
+
+        assert_eq!(
+            (
+                hooks.is_dir(),
+                Config::open(&config_path)
+                    .unwrap()
+                    .get_path("init.templatedir")
+                    .unwrap()
+            ),
+            (true, template.clone()),
+            "Expected the new template to be created and saved in the config"
+        );
+        assert_eq!(
+            setup_template_hooks_dir(Config::open(&config_path).unwrap(), &dir.join("other"))
+                .unwrap(),
+            template.join("hooks"),
+            "Expected a template already in the config to be used"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn config_files_are_rejected() {
+        let dir = temp_dir("file-scope");
+        let config_path = dir.join("gitconfig");
+
+        assert!(
+            create(&Scope::File(config_path.clone()), &dir).is_err(),
+            "Expected installing with a config file to be an error"
+        );
+        assert!(
+            !config_path.exists(),
+            "Expected the config file to be left alone"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use miette::{IntoDiagnostic, Result};
 
@@ -24,27 +24,73 @@ fn is_our_wrapper(install_path: &Path, binary_path: &Path) -> bool {
         .is_ok_and(|content| content == wrapper_content(binary_path))
 }
 
-pub fn link(hook_path: &Path, hook_name: &str) -> Result<()> {
-    let binary_path = which::which(binary_name(hook_name)).into_diagnostic()?;
-    let binary_path = binary_path.canonicalize().into_diagnostic()?;
-    let install_path = hook_path.join(install_name(hook_name));
+/// Install the hooks, leaving the hooks directory alone if any of them can't
+/// be
+///
+/// Other tools use the optional hooks too, so if one of them is already there
+/// it's skipped rather than stopping the install. The skipped hooks are
+/// returned.
+pub fn link_all(hook_path: &Path, hooks: &[&str], optional: &[&str]) -> Result<Vec<PathBuf>> {
+    let mut to_link = vec![];
+    let mut skipped = vec![];
+
+    for hook_name in hooks {
+        match existing_hook(hook_path, hook_name)? {
+            Existing::Foreign(install_path) => {
+                return Err(GitMitInstallError::ExistingHook(
+                    install_path.to_string_lossy().to_string(),
+                )
+                .into());
+            }
+            Existing::Missing(binary_path, install_path) => {
+                to_link.push((binary_path, install_path));
+            }
+            Existing::Installed => {}
+        }
+    }
 
-    if already_installed(&install_path, &binary_path) {
-        return Ok(());
+    for hook_name in optional {
+        match existing_hook(hook_path, hook_name)? {
+            Existing::Foreign(install_path) => skipped.push(install_path),
+            Existing::Missing(binary_path, install_path) => {
+                to_link.push((binary_path, install_path));
+            }
+            Existing::Installed => {}
+        }
     }
 
-    if install_path.exists() {
-        return Err(
-            GitMitInstallError::ExistingHook(install_path.to_string_lossy().to_string()).into(),
-        );
+    for (binary_path, install_path) in to_link {
+        #[cfg(target_os = "windows")]
+        write_wrapper(&binary_path, &install_path)?;
+        #[cfg(not(target_os = "windows"))]
+        symlink(&binary_path, &install_path)?;
     }
 
-    #[cfg(target_os = "windows")]
-    write_wrapper(&binary_path, &install_path)?;
-    #[cfg(not(target_os = "windows"))]
-    symlink(&binary_path, &install_path)?;
+    Ok(skipped)
+}
 
-    Ok(())
+/// What's already installed for a hook
+enum Existing {
+    /// Nothing yet, so the binary can be installed at the path
+    Missing(PathBuf, PathBuf),
+    /// Our hook is already there
+    Installed,
+    /// Some other hook is at the path
+    Foreign(PathBuf),
+}
+
+fn existing_hook(hook_path: &Path, hook_name: &str) -> Result<Existing> {
+    let binary_path = which::which(binary_name(hook_name)).into_diagnostic()?;
+    let binary_path = binary_path.canonicalize().into_diagnostic()?;
+    let install_path = hook_path.join(install_name(hook_name));
+
+    if already_installed(&install_path, &binary_path) {
+        Ok(Existing::Installed)
+    } else if install_path.exists() {
+        Ok(Existing::Foreign(install_path))
+    } else {
+        Ok(Existing::Missing(binary_path, install_path))
+    }
 }
 
 /// The mit binary to locate on `PATH`, e.g. `mit-pre-commit` (unix) or
@@ -87,12 +133,18 @@ fn symlink(binary_path: &Path, install_path: &Path) -> Result<()> {
 
 #[cfg(test)]
 #[cfg(not(target_os = "windows"))]
+#[allow(clippy::uninlined_format_args)]
 mod tests {
     use super::*;
     use std::os::unix::fs::PermissionsExt;
+    use std::sync::Mutex;
+
+    /// The tests change `PATH`, so they can't run at the same time
+    static PATH_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
     fn link_detects_existing_correct_symlink_and_rejects_regular_file() {
+        let _lock = PATH_LOCK.lock().unwrap();
         let temp = std::env::temp_dir().join(format!(
             "git-mit-install-test-{}",
             std::time::SystemTime::now()
@@ -118,10 +170,10 @@ mod tests {
         }
 
         // First install creates the symlink
-        link(&hook_dir, "pre-commit").unwrap();
+        link_all(&hook_dir, &["pre-commit"], &[]).unwrap();
 
         // Second install should succeed because symlink already points to correct binary
-        let result = link(&hook_dir, "pre-commit");
+        let result = link_all(&hook_dir, &["pre-commit"], &[]);
         assert!(
             result.is_ok(),
             "Expected Ok(()) when symlink already points to correct binary, got {:?}",
@@ -132,7 +184,7 @@ mod tests {
         std::fs::remove_file(hook_dir.join("pre-commit")).unwrap();
         std::fs::File::create(hook_dir.join("pre-commit")).unwrap();
 
-        let result = link(&hook_dir, "pre-commit");
+        let result = link_all(&hook_dir, &["pre-commit"], &[]);
         assert!(
             result.is_err(),
             "Expected error when regular file exists at install path, got Ok(())"
@@ -144,6 +196,68 @@ mod tests {
         }
         let _ = std::fs::remove_dir_all(&temp);
     }
+
+    #[test]
+    fn existing_hooks_are_checked_before_anything_is_linked() {
+        let _lock = PATH_LOCK.lock().unwrap();
+        let temp = std::env::temp_dir().join(format!(
+            "git-mit-install-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let hook_dir = temp.join("hooks");
+        let bin_dir = temp.join("bin");
+        std::fs::create_dir_all(&hook_dir).unwrap();
+        std::fs::create_dir_all(&bin_dir).unwrap();
+
+        for hook in ["pre-commit", "commit-msg", "post-checkout"] {
+            let binary = bin_dir.join(format!("mit-{hook}"));
+            std::fs::File::create(&binary).unwrap();
+            let mut perms = std::fs::metadata(&binary).unwrap().permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&binary, perms).unwrap();
+        }
+
+        let old_path = std::env::var("PATH").unwrap_or_default();
+        // SAFETY: the tests that change PATH hold the lock
+        unsafe {
+            std::env::set_var("PATH", format!("{}:{}", bin_dir.display(), old_path));
+        }
+
+        std::fs::write(hook_dir.join("post-checkout"), "#!/bin/sh\ngit lfs\n").unwrap();
+        std::fs::write(hook_dir.join("commit-msg"), "#!/bin/sh\n").unwrap();
+
+        let result = link_all(&hook_dir, &["pre-commit", "commit-msg"], &["post-checkout"]);
+        assert!(
+            result.is_err() && !hook_dir.join("pre-commit").exists(),
+            "Expected nothing to be linked when a hook git-mit needs is already there, got {:?}",
+            result
+        );
+
+        std::fs::remove_file(hook_dir.join("commit-msg")).unwrap();
+
+        let skipped =
+            link_all(&hook_dir, &["pre-commit", "commit-msg"], &["post-checkout"]).unwrap();
+        assert_eq!(
+            skipped,
+            vec![hook_dir.join("post-checkout")],
+            "Expected the other tool's post-checkout to be skipped"
+        );
+        assert!(
+            hook_dir.join("commit-msg").is_symlink()
+                && std::fs::read_to_string(hook_dir.join("post-checkout")).unwrap()
+                    == "#!/bin/sh\ngit lfs\n",
+            "Expected the hooks to be linked, leaving the other tool's hook alone"
+        );
+
+        // SAFETY: the tests that change PATH hold the lock
+        unsafe {
+            std::env::set_var("PATH", old_path);
+        }
+        let _ = std::fs::remove_dir_all(&temp);
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -264,7 +378,7 @@ mod windows_tests {
         }
 
         // First install writes the wrapper at the bare hook name Git looks for.
-        link(&hook_dir, "pre-commit").unwrap();
+        link_all(&hook_dir, &["pre-commit"], &[]).unwrap();
         let hook = hook_dir.join("pre-commit");
         assert!(
             hook.exists(),
@@ -282,7 +396,7 @@ mod windows_tests {
         );
 
         // Second install must be a no-op, not an error.
-        let again = link(&hook_dir, "pre-commit");
+        let again = link_all(&hook_dir, &["pre-commit"], &[]);
         assert!(
             again.is_ok(),
             "re-install must be idempotent, got {again:?}"
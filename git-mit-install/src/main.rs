@@ -27,7 +27,7 @@ use clap_complete::generate;
 use hook::{dir, install, uninstall};
 use indoc::indoc;
 use miette::Result;
-use mit_commit_message_lints::console::error_handling::miette_install;
+use mit_commit_message_lints::{console::error_handling::miette_install, external::is_mit_hook};
 
 mod cli;
 mod errors;
@@ -48,7 +48,7 @@ fn main() -> Result<()> {
     }
 
     let hooks = dir::create(
-        cli_args.scope.is_global(),
+        &cli_args.scope,
         &cli_args
             .home_dir
             .expect("Home directory is required if scope is global"),
@@ -59,28 +59,46 @@ fn main() -> Result<()> {
         uninstall::unlink(&hooks, "pre-commit")?;
         uninstall::unlink(&hooks, "commit-msg")?;
 
-        if cli_args.scope.is_global() {
+        // Other tools use these hooks too, so only remove them if they're ours
+        for hook in ["post-commit", "post-checkout"] {
+            if is_mit_hook(&hooks.join(hook), hook) {
+                uninstall::unlink(&hooks, hook)?;
+            }
+        }
+
+        if cli_args.scope.is_repository() {
             mit_commit_message_lints::console::style::success(
-                "git-mit has been removed from the global template",
-                "existing repositories keep their hooks until you remove them manually",
+                "git-mit has been removed from the current repository",
+                "",
             );
         } else {
             mit_commit_message_lints::console::style::success(
-                "git-mit has been removed from the current repository",
-                "",
+                "git-mit has been removed from the global template",
+                "existing repositories keep their hooks until you remove them manually",
             );
         }
     } else {
-        install::link(&hooks, "prepare-commit-msg")?;
-        install::link(&hooks, "pre-commit")?;
-        install::link(&hooks, "commit-msg")?;
-
-        if cli_args.scope.is_global() {
-            mit_commit_message_lints::console::style::success(
-                "git-mit will be added for newly created or cloned repositories",
-                "inside existing repositories run \"git init\" to set them up",
+        let skipped = install::link_all(
+            &hooks,
+            &["prepare-commit-msg", "pre-commit", "commit-msg"],
+            &["post-commit", "post-checkout"],
+        )?;
+
+        for hook in skipped {
+            let missing = if hook.ends_with("post-commit") {
+                "sessions won't be extended when you commit"
+            } else {
+                "sessions kept for a branch won't be put back when you switch to it"
+            };
+            mit_commit_message_lints::console::style::warning(
+                &format!("skipped {}, it already exists", hook.display()),
+                Some(&format!(
+                    "{missing}, remove it and install again if you need this"
+                )),
             );
-        } else {
+        }
+
+        if cli_args.scope.is_repository() {
             mit_commit_message_lints::console::style::success(
                 "git-mit is setup for the current repository",
                 indoc! {r#"
@@ -89,6 +107,11 @@ fn main() -> Result<()> {
                     git mit-install --scope=global
                 "#},
             );
+        } else {
+            mit_commit_message_lints::console::style::success(
+                "git-mit will be added for newly created or cloned repositories",
+                "inside existing repositories run \"git init\" to set them up",
+            );
         }
 
         mit_commit_message_lints::console::style::success(
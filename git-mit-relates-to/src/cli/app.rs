@@ -6,11 +6,23 @@ use clap_complete::Shell;
 #[clap(bin_name = "git-mit-relates-to")]
 pub struct Args {
     /// The issue number or other string to place into the Relates-to trailer
-    #[clap(required_unless_present = "completion")]
+    #[clap(required_unless_present_any = ["completion", "clear"])]
     pub issue_number: Option<String>,
-    /// Number of minutes to expire the configuration in
+    /// Stop adding the Relates-to trailer to commits
+    #[clap(long, conflicts_with = "issue_number")]
+    pub clear: bool,
+    /// When to expire the configuration: minutes like `90`, a length like
+    /// `2h` or `1h30m`, a time like `17:30`, `end-of-day` or `end-of-week`
     #[clap(long, short, env = "GIT_MIT_RELATES_TO_TIMEOUT", default_value = "60")]
-    pub timeout: u64,
+    pub timeout: String,
+    /// Keep the relates-to for the branch that's checked out, rather than the
+    /// whole repository
+    #[clap(long)]
+    pub branch: bool,
+    /// Keep the relates-to for this worktree, rather than every worktree of
+    /// the repository
+    #[clap(long)]
+    pub worktree: bool,
 
     #[clap(long, value_enum, value_parser)]
     pub completion: Option<Shell>,
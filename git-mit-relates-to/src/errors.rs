@@ -6,4 +6,20 @@ pub enum GitRelatesTo {
     #[error("not relates to message set")]
     #[diagnostic(code(git_mit_relates_to::errors::git_relates_to::no_relates_to_message_set))]
     NoRelatesToMessageSet,
+    #[error("no branch checked out")]
+    #[diagnostic(
+        code(git_mit_relates_to::errors::git_relates_to::no_branch),
+        help(
+            "the relates-to can only be kept for a branch while one is checked out, check out a branch or run `git mit-relates-to` without `--branch`"
+        )
+    )]
+    NoBranch,
+    #[error("not in a git repository")]
+    #[diagnostic(
+        code(git_mit_relates_to::errors::git_relates_to::outside_repository),
+        help(
+            "the relates-to can only be kept for a worktree of a git repository, run `git mit-relates-to` from one or without `--worktree`"
+        )
+    )]
+    OutsideRepository,
 }
@@ -19,7 +19,7 @@
 )]
 #![allow(clippy::multiple_crate_versions)]
 
-use std::{convert::TryFrom, env, io::stdout, time::Duration};
+use std::{convert::TryFrom, env, io::stdout, str::FromStr};
 
 use clap::{CommandFactory, Parser};
 use clap_complete::generate;
@@ -27,8 +27,9 @@ use git2::Repository;
 use miette::{IntoDiagnostic, Result};
 use mit_commit_message_lints::{
     console::{error_handling::miette_install, style},
-    external::Git2,
-    relates::{RelateTo, set_relates_to},
+    external::{Git2, Vcs},
+    relates::{RelateTo, clear_relates_to, set_relates_to},
+    timeout::Timeout,
 };
 
 use crate::cli::app::Args;
@@ -51,6 +52,29 @@ fn main() -> Result<()> {
         std::process::exit(0);
     }
 
+    let current_dir = env::current_dir().into_diagnostic()?;
+    let mut vcs = if cli_args.worktree {
+        if !repo_present() {
+            return Err(GitRelatesTo::OutsideRepository.into());
+        }
+
+        Git2::worktree(&current_dir)?
+    } else {
+        Git2::try_from(current_dir)?
+    };
+
+    if cli_args.branch {
+        if vcs.branch().is_none() {
+            return Err(GitRelatesTo::NoBranch.into());
+        }
+
+        vcs = vcs.scoped_to_branch();
+    }
+
+    if cli_args.clear {
+        return clear_relates_to(&mut vcs);
+    }
+
     let relates_to = cli_args.issue_number.map(RelateTo::from).map_or_else(
         || Err::<RelateTo<'_>, GitRelatesTo>(GitRelatesTo::NoRelatesToMessageSet),
         Ok,
@@ -60,13 +84,8 @@ fn main() -> Result<()> {
         not_setup_warning();
     }
 
-    let current_dir = env::current_dir().into_diagnostic()?;
-    let mut vcs = Git2::try_from(current_dir)?;
-    set_relates_to(
-        &mut vcs,
-        &relates_to,
-        Duration::from_secs(cli_args.timeout * 60),
-    )?;
+    let timeout = Timeout::from_str(&cli_args.timeout)?;
+    set_relates_to(&mut vcs, &relates_to, &timeout)?;
 
     Ok(())
 }
@@ -82,7 +101,7 @@ fn is_hook_present() -> bool {
     env::current_dir()
         .ok()
         .and_then(|path| Repository::discover(path).ok())
-        .map(|repo| repo.path().join("hooks").join("commit-msg"))
+        .map(|repo| repo.commondir().join("hooks").join("commit-msg"))
         .as_ref()
         .is_some_and(|path_buf| {
             path_buf
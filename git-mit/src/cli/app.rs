@@ -6,6 +6,11 @@ use mit_commit_message_lints::mit::AuthorArgs;
 #[derive(Parser)]
 #[clap(author, version, about)]
 #[clap(bin_name = "git-mit")]
+#[clap(
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+    disable_help_subcommand = true
+)]
 #[clap( after_help = indoc!(
     "
     COMMON TASKS:
@@ -39,12 +44,49 @@ use mit_commit_message_lints::mit::AuthorArgs;
             git mit-relates-to \"[#12345678]\"
     "
 ))]
+#[allow(clippy::struct_excessive_bools)]
 pub struct CliArgs {
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+
     /// Initials of the mit to put in the commit
-    #[clap(required_unless_present = "completion")]
+    ///
+    /// Authors that aren't configured can be given like "Jane Doe
+    /// <jane@example.com>". When run in a terminal without any, you can pick
+    /// the authors from a list
     pub initials: Vec<String>,
 
-    /// Path to a file where mit initials, emails and names can be found
+    /// Save the authors given like "Jane Doe <jane@example.com>" with these
+    /// initials, one for each author, in the same order
+    #[clap(long, value_delimiter = ',', conflicts_with = "clear")]
+    pub save: Vec<String>,
+
+    /// Use these authors as the co-authors of just the next commit, then go
+    /// back to the current session
+    #[clap(long, conflicts_with = "clear")]
+    pub once: bool,
+
+    /// End the current pairing session, removing the co-authors
+    #[clap(long, conflicts_with = "initials")]
+    pub clear: bool,
+
+    /// When clearing, also put back the git author you had before the session
+    /// started
+    #[clap(long, requires = "clear")]
+    pub restore_identity: bool,
+
+    /// Keep the session for the branch that's checked out, rather than the
+    /// whole repository
+    #[clap(long, conflicts_with = "once")]
+    pub branch: bool,
+
+    /// Keep the session for this worktree, rather than every worktree of the
+    /// repository
+    #[clap(long)]
+    pub worktree: bool,
+
+    /// Paths to files or directories where mit initials, emails and names can
+    /// be found, separated like `PATH`, later ones taking precedence
     #[clap(
         short,
         long,
@@ -59,15 +101,35 @@ pub struct CliArgs {
     #[clap(short, long, env = "GIT_MIT_AUTHORS_EXEC")]
     pub exec: Option<String>,
 
-    /// Number of minutes to expire the configuration in
+    /// When to expire the configuration: minutes like `90`, a length like
+    /// `2h` or `1h30m`, a time like `17:30`, `end-of-day` or `end-of-week`
     #[clap(short, long, env = "GIT_MIT_AUTHORS_TIMEOUT", default_value = "60")]
-    pub timeout: u64,
+    pub timeout: String,
 
     /// Shell to generate completions for
     #[clap(long, value_enum, value_parser)]
     pub completion: Option<Shell>,
 }
 
+#[derive(clap::Subcommand, Eq, PartialEq, Debug, Clone)]
+pub enum Command {
+    /// Print a short summary of the current authors for a shell prompt
+    ///
+    /// Only the git config is read, so this is fast enough to run every time
+    /// the prompt is drawn. Nothing is printed if there are no current authors.
+    Prompt {
+        /// The template to print, `{initials}`, `{remaining}` (in minutes) and
+        /// `{relates_to}` are filled in
+        #[clap(
+            short,
+            long,
+            env = "GIT_MIT_PROMPT_FORMAT",
+            default_value = "{initials} {remaining}m"
+        )]
+        format: String,
+    },
+}
+
 impl AuthorArgs for CliArgs {
     fn author_command(&self) -> Option<&str> {
         self.exec.as_deref()
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
 use miette::{Diagnostic, LabeledSpan, SourceCode};
 use thiserror::Error;
@@ -8,32 +8,65 @@ use thiserror::Error;
 #[diagnostic(help("To add an author run `git mit` from a git repository"))]
 pub struct NoRepository {}
 
+#[derive(Error, Diagnostic, Debug)]
+#[error("no branch checked out")]
+#[diagnostic(help(
+    "Sessions can only be kept for a branch while one is checked out, check out a branch or run `git mit` without `--branch`"
+))]
+pub struct NoBranch {}
+
+#[derive(Error, Diagnostic, Debug)]
+#[error("no authors picked")]
+#[diagnostic(help("Select at least one author with space before pressing enter"))]
+pub struct NoAuthorsPicked {}
+
+#[derive(Error, Diagnostic, Debug)]
+#[error("expected {expected} initials to save the authors with, got {actual}")]
+#[diagnostic(help(
+    "Give `--save` one initial for each author written like \"Jane Doe <jane@example.com>\", in the same order"
+))]
+pub struct SaveInitialsMismatch {
+    pub expected: usize,
+    pub actual: usize,
+}
+
 #[derive(Error, Debug)]
 #[error("could not find initial")]
 pub struct UnknownAuthor {
     pub command: String,
     pub missing_initials: Vec<String>,
+    /// The closest configured authors for each missing initial
+    pub suggestions: BTreeMap<String, Vec<String>>,
 }
 
 impl Diagnostic for UnknownAuthor {
     fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
-        let mut tips = vec![
-            "To see a summary of your configured authors run",
-            "`git mit-config mit generate`",
-            "To add a new author run",
-            "`git mit-config mit set eg \"Egg Sample\" egg.sample@example.com`",
+        let mut tips: Vec<String> = vec![
+            "To see a summary of your configured authors run".into(),
+            "`git mit-config mit generate`".into(),
+            "To add a new author run".into(),
+            "`git mit-config mit set eg \"Egg Sample\" egg.sample@example.com`".into(),
         ];
 
         if self.missing_initials.contains(&"config".to_string()) {
-            tips.push("Did you mean `git mit-config`");
+            tips.push("Did you mean `git mit-config`".into());
         }
 
         if self.missing_initials.contains(&"relates-to".to_string()) {
-            tips.push("Did you mean `git mit-relates-to`");
+            tips.push("Did you mean `git mit-relates-to`".into());
         }
 
         if self.missing_initials.contains(&"install".to_string()) {
-            tips.push("Did you mean `git mit-install`");
+            tips.push("Did you mean `git mit-install`".into());
+        }
+
+        for (initial, suggestions) in &self.suggestions {
+            if !suggestions.is_empty() {
+                tips.push(format!(
+                    "Instead of `{initial}` did you mean {}",
+                    suggestions.join(" or ")
+                ));
+            }
         }
 
         let help: String = tips.join(" ");
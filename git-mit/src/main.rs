@@ -19,22 +19,35 @@
 )]
 #![allow(clippy::multiple_crate_versions)]
 
-use std::{convert::TryFrom, env, io::stdout, time::Duration};
+use std::{
+    convert::TryFrom,
+    env,
+    io::{IsTerminal, stdin, stdout},
+    str::FromStr,
+};
 
-use clap::{CommandFactory, Parser};
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use clap_complete::generate;
-use errors::NoRepository;
+use errors::{NoBranch, NoRepository};
 use git2::Repository;
 use miette::{IntoDiagnostic, Result};
 use mit_commit_message_lints::{
     console::{error_handling::miette_install, style},
-    external::Git2,
-    mit::{Authors, get_authors, set_commit_authors},
+    external::{Git2, Vcs, read_head_commit},
+    mit::{
+        Author, Authors, clear_commit_authors, get_authors, get_session, set_commit_authors,
+        set_config_authors, set_once_authors,
+    },
+    timeout::Timeout,
 };
 
-use crate::{cli::app::CliArgs, errors::UnknownAuthor};
+use crate::{
+    cli::app::{CliArgs, Command},
+    errors::{SaveInitialsMismatch, UnknownAuthor},
+};
 mod cli;
 mod errors;
+mod picker;
 
 fn main() -> Result<()> {
     miette_install();
@@ -49,7 +62,23 @@ fn main() -> Result<()> {
         std::process::exit(0);
     }
 
-    let mut git_config = Git2::try_from(env::current_dir().into_diagnostic()?)?;
+    let mut git_config = open_config(&cli_args)?;
+
+    if let Some(Command::Prompt { format }) = &cli_args.command {
+        let session = get_session(&git_config)?;
+        style::to_be_piped(&session.prompt(&Authors::try_from(&git_config)?, format)?);
+
+        return Ok(());
+    }
+
+    if cli_args.clear {
+        if !repo_present() {
+            return Err(NoRepository {}.into());
+        }
+
+        return clear_commit_authors(&mut git_config, cli_args.restore_identity);
+    }
+
     let file_authors = get_authors(&cli_args)?;
     let authors = file_authors.merge(&Authors::try_from(&git_config)?);
 
@@ -61,26 +90,154 @@ fn main() -> Result<()> {
         not_setup_warning();
     }
 
-    let initials: Vec<&str> = cli_args.initials.iter().map(String::as_str).collect();
-    let missing = authors.missing_initials(initials.clone());
+    if cli_args.initials.is_empty() && !(stdin().is_terminal() && stdout().is_terminal()) {
+        CliArgs::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "the following required arguments were not provided:\n  <INITIALS>...",
+            )
+            .exit();
+    }
+
+    let ad_hoc_authors = cli_args
+        .initials
+        .iter()
+        .filter(|argument| is_ad_hoc_author(argument))
+        .map(|argument| Author::try_from(argument.as_str()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let initials: Vec<&str> = cli_args
+        .initials
+        .iter()
+        .map(String::as_str)
+        .filter(|argument| !is_ad_hoc_author(argument))
+        .collect();
+    let missing = authors.missing_initials(&initials);
 
     if !missing.is_empty() {
         return Err(UnknownAuthor {
             command: env::args().collect::<Vec<_>>().join(" "),
             missing_initials: missing.clone().into_iter().map(String::from).collect(),
+            suggestions: missing
+                .iter()
+                .map(|initial| {
+                    (
+                        (*initial).to_string(),
+                        authors
+                            .suggestions(initial)
+                            .into_iter()
+                            .map(|(initial, author)| format!("`{initial}` ({})", author.name()))
+                            .collect(),
+                    )
+                })
+                .collect(),
         }
         .into());
     }
 
-    set_commit_authors(
-        &mut git_config,
-        &authors.get(&initials),
-        Duration::from_secs(cli_args.timeout * 60),
-    )?;
+    save_ad_hoc_authors(&mut git_config, &cli_args.save, &ad_hoc_authors)?;
+
+    let selected = if cli_args.initials.is_empty() {
+        picker::pick(&authors, &get_session(&git_config)?)?
+    } else {
+        select_authors(&cli_args.initials, &authors, &ad_hoc_authors)
+    };
+
+    if cli_args.once {
+        let head = read_head_commit(env::current_dir().into_diagnostic()?)?;
+        return set_once_authors(
+            &mut git_config,
+            &selected,
+            head.as_ref().map(|commit| commit.id.as_str()),
+        );
+    }
+
+    let timeout = Timeout::from_str(&cli_args.timeout)?;
+    set_commit_authors(&mut git_config, &selected, &timeout)?;
 
     Ok(())
 }
 
+/// The config the session is kept in, for the worktree or branch if asked
+fn open_config(cli_args: &CliArgs) -> Result<Git2> {
+    let current_dir = env::current_dir().into_diagnostic()?;
+    let git_config = if cli_args.worktree {
+        if !repo_present() {
+            return Err(NoRepository {}.into());
+        }
+
+        Git2::worktree(&current_dir)?
+    } else {
+        Git2::try_from(current_dir)?
+    };
+
+    if !cli_args.branch {
+        return Ok(git_config);
+    }
+
+    if git_config.branch().is_none() {
+        return Err(NoBranch {}.into());
+    }
+
+    Ok(git_config.scoped_to_branch())
+}
+
+/// Save authors given like `Jane Doe <jane@example.com>` with the initials
+/// from `--save`
+fn save_ad_hoc_authors(
+    git_config: &mut Git2,
+    initials: &[String],
+    ad_hoc_authors: &[Author<'_>],
+) -> Result<()> {
+    if initials.is_empty() {
+        return Ok(());
+    }
+
+    if initials.len() != ad_hoc_authors.len() {
+        return Err(SaveInitialsMismatch {
+            expected: ad_hoc_authors.len(),
+            actual: initials.len(),
+        }
+        .into());
+    }
+
+    initials
+        .iter()
+        .zip(ad_hoc_authors)
+        .try_for_each(|(initial, author)| set_config_authors(git_config, initial, author))
+}
+
+/// Arguments that look like `Jane Doe <jane@example.com>` rather than initials
+fn is_ad_hoc_author(argument: &str) -> bool {
+    argument.contains(|character: char| {
+        character.is_whitespace() || matches!(character, '<' | '>' | '@')
+    })
+}
+
+/// The authors in the order they were given, each only once
+fn select_authors<'a>(
+    arguments: &'a [String],
+    authors: &'a Authors<'a>,
+    ad_hoc_authors: &'a [Author<'a>],
+) -> Vec<&'a Author<'a>> {
+    let mut ad_hoc = ad_hoc_authors.iter();
+
+    arguments
+        .iter()
+        .flat_map(|argument| {
+            if is_ad_hoc_author(argument) {
+                ad_hoc.next().into_iter().collect()
+            } else {
+                authors.get(&[argument.as_str()])
+            }
+        })
+        .fold(vec![], |mut selected, author| {
+            if !selected.contains(&author) {
+                selected.push(author);
+            }
+            selected
+        })
+}
+
 fn not_setup_warning() {
     style::warning(
         "Hooks not found in this repository, your commits won't contain trailers, and lints will not be checked",
@@ -92,7 +249,7 @@ fn is_hook_present() -> bool {
     env::current_dir()
         .ok()
         .and_then(|path| Repository::discover(path).ok())
-        .map(|repo| repo.path().join("hooks").join("commit-msg"))
+        .map(|repo| repo.commondir().join("hooks").join("commit-msg"))
         .as_ref()
         .is_some_and(|path_buf| {
             path_buf
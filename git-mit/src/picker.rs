@@ -0,0 +1,171 @@
+use std::fmt::{Display, Formatter};
+
+use inquire::{MultiSelect, Select};
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::mit::{Author, Authors, Session};
+
+use crate::errors::NoAuthorsPicked;
+
+#[derive(Clone, Copy)]
+struct Choice<'a> {
+    initial: &'a str,
+    author: &'a Author<'a>,
+}
+
+impl Display for Choice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  {} <{}>",
+            self.initial,
+            self.author.name(),
+            self.author.email()
+        )
+    }
+}
+
+/// Ask who is working on this from the configured authors
+///
+/// The authors in the current session are listed first and already
+/// selected. When more than one author is picked we then ask who the main
+/// author is, and who comes next, until everyone is in order. Pressing enter
+/// each time keeps the order they are listed in.
+pub fn pick<'a>(authors: &'a Authors<'a>, session: &Session<'_>) -> Result<Vec<&'a Author<'a>>> {
+    let active = session.active_authors();
+    let choices: Vec<Choice<'a>> = authors
+        .with_first(&active)
+        .into_iter()
+        .map(|(initial, author)| Choice { initial, author })
+        .collect();
+    let selected = preselected(&choices, &active);
+
+    let picked = MultiSelect::new("Who is working on this?", choices)
+        .with_default(&selected)
+        .with_help_message("↑↓ to move, space to select, type to filter, enter to confirm")
+        .prompt()
+        .into_diagnostic()?;
+
+    if picked.is_empty() {
+        return Err(NoAuthorsPicked {}.into());
+    }
+
+    Ok(order(picked, |position, remaining| {
+        let question = if position == 0 {
+            "Who is the main author?"
+        } else {
+            "Who is next?"
+        };
+        Select::new(question, remaining).prompt().into_diagnostic()
+    })?
+    .into_iter()
+    .map(|choice| choice.author)
+    .collect())
+}
+
+/// The positions of the choices that are in the current session
+fn preselected(choices: &[Choice<'_>], active: &[&Author<'_>]) -> Vec<usize> {
+    choices
+        .iter()
+        .enumerate()
+        .filter(|(_, choice)| {
+            active
+                .iter()
+                .any(|author| author.email().eq_ignore_ascii_case(choice.author.email()))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Put the picked authors in the order `choose` gives them
+///
+/// `choose` is given the position being filled and who is left, and is asked
+/// until only one author remains, who goes last.
+fn order<'a>(
+    mut remaining: Vec<Choice<'a>>,
+    mut choose: impl FnMut(usize, Vec<Choice<'a>>) -> Result<Choice<'a>>,
+) -> Result<Vec<Choice<'a>>> {
+    let mut ordered = Vec::with_capacity(remaining.len());
+
+    while remaining.len() > 1 {
+        let next = choose(ordered.len(), remaining.clone())?;
+        remaining.retain(|choice| choice.initial != next.initial);
+        ordered.push(next);
+    }
+
+    ordered.extend(remaining);
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use mit_commit_message_lints::mit::Author;
+
+    use super::{Choice, order, preselected};
+
+    fn authors() -> Vec<(&'static str, Author<'static>)> {
+        vec![
+            (
+                "ae",
+                Author::new("Anyone Else".into(), "anyone@example.com".into(), None),
+            ),
+            (
+                "bt",
+                Author::new("Billie Thompson".into(), "billie@example.com".into(), None),
+            ),
+            (
+                "se",
+                Author::new("Someone Else".into(), "someone@example.com".into(), None),
+            ),
+        ]
+    }
+
+    fn choices<'a>(authors: &'a [(&'static str, Author<'static>)]) -> Vec<Choice<'a>> {
+        authors
+            .iter()
+            .map(|(initial, author)| Choice { initial, author })
+            .collect()
+    }
+
+    #[test]
+    fn authors_are_in_the_order_they_are_chosen() {
+        let authors = authors();
+
+        let ordered = order(choices(&authors), |_, remaining| {
+            Ok(*remaining.last().unwrap())
+        })
+        .unwrap();
+
+        assert_eq!(
+            ordered
+                .iter()
+                .map(|choice| choice.initial)
+                .collect::<Vec<_>>(),
+            vec!["se", "bt", "ae"],
+            "Expected the first chosen to be the main author, then the next"
+        );
+    }
+
+    #[test]
+    fn a_single_author_is_not_asked_about() {
+        let authors = authors();
+
+        let ordered = order(choices(&authors[..1]), |_, _| {
+            panic!("Expected no question for a single author")
+        })
+        .unwrap();
+
+        assert_eq!(ordered.len(), 1, "Expected the one author back");
+    }
+
+    #[test]
+    fn the_session_is_preselected() {
+        let authors = authors();
+        let active = [&authors[2].1];
+
+        assert_eq!(
+            preselected(&choices(&authors), &active),
+            vec![2],
+            "Expected the author in the session to be selected"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 $Arch = "x86_64-pc-windows-msvc.exe"
 
 # Define binaries to download
-$Binaries = @("git-mit", "git-mit-config", "git-mit-install", "git-mit-relates-to", "mit-commit-msg", "mit-pre-commit", "mit-prepare-commit-msg")
+$Binaries = @("git-mit", "git-mit-config", "git-mit-install", "git-mit-relates-to", "mit-commit-msg", "mit-pre-commit", "mit-post-checkout", "mit-post-commit", "mit-prepare-commit-msg")
 
 # Create install directory if it doesn't exist
 $InstallDir = "$env:USERPROFILE\git-mit"
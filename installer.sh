@@ -19,7 +19,7 @@ TMP_DIR=$(mktemp -d)
 cd "$TMP_DIR" || exit 1
 
 # Define binaries to download
-BINARIES=("git-mit" "git-mit-config" "git-mit-install" "git-mit-relates-to" "mit-commit-msg" "mit-pre-commit" "mit-prepare-commit-msg")
+BINARIES=("git-mit" "git-mit-config" "git-mit-install" "git-mit-relates-to" "mit-commit-msg" "mit-pre-commit" "mit-post-checkout" "mit-post-commit" "mit-prepare-commit-msg")
 
 # Download and verify each binary
 for binary in "${BINARIES[@]}"; do
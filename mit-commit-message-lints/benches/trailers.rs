@@ -7,6 +7,7 @@ use mit_commit_message_lints::{
     external,
     mit::{Author, get_commit_coauthor_configuration, set_commit_authors},
     relates::{RelateTo, get_relate_to_configuration, set_relates_to},
+    timeout::Timeout,
 };
 
 const COMMIT_WITH_ALL_FEATURES: &str = indoc!(
@@ -74,7 +75,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 set_relates_to(
                     &mut vcs,
                     &RelateTo::from("#12345678"),
-                    Duration::from_secs(60 * 60),
+                    &Timeout::In(Duration::from_secs(60 * 60)),
                 )
                 .unwrap();
 
@@ -84,7 +85,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                         &Author::new("Someone Else".into(), "someone@example.com".into(), None),
                         &Author::new("Anyone Else".into(), "anyone@example.com".into(), None),
                     ],
-                    Duration::from_secs(60 * 60),
+                    &Timeout::In(Duration::from_secs(60 * 60)),
                 )
                 .unwrap();
 
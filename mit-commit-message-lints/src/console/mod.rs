@@ -1,4 +1,5 @@
 //! Tooling to create a standard CLI experience
 
 pub mod error_handling;
+pub mod output;
 pub mod style;
@@ -0,0 +1,443 @@
+//! Machine-readable output for lint problems
+
+use std::fmt::{Display, Formatter};
+
+use miette::{Diagnostic, IntoDiagnostic, Result};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::lints::{LintReport, LintSeverity};
+
+const TOOL_NAME: &str = "git-mit";
+const TOOL_URI: &str = "https://github.com/PurpleBooth/git-mit";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// How to print the problems found by the lints
+#[derive(clap::ValueEnum, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub enum OutputFormat {
+    /// Pretty output for people
+    #[default]
+    Human,
+    /// A JSON document with a list of problems
+    Json,
+    /// A SARIF log, for code scanning tools and editors
+    Sarif,
+    /// GitHub Actions workflow commands, shown as annotations
+    GithubAnnotations,
+}
+
+/// A problem in a form that can be serialised
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct ProblemOutput {
+    /// The lint that found the problem
+    pub code: Option<String>,
+    /// Whether this problem stops the commit
+    pub severity: LintSeverity,
+    /// What is wrong
+    pub message: String,
+    /// How to fix it
+    pub help: Option<String>,
+    /// Where to read more
+    pub url: Option<String>,
+    /// The parts of the commit message the problem is about
+    pub labels: Vec<LabelOutput>,
+}
+
+/// A labelled span of the commit message
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct LabelOutput {
+    /// Text shown next to the span
+    pub label: Option<String>,
+    /// Byte offset from the start of the commit message
+    pub offset: usize,
+    /// Length of the span in bytes
+    pub length: usize,
+    /// Line the span starts on, from 1
+    pub line: usize,
+    /// Column the span starts at, in characters from 1
+    pub column: usize,
+    /// Line the span ends on, from 1
+    pub end_line: usize,
+    /// Column just after the end of the span, in characters from 1
+    pub end_column: usize,
+}
+
+impl ProblemOutput {
+    /// Describe a problem, working out line and column numbers from the
+    /// commit message it was found in
+    #[must_use]
+    pub fn new(problem: &dyn Diagnostic, severity: LintSeverity, commit_message: &str) -> Self {
+        Self {
+            code: problem.code().map(|code| code.to_string()),
+            severity,
+            message: problem.to_string(),
+            help: problem.help().map(|help| help.to_string()),
+            url: problem.url().map(|url| url.to_string()),
+            labels: problem
+                .labels()
+                .map(|labels| {
+                    labels
+                        .map(|label| {
+                            let (line, column) = position(commit_message, label.offset());
+                            let (end_line, end_column) =
+                                position(commit_message, label.offset() + label.len());
+                            LabelOutput {
+                                label: label.label().map(ToString::to_string),
+                                offset: label.offset(),
+                                length: label.len(),
+                                line,
+                                column,
+                                end_line,
+                                end_column,
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The line and column of a byte offset, both counting from 1
+///
+/// Offsets inside a character are moved back to the start of it
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Describe every problem in a report, errors first
+#[must_use]
+pub fn problems(report: &LintReport, commit_message: &str) -> Vec<ProblemOutput> {
+    report
+        .errors()
+        .iter()
+        .map(|problem| ProblemOutput::new(problem.as_ref(), LintSeverity::Error, commit_message))
+        .chain(report.warnings().iter().map(|problem| {
+            ProblemOutput::new(problem.as_ref(), LintSeverity::Warning, commit_message)
+        }))
+        .collect()
+}
+
+/// Render problems as a JSON document
+///
+/// # Errors
+///
+/// If serialisation fails
+pub fn to_json(problems: &[ProblemOutput]) -> Result<String> {
+    serde_json::to_string_pretty(&json!({ "problems": problems })).into_diagnostic()
+}
+
+/// Render problems as a SARIF log
+///
+/// # Errors
+///
+/// If serialisation fails
+pub fn to_sarif(problems: &[ProblemOutput], path: &str) -> Result<String> {
+    let mut rule_ids = problems
+        .iter()
+        .filter_map(|problem| problem.code.as_deref())
+        .collect::<Vec<_>>();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    let rules = rule_ids
+        .into_iter()
+        .map(|id| {
+            let problem = problems
+                .iter()
+                .find(|problem| problem.code.as_deref() == Some(id));
+            let mut rule = json!({ "id": id });
+            if let Some(url) = problem.and_then(|problem| problem.url.as_deref()) {
+                rule["helpUri"] = json!(url);
+            }
+            if let Some(help) = problem.and_then(|problem| problem.help.as_deref()) {
+                rule["help"] = json!({ "text": help });
+            }
+            rule
+        })
+        .collect::<Vec<_>>();
+
+    let results = problems
+        .iter()
+        .map(|problem| {
+            json!({
+                "ruleId": problem.code,
+                "level": problem.severity.to_string(),
+                "message": { "text": problem.message },
+                "locations": problem.labels.iter().map(|label| {
+                    let mut location = json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": path },
+                            "region": {
+                                "startLine": label.line,
+                                "startColumn": label.column,
+                                "endLine": label.end_line,
+                                "endColumn": label.end_column,
+                            },
+                        },
+                    });
+                    if let Some(text) = &label.label {
+                        location["message"] = json!({ "text": text });
+                    }
+                    location
+                }).collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string_pretty(&json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_URI,
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    }))
+    .into_diagnostic()
+}
+
+/// Render problems as GitHub Actions workflow commands
+#[must_use]
+pub fn to_github_annotations(problems: &[ProblemOutput], path: &str) -> String {
+    problems
+        .iter()
+        .map(|problem| GithubAnnotation { problem, path }.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+struct GithubAnnotation<'a> {
+    problem: &'a ProblemOutput,
+    path: &'a str,
+}
+
+impl Display for GithubAnnotation<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let command = match self.problem.severity {
+            LintSeverity::Warning => "warning",
+            LintSeverity::Error | LintSeverity::Off => "error",
+        };
+
+        write!(f, "::{command} file={}", escape_property(self.path))?;
+        if let Some(label) = self.problem.labels.first() {
+            write!(
+                f,
+                ",line={},col={},endLine={},endColumn={}",
+                label.line, label.column, label.end_line, label.end_column
+            )?;
+        }
+        if let Some(code) = &self.problem.code {
+            write!(f, ",title={}", escape_property(code))?;
+        }
+
+        let message = self.problem.help.as_ref().map_or_else(
+            || self.problem.message.clone(),
+            |help| format!("{}\n\n{help}", self.problem.message),
+        );
+        write!(f, "::{}", escape_data(&message))
+    }
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use mit_commit::CommitMessage;
+    use mit_lint::{Lint, Lints};
+
+    use super::{
+        LabelOutput, ProblemOutput, position, problems, to_github_annotations, to_json, to_sarif,
+    };
+    use crate::lints::{LintProblem, LintReport, LintSeverity};
+
+    fn report(message: &str, lints: Vec<Lint>) -> LintReport {
+        LintReport::new(
+            mit_lint::lint(&CommitMessage::from(message), &Lints::from(lints))
+                .into_iter()
+                .map(|problem| Box::new(problem) as LintProblem)
+                .collect(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn labels_get_line_and_column_numbers() {
+        let message = indoc!(
+            "
+            Add an endpoint
+            This should have been separated
+            "
+        );
+
+        let output = problems(
+            &report(message, vec![Lint::SubjectNotSeparateFromBody]),
+            message,
+        );
+
+        let label = &output[0].labels[0];
+        assert_eq!(
+            (label.line, label.column),
+            (2, 1),
+            "Expected the label to start at the beginning of the second line"
+        );
+        assert_eq!(
+            output[0].code.as_deref(),
+            Some("SubjectNotSeparateFromBody"),
+            "Expected the code of the lint"
+        );
+    }
+
+    #[test]
+    fn json_lists_the_problems() {
+        let message = "add an endpoint\n";
+        let output = problems(&report(message, vec![Lint::SubjectNotCapitalized]), message);
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&output).unwrap()).unwrap();
+
+        assert_eq!(
+            json["problems"][0]["severity"], "error",
+            "Expected the severity to be serialised"
+        );
+        assert_eq!(
+            json["problems"][0]["labels"][0]["column"], 1,
+            "Expected the column to be serialised"
+        );
+    }
+
+    #[test]
+    fn sarif_has_a_result_per_problem() {
+        let message = "add an endpoint\n";
+        let output = problems(&report(message, vec![Lint::SubjectNotCapitalized]), message);
+
+        let sarif: serde_json::Value =
+            serde_json::from_str(&to_sarif(&output, ".git/COMMIT_EDITMSG").unwrap()).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0", "Expected a SARIF 2.1.0 log");
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["ruleId"], "SubjectNotCapitalized",
+            "Expected the result to reference the rule"
+        );
+        assert_eq!(
+            sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]
+                ["uri"],
+            ".git/COMMIT_EDITMSG",
+            "Expected the location to be the commit message file"
+        );
+    }
+
+    #[test]
+    fn sarif_leaves_out_what_is_missing() {
+        let problem = ProblemOutput {
+            code: Some("no-wip".into()),
+            severity: LintSeverity::Error,
+            message: "Don't commit WIP".into(),
+            help: None,
+            url: None,
+            labels: vec![LabelOutput {
+                label: None,
+                offset: 0,
+                length: 3,
+                line: 1,
+                column: 1,
+                end_line: 1,
+                end_column: 4,
+            }],
+        };
+
+        let sarif: serde_json::Value =
+            serde_json::from_str(&to_sarif(&[problem], ".git/COMMIT_EDITMSG").unwrap()).unwrap();
+
+        let rule = &sarif["runs"][0]["tool"]["driver"]["rules"][0];
+        assert!(
+            rule.get("helpUri").is_none() && rule.get("help").is_none(),
+            "Expected no help properties when there is no help, got {rule}"
+        );
+        let location = &sarif["runs"][0]["results"][0]["locations"][0];
+        assert!(
+            location.get("message").is_none(),
+            "Expected no message for a span without a label, got {location}"
+        );
+    }
+
+    #[test]
+    fn positions_count_characters_not_bytes() {
+        let message = "Ajouter café\nThé ou café\n";
+
+        assert_eq!(
+            position(message, "Ajouter café\nThé ou ".len()),
+            (2, 8),
+            "Expected the column to count each character once"
+        );
+        assert_eq!(
+            position(message, "Ajouter café\nTh".len() + 1),
+            (2, 3),
+            "Expected an offset inside a character to move back to its start"
+        );
+    }
+
+    #[test]
+    fn non_ascii_labels_get_line_and_column_numbers() {
+        let message = "Ajouté un café\nCeci aurait dû être séparé\n";
+
+        let output = problems(
+            &report(message, vec![Lint::SubjectNotSeparateFromBody]),
+            message,
+        );
+
+        let label = &output[0].labels[0];
+        assert_eq!(
+            (label.line, label.column, label.end_line),
+            (2, 1, 2),
+            "Expected the label to be the second line"
+        );
+        assert_eq!(
+            label.end_column,
+            "Ceci aurait dû être séparé".chars().count() + 1,
+            "Expected the end column in characters"
+        );
+    }
+
+    #[test]
+    fn github_annotations_are_escaped_workflow_commands() {
+        let problem = ProblemOutput {
+            code: Some("no-wip".into()),
+            severity: LintSeverity::Warning,
+            message: "Don't commit WIP".into(),
+            help: Some("Finish it, 100%".into()),
+            url: None,
+            labels: vec![],
+        };
+
+        assert_eq!(
+            to_github_annotations(&[problem], "a,b:c"),
+            "::warning file=a%2Cb%3Ac,title=no-wip::Don't commit WIP%0A%0AFinish it, 100%25",
+            "Expected a warning command with escaped properties and data"
+        );
+    }
+}
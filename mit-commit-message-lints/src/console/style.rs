@@ -1,6 +1,6 @@
 //! Visual styling for the output
 
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
 use comfy_table::{
     Attribute, Cell, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
@@ -9,7 +9,10 @@ use miette::{Diagnostic, GraphicalReportHandler, Severity};
 use mit_lint::{Lint, Lints};
 use thiserror::Error;
 
-use crate::mit::Authors;
+use crate::{
+    lints::{CustomLints, LintPlugins, LintSeverity},
+    mit::{Author, Authors, RotationOption, Session},
+};
 
 /// Print an advice using our error handler tool
 ///
@@ -85,13 +88,25 @@ pub fn warning(warning: &str, tip: Option<&str>) {
     eprintln!("{out}");
 }
 
+/// Warn about lint plugins that weren't run because they aren't trusted
+pub fn untrusted_plugins(plugins: &LintPlugins) {
+    for name in plugins.untrusted() {
+        warning(
+            &format!("the lint plugin {name} from .git-mit.toml wasn't run"),
+            Some(
+                "anyone who can change the repository can change its plugins, if you trust it run `git config mit.lint.trust-plugins true`",
+            ),
+        );
+    }
+}
+
 /// Print entirely undecorated to stdout
 pub fn to_be_piped(output: &str) {
     println!("{output}");
 }
 
 /// Print a table of lints
-pub fn lint_table(list: &Lints, enabled: &Lints) {
+pub fn lint_table(list: &Lints, severities: &BTreeMap<Lint, LintSeverity>, custom: &CustomLints) {
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
@@ -99,23 +114,30 @@ pub fn lint_table(list: &Lints, enabled: &Lints) {
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec!["Lint", "Status"]);
 
-    let enabled_set: std::collections::HashSet<Lint> = enabled.clone().into_iter().collect();
-
     let rows: Table = list.clone().into_iter().fold(table, |mut table, lint| {
         table.add_row(vec![
             lint.name(),
-            if enabled_set.contains(&lint) {
-                "enabled"
-            } else {
-                "disabled"
-            },
+            lint_status(severities.get(&lint).copied().unwrap_or(LintSeverity::Off)),
         ]);
         table
     });
 
+    let rows: Table = custom.clone().into_iter().fold(rows, |mut table, lint| {
+        table.add_row(vec![lint.name(), lint_status(lint.severity())]);
+        table
+    });
+
     println!("{rows}");
 }
 
+const fn lint_status(severity: LintSeverity) -> &'static str {
+    match severity {
+        LintSeverity::Error => "enabled",
+        LintSeverity::Warning => "warning",
+        LintSeverity::Off => "disabled",
+    }
+}
+
 /// Print a table of authors
 #[must_use]
 pub fn author_table(authors: &Authors<'_>) -> String {
@@ -124,7 +146,7 @@ pub fn author_table(authors: &Authors<'_>) -> String {
         .load_preset(UTF8_FULL)
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec!["Initial", "Name", "Email", "Signing Key"]);
+        .set_header(vec!["Initial", "Name", "Email", "Signing Key", "Source"]);
 
     let rows: Table = authors
         .authors
@@ -138,6 +160,10 @@ pub fn author_table(authors: &Authors<'_>) -> String {
                     || Cell::new("None".to_string()).add_attributes(vec![Attribute::Italic]),
                     Cell::new,
                 ),
+                authors.sources().get(initial).map_or_else(
+                    || Cell::new("Unknown".to_string()).add_attributes(vec![Attribute::Italic]),
+                    Cell::new,
+                ),
             ]);
             table
         });
@@ -145,6 +171,86 @@ pub fn author_table(authors: &Authors<'_>) -> String {
     format!("{rows}")
 }
 
+/// Print a table summarising a pairing session
+#[must_use]
+pub fn session_table(session: &Session<'_>) -> String {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Setting", "Value"]);
+
+    let coauthors = session
+        .coauthors
+        .iter()
+        .map(author_line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let hooks = session
+        .hooks
+        .iter()
+        .map(|(hook, installed)| {
+            format!(
+                "{hook}: {}",
+                if *installed { "installed" } else { "missing" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    table.add_row(vec![
+        Cell::new("Author"),
+        optional_cell(session.author.as_ref().map(author_line)),
+    ]);
+    table.add_row(vec![
+        Cell::new("Co-authors"),
+        optional_cell(Some(coauthors).filter(|coauthors| !coauthors.is_empty())),
+    ]);
+    table.add_row(vec![
+        Cell::new("Branch"),
+        Cell::new(session.branch.as_deref().unwrap_or("all branches")),
+    ]);
+    table.add_row(vec![
+        Cell::new("Expires in"),
+        optional_cell(session.remaining.map(remaining)),
+    ]);
+    table.add_row(vec![
+        Cell::new("Relates to"),
+        optional_cell(session.relates_to.clone()),
+    ]);
+    table.add_row(vec![
+        Cell::new("Rotation"),
+        Cell::new(session.rotation.unwrap_or(RotationOption::Off)),
+    ]);
+    table.add_row(vec![
+        Cell::new("Hooks"),
+        optional_cell(Some(hooks).filter(|hooks| !hooks.is_empty())),
+    ]);
+
+    format!("{table}")
+}
+
+fn author_line(author: &Author<'_>) -> String {
+    format!("{} <{}>", author.name(), author.email())
+}
+
+fn remaining(seconds: i64) -> String {
+    match seconds {
+        0 => "expired".to_string(),
+        1..60 => "less than a minute".to_string(),
+        60..3600 => format!("{}m", seconds / 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+fn optional_cell(value: Option<String>) -> Cell {
+    value.map_or_else(
+        || Cell::new("None".to_string()).add_attributes(vec![Attribute::Italic]),
+        Cell::new,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use miette::{Diagnostic, Severity};
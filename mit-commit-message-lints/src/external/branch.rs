@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+
+/// The settings that make up a session, by the kind of session they're part
+/// of. Entries ending in a `.` are prefixes.
+const SESSION_KEYS: [(&str, &[&str]); 2] = [
+    (
+        "author",
+        &[
+            "mit.author.expires",
+            "mit.author.expires-timeout",
+            "mit.author.coauthors.",
+            "mit.author.user.",
+        ],
+    ),
+    (
+        "relate",
+        &[
+            "mit.relate.to",
+            "mit.relate.expires",
+            "mit.relate.expires-timeout",
+        ],
+    ),
+];
+
+/// Sessions kept for the branch that's checked out
+///
+/// The authors and relates-to can be saved for a branch, under
+/// `mit.branch.<name>.author.*` and `mit.branch.<name>.relate.*`. While the
+/// branch has its own, they're read and written instead of the ones for the
+/// whole repository.
+#[derive(Debug, Clone, Default)]
+pub struct BranchSessions {
+    branch: Option<String>,
+    scoped: bool,
+}
+
+impl BranchSessions {
+    /// Sessions for this branch, if one is checked out
+    #[must_use]
+    pub const fn new(branch: Option<String>) -> Self {
+        Self {
+            branch,
+            scoped: false,
+        }
+    }
+
+    /// Save sessions for the branch even if it doesn't have its own yet
+    #[must_use]
+    pub fn scoped(self) -> Self {
+        Self {
+            scoped: true,
+            ..self
+        }
+    }
+
+    /// The branch that's checked out
+    #[must_use]
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    /// Where a setting is kept, given a way to check whether a key is defined
+    ///
+    /// The branch name is passed through `escape`, so this can be used for
+    /// patterns too.
+    pub fn resolve<'k>(
+        &self,
+        key: &'k str,
+        defined: impl Fn(&str) -> bool,
+        escape: impl Fn(&str) -> String,
+    ) -> Cow<'k, str> {
+        let (Some(branch), Some(kind)) = (self.branch.as_deref(), session_kind(key)) else {
+            return key.into();
+        };
+
+        if !self.scoped && !defined(&format!("mit.branch.{branch}.{kind}.expires")) {
+            return key.into();
+        }
+
+        format!(
+            "mit.branch.{}.{}",
+            escape(branch),
+            key.trim_start_matches("mit.")
+        )
+        .into()
+    }
+
+    /// The name everything else uses for a setting kept for the branch
+    #[must_use]
+    pub fn unresolve(&self, key: &str) -> String {
+        self.branch
+            .as_deref()
+            .and_then(|branch| key.strip_prefix(&format!("mit.branch.{branch}.")))
+            .map_or_else(|| key.to_string(), |rest| format!("mit.{rest}"))
+    }
+}
+
+fn session_kind(key: &str) -> Option<&'static str> {
+    SESSION_KEYS.iter().find_map(|(kind, keys)| {
+        keys.iter()
+            .any(|session_key| {
+                key == *session_key || (session_key.ends_with('.') && key.starts_with(session_key))
+            })
+            .then_some(*kind)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BranchSessions;
+
+    fn resolve(sessions: &BranchSessions, key: &str, defined: &[&str]) -> String {
+        sessions
+            .resolve(key, |key| defined.contains(&key), ToString::to_string)
+            .to_string()
+    }
+
+    #[test]
+    fn only_branches_with_their_own_session_are_used() {
+        let sessions = BranchSessions::new(Some("feature".into()));
+
+        assert_eq!(
+            resolve(&sessions, "mit.author.coauthors.0.name", &[]),
+            "mit.author.coauthors.0.name",
+            "Expected the repository's session without one for the branch"
+        );
+        assert_eq!(
+            resolve(
+                &sessions,
+                "mit.author.coauthors.0.name",
+                &["mit.branch.feature.author.expires"]
+            ),
+            "mit.branch.feature.author.coauthors.0.name",
+            "Expected the branch's session once it has one"
+        );
+        assert_eq!(
+            resolve(
+                &sessions,
+                "mit.relate.to",
+                &["mit.branch.feature.author.expires"]
+            ),
+            "mit.relate.to",
+            "Expected the relates-to to be separate from the authors"
+        );
+    }
+
+    #[test]
+    fn scoped_sessions_are_always_for_the_branch() {
+        let sessions = BranchSessions::new(Some("feature".into())).scoped();
+
+        assert_eq!(
+            resolve(&sessions, "mit.relate.to", &[]),
+            "mit.branch.feature.relate.to",
+            "Expected a scoped session to be saved for the branch"
+        );
+        assert_eq!(
+            resolve(&sessions, "mit.author.config.bt.email", &[]),
+            "mit.author.config.bt.email",
+            "Expected settings that aren't part of a session to be left alone"
+        );
+        assert_eq!(
+            sessions.unresolve("mit.branch.feature.author.expires"),
+            "mit.author.expires",
+            "Expected the branch to be removed from the name"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use git2::{Config, Repository};
+use miette::{Diagnostic, IntoDiagnostic, Result};
+use thiserror::Error;
+
+use crate::external::git2::checked_out_branch;
+
+const CONFIG_KEY_TIMEOUT: &str = "mit.exec.timeout";
+const CONFIG_KEY_CACHE_TTL: &str = "mit.exec.cache-ttl";
+
+/// A command took longer than the configured timeout
+#[derive(Error, Debug, Diagnostic)]
+#[error("the command was stopped after running for {seconds}s")]
+#[diagnostic(
+    url(docsrs),
+    code(mit_commit_message_lints::external::exec::exec_timed_out),
+    help("you can give it longer with `git config mit.exec.timeout <seconds>`")
+)]
+pub struct ExecTimedOut {
+    /// The command that was run
+    #[source_code]
+    pub command: String,
+    /// How long it was given
+    pub seconds: u64,
+}
+
+/// What a command printed, and how it exited
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExecOutput {
+    /// Everything written to stdout
+    pub stdout: Vec<u8>,
+    /// The exit code, if the command wasn't stopped by a signal
+    pub exit_code: Option<i32>,
+}
+
+impl ExecOutput {
+    /// Whether the command exited successfully
+    #[must_use]
+    pub const fn success(&self) -> bool {
+        matches!(self.exit_code, Some(0))
+    }
+}
+
+/// Run a command that generates configuration, like the authors file
+///
+/// The command is given details about the repository in its environment:
+/// `GIT_MIT_REPO_ROOT`, `GIT_MIT_GIT_DIR`, `GIT_MIT_BRANCH`, `GIT_MIT_HOOK`,
+/// and `GIT_MIT_REMOTES` with a line of name and URL for each remote.
+///
+/// It's stopped after `mit.exec.timeout` seconds, if that's set. If
+/// `mit.exec.cache-ttl` is set, successful output is kept under the git
+/// directory and reused for that many seconds.
+///
+/// # Errors
+///
+/// If the command can't be run, or takes longer than the timeout
+pub fn run_exec(command: &str, current_dir: &Path, hook: Option<&str>) -> Result<ExecOutput> {
+    let repository = Repository::discover(current_dir).ok();
+    let environment = context(repository.as_ref(), hook);
+    let config = repository
+        .as_ref()
+        .map_or_else(Config::open_default, Repository::config)
+        .ok();
+    let setting = |key: &str| {
+        config
+            .as_ref()
+            .and_then(|config| config.get_i64(key).ok())
+            .and_then(|value| u64::try_from(value).ok())
+            .filter(|value| *value > 0)
+            .map(Duration::from_secs)
+    };
+    let timeout = setting(CONFIG_KEY_TIMEOUT);
+    let cache = setting(CONFIG_KEY_CACHE_TTL).and_then(|ttl| {
+        repository
+            .as_ref()
+            .map(|repository| (cache_path(repository.path(), command, &environment), ttl))
+    });
+
+    if let Some(stdout) = cache
+        .as_ref()
+        .and_then(|(path, ttl)| read_cache(path, *ttl))
+    {
+        return Ok(ExecOutput {
+            stdout,
+            exit_code: Some(0),
+        });
+    }
+
+    let output = spawn(command, &environment, timeout)?;
+
+    if let Some((path, _)) = cache.filter(|_| output.success()) {
+        write_cache(&path, &output.stdout).into_diagnostic()?;
+    }
+
+    Ok(output)
+}
+
+fn context(repository: Option<&Repository>, hook: Option<&str>) -> Vec<(&'static str, String)> {
+    let mut environment = vec![];
+
+    if let Some(hook) = hook {
+        environment.push(("GIT_MIT_HOOK", hook.to_string()));
+    }
+
+    let Some(repository) = repository else {
+        return environment;
+    };
+
+    if let Some(root) = repository.workdir() {
+        environment.push(("GIT_MIT_REPO_ROOT", root.to_string_lossy().to_string()));
+    }
+
+    environment.push((
+        "GIT_MIT_GIT_DIR",
+        repository.path().to_string_lossy().to_string(),
+    ));
+
+    if let Some(branch) = checked_out_branch(repository) {
+        environment.push(("GIT_MIT_BRANCH", branch));
+    }
+
+    let remotes = repository
+        .remotes()
+        .map(|names| {
+            names
+                .iter()
+                .flatten()
+                .flatten()
+                .filter_map(|name| {
+                    let remote = repository.find_remote(name).ok()?;
+                    Some(format!("{name} {}", remote.url().ok()?))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+    environment.push(("GIT_MIT_REMOTES", remotes));
+
+    environment
+}
+
+fn cache_path(git_dir: &Path, command: &str, environment: &[(&str, String)]) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    command.hash(&mut hasher);
+    environment.hash(&mut hasher);
+
+    git_dir
+        .join("mit")
+        .join("exec-cache")
+        .join(format!("{:016x}", hasher.finish()))
+}
+
+/// Save the output, only readable by the user, as it can be things like
+/// passwords
+fn write_cache(path: &Path, stdout: &[u8]) -> std::io::Result<()> {
+    let mut directory = fs::DirBuilder::new();
+    let mut file = fs::OpenOptions::new();
+    file.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+        directory.mode(0o700);
+        file.mode(0o600);
+    }
+
+    if let Some(parent) = path.parent() {
+        directory.recursive(true).create(parent)?;
+    }
+
+    // The mode is only used for new files, so don't reuse an old one
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => return Err(error),
+        _ => {}
+    }
+
+    file.open(path)?.write_all(stdout)
+}
+
+fn read_cache(path: &Path, ttl: Duration) -> Option<Vec<u8>> {
+    let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+
+    if age > ttl {
+        return None;
+    }
+
+    fs::read(path).ok()
+}
+
+fn spawn(
+    command: &str,
+    environment: &[(&str, String)],
+    timeout: Option<Duration>,
+) -> Result<ExecOutput> {
+    let commandline = shell_words::split(command).into_diagnostic()?;
+    let child = Command::new(commandline.first().unwrap_or(&String::new()))
+        .args(commandline.iter().skip(1))
+        .envs(environment.iter().map(|(key, value)| (*key, value)))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .into_diagnostic()?;
+
+    wait_with_timeout(child, timeout)?.ok_or_else(|| {
+        ExecTimedOut {
+            command: command.to_string(),
+            seconds: timeout.map_or(0, |timeout| timeout.as_secs()),
+        }
+        .into()
+    })
+}
+
+/// Wait for a command to finish, reading everything it prints on stdout
+///
+/// If it's still running after the timeout it's killed, and there's no
+/// output.
+///
+/// # Errors
+///
+/// If waiting for the command or reading its output fails
+pub fn wait_with_timeout(
+    mut child: Child,
+    timeout: Option<Duration>,
+) -> Result<Option<ExecOutput>> {
+    let mut stdout = child.stdout.take();
+    let reader = thread::spawn(move || {
+        let mut buffer = vec![];
+        if let Some(stdout) = stdout.as_mut() {
+            stdout.read_to_end(&mut buffer).map(|_| buffer)
+        } else {
+            Ok(buffer)
+        }
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().into_diagnostic()? {
+            break status;
+        }
+
+        if timeout.is_some_and(|timeout| started.elapsed() > timeout) {
+            child.kill().into_diagnostic()?;
+            child.wait().into_diagnostic()?;
+
+            return Ok(None);
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let stdout = reader
+        .join()
+        .map_err(|_| miette::miette!("failed to read the output of the command"))?
+        .into_diagnostic()?;
+
+    Ok(Some(ExecOutput {
+        stdout,
+        exit_code: status.code(),
+    }))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::PermissionsExt, time::Instant};
+
+    use git2::Repository;
+
+    use super::run_exec;
+
+    #[test]
+    fn the_command_is_given_the_context() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        repository.set_head("refs/heads/feature").unwrap();
+        repository
+            .remote("origin", "https://example.com/team/repo.git")
+            .unwrap();
+
+        let output = run_exec(
+            "sh -c 'echo \"$GIT_MIT_HOOK|$GIT_MIT_BRANCH|$GIT_MIT_REMOTES\"'",
+            directory.path(),
+            Some("prepare-commit-msg"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "prepare-commit-msg|feature|origin https://example.com/team/repo.git\n",
+            "Expected the hook, branch and remotes to be in the environment"
+        );
+    }
+
+    #[test]
+    fn slow_commands_are_stopped() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        repository
+            .config()
+            .unwrap()
+            .set_i64("mit.exec.timeout", 1)
+            .unwrap();
+
+        let started = Instant::now();
+        let result = run_exec("sleep 10", directory.path(), None);
+
+        assert!(result.is_err(), "Expected the command to time out");
+        assert!(
+            started.elapsed().as_secs() < 5,
+            "Expected the command to be stopped at the timeout"
+        );
+    }
+
+    #[test]
+    fn output_can_be_cached() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        repository
+            .config()
+            .unwrap()
+            .set_i64("mit.exec.cache-ttl", 60)
+            .unwrap();
+        let command = "sh -c 'echo $$'";
+
+        let first = run_exec(command, directory.path(), None).unwrap();
+        let second = run_exec(command, directory.path(), None).unwrap();
+
+        assert_eq!(
+            first, second,
+            "Expected the second run to use the cached output"
+        );
+        assert!(
+            directory.path().join(".git/mit/exec-cache").is_dir(),
+            "Expected the cache to be under the git directory"
+        );
+    }
+
+    #[test]
+    fn the_cache_is_only_readable_by_the_user() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        repository
+            .config()
+            .unwrap()
+            .set_i64("mit.exec.cache-ttl", 60)
+            .unwrap();
+
+        run_exec("echo secret", directory.path(), None).unwrap();
+
+        let cache = directory.path().join(".git/mit/exec-cache");
+        let file = fs::read_dir(&cache).unwrap().next().unwrap().unwrap();
+        assert_eq!(
+            fs::metadata(&cache).unwrap().permissions().mode() & 0o777,
+            0o700,
+            "Expected only the user to be able to open the cache"
+        );
+        assert_eq!(
+            file.metadata().unwrap().permissions().mode() & 0o777,
+            0o600,
+            "Expected only the user to be able to read the cached output"
+        );
+    }
+}
@@ -1,19 +1,30 @@
-use std::{collections::BTreeMap, convert::TryFrom, path::PathBuf};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
 
-use git2::{Config, Repository, RepositoryState};
+use git2::{Config, ConfigLevel, ErrorCode, Repository, RepositoryState};
 use miette::{IntoDiagnostic, Report, Result, miette};
 
 use crate::{
-    external::{Vcs, vcs::RepoState},
-    mit::{Author, Authors},
+    external::{Vcs, branch::BranchSessions, vcs::RepoState},
+    mit::{Author, Authors, get_config_exact_initials, get_config_groups},
 };
 
+const CONFIG_KEY_WORKTREE_CONFIG: &str = "extensions.worktreeConfig";
+const WORKTREE_SESSION_KEYS: &str = r"^(user|mit\.(author|relate|branch))\.";
+
 /// Libgit2 vcs implementation
 #[allow(missing_debug_implementations)]
 pub struct Git2 {
     config_snapshot: Config,
     config_live: Config,
+    config_target: Option<Config>,
     state: Option<RepositoryState>,
+    workdir: Option<PathBuf>,
+    branches: BranchSessions,
 }
 
 impl Git2 {
@@ -24,52 +35,174 @@ impl Git2 {
         Ok(Self {
             config_snapshot: config.snapshot().into_diagnostic()?,
             config_live: config,
+            config_target: None,
             state,
+            workdir: None,
+            branches: BranchSessions::new(None),
         })
     }
 
-    fn config_defined(&self, lint_name: &str) -> Result<bool> {
-        Ok(self
-            .config_snapshot
-            .entries(Some(lint_name))
-            .into_diagnostic()?
-            .next()
-            .is_some())
+    /// Save changes to one level of the config, like `config.worktree`,
+    /// rather than the most specific level that's loaded
+    #[must_use]
+    pub fn writing_to(self, target: Config) -> Self {
+        Self {
+            config_target: Some(target),
+            ..self
+        }
     }
 
-    fn refresh_snapshot(&mut self) -> Result<()> {
-        self.config_snapshot = self.config_live.snapshot().into_diagnostic()?;
-        Ok(())
+    /// Read the whole config, saving changes to the user's global config
+    ///
+    /// # Errors
+    ///
+    /// If there's no global config to save changes to
+    pub fn global(config: Config) -> Result<Self> {
+        let target = config
+            .open_level(ConfigLevel::Global)
+            .or_else(|_| config.open_level(ConfigLevel::XDG))
+            .into_diagnostic()?;
+
+        Ok(Self::new(config, None)?.writing_to(target))
     }
-}
 
-impl Vcs for Git2 {
-    fn entries(&self, glob: Option<&str>) -> Result<Vec<String>> {
+    /// Open the config of the worktree a directory is in, saving changes to
+    /// its `config.worktree`
+    ///
+    /// This turns on `extensions.worktreeConfig` if it isn't already, so git
+    /// reads the file too.
+    ///
+    /// # Errors
+    ///
+    /// If there's no repository, or its config can't be read or written
+    pub fn worktree(current_dir: &Path) -> Result<Self> {
+        let repository = Repository::discover(current_dir).into_diagnostic()?;
+        let mut common = repository
+            .config()
+            .and_then(|config| config.open_level(ConfigLevel::Local))
+            .into_diagnostic()?;
+
+        if !common.get_bool(CONFIG_KEY_WORKTREE_CONFIG).unwrap_or(false) {
+            common
+                .set_bool(CONFIG_KEY_WORKTREE_CONFIG, true)
+                .into_diagnostic()?;
+        }
+
+        // Extensions are read when the repository is opened
+        let repository = Repository::open(repository.path()).into_diagnostic()?;
+        let config = repository.config().into_diagnostic()?;
+        let target = config.open_level(ConfigLevel::Worktree).into_diagnostic()?;
+
+        Ok(Self::new(config, Some(repository.state()))?
+            .writing_to(target)
+            .with_workdir(repository.workdir().map(Path::to_path_buf))
+            .with_branch(checked_out_branch(&repository)))
+    }
+
+    /// Set the working directory of the repository this config is from
+    #[must_use]
+    pub fn with_workdir(self, workdir: Option<PathBuf>) -> Self {
+        Self { workdir, ..self }
+    }
+
+    /// Set the branch that's checked out in the repository this config is
+    /// from
+    #[must_use]
+    pub fn with_branch(self, branch: Option<String>) -> Self {
+        Self {
+            branches: BranchSessions::new(branch),
+            ..self
+        }
+    }
+
+    /// Save the session for the branch that's checked out, rather than the
+    /// whole repository
+    #[must_use]
+    pub fn scoped_to_branch(self) -> Self {
+        Self {
+            branches: self.branches.clone().scoped(),
+            ..self
+        }
+    }
+
+    fn config_defined(&self, name: &str) -> Result<bool> {
+        match self.config_snapshot.get_entry(name) {
+            Ok(_) => Ok(true),
+            Err(error) if error.code() == ErrorCode::NotFound => Ok(false),
+            Err(error) => Err(error).into_diagnostic(),
+        }
+    }
+
+    fn resolve<'k>(&self, key: &'k str) -> Cow<'k, str> {
+        self.branches.resolve(
+            key,
+            |key| self.config_defined(key).unwrap_or(false),
+            ToString::to_string,
+        )
+    }
+
+    fn resolve_glob<'k>(&self, glob: &'k str) -> Cow<'k, str> {
+        self.branches.resolve(
+            glob,
+            |key| self.config_defined(key).unwrap_or(false),
+            escape_regex,
+        )
+    }
+
+    fn entry_names(&self, config: &Config, glob: Option<&str>) -> Result<Vec<String>> {
+        let glob = glob.map(|glob| self.resolve_glob(glob));
         let mut entries = vec![];
-        let mut item = self.config_snapshot.entries(glob).into_diagnostic()?;
+        let mut item = config.entries(glob.as_deref()).into_diagnostic()?;
         while let Some(entry) = item.next() {
             if let Ok(name) = entry.into_diagnostic()?.name() {
-                entries.push(name.into());
+                entries.push(self.branches.unresolve(name));
             }
         }
 
         Ok(entries)
     }
 
+    fn target(&mut self) -> &mut Config {
+        self.config_target.as_mut().unwrap_or(&mut self.config_live)
+    }
+
+    fn refresh_snapshot(&mut self) -> Result<()> {
+        self.config_snapshot = self.config_live.snapshot().into_diagnostic()?;
+        Ok(())
+    }
+}
+
+impl Vcs for Git2 {
+    fn entries(&self, glob: Option<&str>) -> Result<Vec<String>> {
+        self.entry_names(&self.config_snapshot, glob)
+    }
+
+    fn target_entries(&self, glob: Option<&str>) -> Result<Vec<String>> {
+        self.entry_names(
+            self.config_target.as_ref().unwrap_or(&self.config_snapshot),
+            glob,
+        )
+    }
+
     fn get_bool(&self, name: &str) -> Result<Option<bool>> {
-        if self.config_defined(name)? {
-            Ok(Some(self.config_snapshot.get_bool(name).into_diagnostic()?))
+        let name = self.resolve(name);
+
+        if self.config_defined(&name)? {
+            Ok(Some(
+                self.config_snapshot.get_bool(&name).into_diagnostic()?,
+            ))
         } else {
             Ok(None)
         }
     }
 
     fn get_str(&self, name: &str) -> Result<Option<&str>> {
-        let defined = self.config_defined(name)?;
+        let name = self.resolve(name);
+        let defined = self.config_defined(&name)?;
 
         if defined {
             self.config_snapshot
-                .get_str(name)
+                .get_str(&name)
                 .map(Some)
                 .into_diagnostic()
         } else {
@@ -78,11 +211,12 @@ impl Vcs for Git2 {
     }
 
     fn get_i64(&self, name: &str) -> Result<Option<i64>> {
-        let defined = self.config_defined(name)?;
+        let name = self.resolve(name);
+        let defined = self.config_defined(&name)?;
 
         if defined {
             self.config_snapshot
-                .get_i64(name)
+                .get_i64(&name)
                 .map(Some)
                 .into_diagnostic()
         } else {
@@ -90,18 +224,37 @@ impl Vcs for Git2 {
         }
     }
 
+    fn get_target_str(&self, name: &str) -> Result<Option<String>> {
+        let name = self.resolve(name);
+        let target = self.config_target.as_ref().unwrap_or(&self.config_live);
+
+        match target.get_string(&name) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.code() == ErrorCode::NotFound => Ok(None),
+            Err(error) => Err(error).into_diagnostic(),
+        }
+    }
+
     fn set_str(&mut self, name: &str, value: &str) -> Result<()> {
-        self.config_live.set_str(name, value).into_diagnostic()?;
+        let name = self.resolve(name).to_string();
+        self.target().set_str(&name, value).into_diagnostic()?;
         self.refresh_snapshot()
     }
 
     fn set_i64(&mut self, name: &str, value: i64) -> Result<()> {
-        self.config_live.set_i64(name, value).into_diagnostic()?;
+        let name = self.resolve(name).to_string();
+        self.target().set_i64(&name, value).into_diagnostic()?;
         self.refresh_snapshot()
     }
 
     fn remove(&mut self, name: &str) -> Result<()> {
-        self.config_live.remove(name).into_diagnostic()?;
+        // The key might only be defined at a level we don't write to, like the
+        // global config, in which case there's nothing for us to remove
+        let name = self.resolve(name).to_string();
+        match self.target().remove(&name) {
+            Err(error) if error.code() == ErrorCode::NotFound => {}
+            result => result.into_diagnostic()?,
+        }
         self.refresh_snapshot()
     }
 
@@ -122,23 +275,83 @@ impl Vcs for Git2 {
             Some(RepositoryState::ApplyMailboxOrRebase) => Some(RepoState::ApplyMailboxOrRebase),
         }
     }
+
+    fn workdir(&self) -> Option<&Path> {
+        self.workdir.as_deref()
+    }
+
+    fn branch(&self) -> Option<&str> {
+        self.branches.branch()
+    }
+}
+
+/// Config entries are matched with a regular expression, so branch names
+/// need their special characters escaping
+fn escape_regex(text: &str) -> String {
+    text.chars()
+        .flat_map(|character| {
+            let escape = "\\.+*?()|[]{}^$".contains(character).then_some('\\');
+            escape.into_iter().chain([character])
+        })
+        .collect()
 }
 
 impl TryFrom<PathBuf> for Git2 {
     type Error = Report;
 
     fn try_from(current_dir: PathBuf) -> Result<Self, Self::Error> {
-        let (config, state) = Repository::discover(current_dir)
+        let (config, state, workdir, branch) = Repository::discover(current_dir)
             .and_then(|repo| {
                 let state = repo.state();
-                repo.config().map(|config| (config, Some(state)))
+                let workdir = repo.workdir().map(Path::to_path_buf);
+                let branch = checked_out_branch(&repo);
+                repo.config()
+                    .map(|config| (config, Some(state), workdir, branch))
             })
-            .or_else(|_| Config::open_default().map(|config| (config, None)))
+            .or_else(|_| Config::open_default().map(|config| (config, None, None, None)))
             .into_diagnostic()?;
-        Self::new(config, state)
+        let target = session_target(&config);
+        let git2 = Self::new(config, state)?
+            .with_workdir(workdir)
+            .with_branch(branch);
+
+        Ok(match target {
+            Some(target) => git2.writing_to(target),
+            None => git2,
+        })
     }
 }
 
+/// Changes go to the worktree's config if it has a session of its own, and
+/// the repository's otherwise, like git does
+fn session_target(config: &Config) -> Option<Config> {
+    config
+        .open_level(ConfigLevel::Worktree)
+        .ok()
+        .filter(|worktree| {
+            worktree
+                .entries(Some(WORKTREE_SESSION_KEYS))
+                .is_ok_and(|mut entries| entries.next().is_some())
+        })
+        .or_else(|| config.open_level(ConfigLevel::Local).ok())
+}
+
+/// The branch checked out in a repository, if any
+///
+/// `HEAD` is read directly, so a branch without any commits yet is still
+/// found.
+#[must_use]
+pub fn checked_out_branch(repository: &Repository) -> Option<String> {
+    repository
+        .find_reference("HEAD")
+        .ok()?
+        .symbolic_target()
+        .ok()
+        .flatten()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(String::from)
+}
+
 /// Parse a config key like `mit.author.config.bt.email` into its
 /// initial (`bt`) and part (`email`).
 ///
@@ -149,7 +362,7 @@ impl TryFrom<PathBuf> for Git2 {
 /// # Errors
 ///
 /// If the key does not contain at least an initial and a part.
-fn parse_initial_and_part(config_key: &str) -> Result<(String, String)> {
+pub fn parse_initial_and_part(config_key: &str) -> Result<(String, String)> {
     let stripped = config_key.trim_start_matches("mit.author.config.");
     let fragments: Vec<&str> = stripped.split_terminator('.').collect();
     if fragments.len() < 2 {
@@ -184,27 +397,203 @@ impl TryFrom<&'_ Git2> for Authors<'_> {
                     let name = cfg.get("name").cloned();
                     let email = cfg.get("email").cloned();
                     let signingkey: Option<String> = cfg.get("signingkey").cloned();
+                    let signingformat = cfg
+                        .get("signingformat")
+                        .and_then(|format| format.parse().ok());
 
                     match (name, email, signingkey) {
-                        (Some(name), Some(email), None) => {
-                            Some((key, Author::new(name.into(), email.into(), None)))
-                        }
+                        (Some(name), Some(email), None) => Some((
+                            key,
+                            Author::new(name.into(), email.into(), None)
+                                .with_signingformat(signingformat),
+                        )),
                         (Some(name), Some(email), Some(signingkey)) => Some((
                             key,
-                            Author::new(name.into(), email.into(), Some(signingkey.into())),
+                            Author::new(name.into(), email.into(), Some(signingkey.into()))
+                                .with_signingformat(signingformat),
                         )),
                         _ => None,
                     }
                 })
                 .map(|(key, value): (&String, Author<'_>)| (key.clone(), value))
                 .collect(),
-        ))
+        )
+        .with_groups(get_config_groups(vcs)?)
+        .with_exact_initials(get_config_exact_initials(vcs)?))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::parse_initial_and_part;
+    use std::{convert::TryFrom, path::Path};
+
+    use git2::{Config, ConfigLevel, Repository, Signature};
+
+    use super::{Git2, parse_initial_and_part};
+    use crate::external::Vcs;
+
+    fn linked_worktree(directory: &Path) -> std::path::PathBuf {
+        let repository = Repository::init(directory.join("main")).unwrap();
+        let signature = Signature::now("Billie Thompson", "billie@example.com").unwrap();
+        let tree = repository
+            .find_tree(repository.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repository
+            .commit(Some("HEAD"), &signature, &signature, "Init", &tree, &[])
+            .unwrap();
+        repository
+            .worktree("linked", &directory.join("linked"), None)
+            .unwrap();
+
+        directory.join("linked")
+    }
+
+    #[test]
+    fn worktree_sessions_are_kept_apart() {
+        let directory = tempfile::tempdir().unwrap();
+        let linked = linked_worktree(directory.path());
+
+        Git2::worktree(&linked)
+            .unwrap()
+            .set_i64("mit.author.expires", 1)
+            .unwrap();
+
+        assert_eq!(
+            Git2::try_from(linked)
+                .unwrap()
+                .get_i64("mit.author.expires")
+                .unwrap(),
+            Some(1),
+            "Expected the worktree to see its own session"
+        );
+        assert_eq!(
+            Git2::try_from(directory.path().join("main"))
+                .unwrap()
+                .get_i64("mit.author.expires")
+                .unwrap(),
+            None,
+            "Expected other worktrees not to see it"
+        );
+        assert!(
+            directory
+                .path()
+                .join("main/.git/worktrees/linked/config.worktree")
+                .is_file(),
+            "Expected the session to be in the worktree's config"
+        );
+    }
+
+    #[test]
+    fn worktrees_with_a_session_keep_changes_to_themselves() {
+        let directory = tempfile::tempdir().unwrap();
+        let linked = linked_worktree(directory.path());
+        Git2::worktree(&linked)
+            .unwrap()
+            .set_i64("mit.author.expires", 1)
+            .unwrap();
+
+        Git2::try_from(linked)
+            .unwrap()
+            .set_i64("mit.author.expires", 2)
+            .unwrap();
+
+        assert_eq!(
+            Git2::try_from(directory.path().join("main"))
+                .unwrap()
+                .get_i64("mit.author.expires")
+                .unwrap(),
+            None,
+            "Expected the change to stay in the worktree"
+        );
+    }
+
+    #[test]
+    fn branches_with_special_characters_keep_their_session() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let signature = Signature::now("Billie Thompson", "billie@example.com").unwrap();
+        let tree = repository
+            .find_tree(repository.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let commit = repository
+            .commit(Some("HEAD"), &signature, &signature, "Init", &tree, &[])
+            .unwrap();
+        repository
+            .branch(
+                "release-1.2",
+                &repository.find_commit(commit).unwrap(),
+                false,
+            )
+            .unwrap();
+        repository.set_head("refs/heads/release-1.2").unwrap();
+
+        let mut vcs = Git2::try_from(directory.path().to_path_buf())
+            .unwrap()
+            .scoped_to_branch();
+        vcs.set_i64("mit.author.expires", 1).unwrap();
+        vcs.set_str("mit.author.coauthors.se.name", "Someone Else")
+            .unwrap();
+
+        let config = repository.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config.get_i64("mit.branch.release-1.2.author.expires").ok(),
+            Some(1),
+            "Expected the session to be saved under the branch's own name"
+        );
+
+        let vcs = Git2::try_from(directory.path().to_path_buf()).unwrap();
+        assert_eq!(
+            vcs.get_i64("mit.author.expires").unwrap(),
+            Some(1),
+            "Expected the branch's session to be read back"
+        );
+        assert_eq!(
+            vcs.entries(Some("mit.author.coauthors.*")).unwrap(),
+            vec!["mit.author.coauthors.se.name".to_string()],
+            "Expected the branch's co-authors to be found"
+        );
+    }
+
+    #[test]
+    fn target_values_ignore_the_other_levels() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut config = Config::new().unwrap();
+        config
+            .add_file(&directory.path().join("global"), ConfigLevel::Global, false)
+            .unwrap();
+        config
+            .add_file(&directory.path().join("local"), ConfigLevel::Local, false)
+            .unwrap();
+        config.set_str("user.name", "Billie Thompson").unwrap();
+        config
+            .open_level(ConfigLevel::Global)
+            .unwrap()
+            .set_str("user.email", "billie@example.com")
+            .unwrap();
+        let target = config.open_level(ConfigLevel::Local).unwrap();
+        let vcs = Git2::new(config, None).unwrap().writing_to(target);
+
+        assert_eq!(
+            vcs.get_str("user.email").unwrap(),
+            Some("billie@example.com"),
+            "Expected reads to see every level"
+        );
+        assert_eq!(
+            vcs.get_target_str("user.email").unwrap(),
+            None,
+            "Expected the global email not to be in the target"
+        );
+        assert_eq!(
+            vcs.get_target_str("user.name").unwrap(),
+            Some("Billie Thompson".to_string()),
+            "Expected the local name to be in the target"
+        );
+        assert_eq!(
+            vcs.target_entries(Some("user.*")).unwrap(),
+            vec!["user.name".to_string()],
+            "Expected only the local entries"
+        );
+    }
 
     #[test]
     fn parses_simple_initials() {
@@ -0,0 +1,119 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use git2::Repository;
+use miette::{IntoDiagnostic, Result};
+
+/// The hooks that `git mit-install` installs
+pub const MIT_HOOKS: [&str; 5] = [
+    "prepare-commit-msg",
+    "pre-commit",
+    "commit-msg",
+    "post-commit",
+    "post-checkout",
+];
+
+/// Find out which of the git-mit hooks are installed in a repository
+///
+/// A hook counts as installed if it's a link to the matching `mit-` binary,
+/// or a wrapper script that runs it. The hooks directory honours
+/// `core.hooksPath`.
+///
+/// # Errors
+///
+/// If the path isn't in a git repository, or its config can't be read
+pub fn installed_hooks(path: &Path) -> Result<BTreeMap<String, bool>> {
+    let repository = Repository::discover(path).into_diagnostic()?;
+    let hooks_dir = repository
+        .config()
+        .into_diagnostic()?
+        .get_path("core.hooksPath")
+        .unwrap_or_else(|_| repository.commondir().join("hooks"));
+
+    Ok(MIT_HOOKS
+        .iter()
+        .map(|hook| (hook.to_string(), is_mit_hook(&hooks_dir.join(hook), hook)))
+        .collect())
+}
+
+/// Whether the hook at a path runs the matching `mit-` binary
+#[must_use]
+pub fn is_mit_hook(hook_path: &Path, hook: &str) -> bool {
+    let binary = format!("mit-{hook}");
+
+    hook_path
+        .canonicalize()
+        .is_ok_and(|path| path.to_string_lossy().contains(&binary))
+        || fs::read_to_string(hook_path).is_ok_and(|content| content.contains(&binary))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use git2::{Repository, Signature};
+
+    use super::installed_hooks;
+
+    #[test]
+    fn hooks_that_do_not_run_mit_are_not_installed() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let hooks = repository.path().join("hooks");
+        fs::create_dir_all(&hooks).unwrap();
+        fs::write(
+            hooks.join("commit-msg"),
+            "#!/bin/sh\nexec \"/usr/bin/mit-commit-msg\" \"$@\"\n",
+        )
+        .unwrap();
+        fs::write(hooks.join("pre-commit"), "#!/bin/sh\nexit 0\n").unwrap();
+
+        let installed = installed_hooks(dir.path()).unwrap();
+
+        assert_eq!(
+            installed.get("commit-msg"),
+            Some(&true),
+            "Expected a wrapper running mit-commit-msg to count as installed"
+        );
+        assert_eq!(
+            installed.get("pre-commit"),
+            Some(&false),
+            "Expected a hook not running mit-pre-commit to not count as installed"
+        );
+        assert_eq!(
+            installed.get("prepare-commit-msg"),
+            Some(&false),
+            "Expected a missing hook to not count as installed"
+        );
+    }
+
+    #[test]
+    fn hooks_are_shared_by_every_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = Repository::init(dir.path().join("main")).unwrap();
+        let signature = Signature::now("Billie Thompson", "billie@example.com").unwrap();
+        let tree = repository
+            .find_tree(repository.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repository
+            .commit(Some("HEAD"), &signature, &signature, "Init", &tree, &[])
+            .unwrap();
+        repository
+            .worktree("linked", &dir.path().join("linked"), None)
+            .unwrap();
+        let hooks = repository.path().join("hooks");
+        fs::create_dir_all(&hooks).unwrap();
+        fs::write(
+            hooks.join("commit-msg"),
+            "#!/bin/sh\nexec \"/usr/bin/mit-commit-msg\" \"$@\"\n",
+        )
+        .unwrap();
+
+        let installed = installed_hooks(&dir.path().join("linked")).unwrap();
+
+        assert_eq!(
+            installed.get("commit-msg"),
+            Some(&true),
+            "Expected the repository's hooks to be found from a linked worktree"
+        );
+    }
+}
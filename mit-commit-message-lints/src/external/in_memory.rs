@@ -1,11 +1,16 @@
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
 
 use glob::Pattern;
 use miette::{IntoDiagnostic, Report, Result, miette};
 
 use crate::{
-    external::{Vcs, vcs::RepoState},
-    mit::{Author, Authors},
+    external::{Vcs, branch::BranchSessions, vcs::RepoState},
+    mit::{Author, Authors, get_config_exact_initials, get_config_groups},
 };
 
 /// An in memory vcs implementation
@@ -14,19 +19,67 @@ use crate::{
 #[derive(Debug)]
 pub struct InMemory<'a> {
     store: &'a mut BTreeMap<String, String>,
+    workdir: Option<PathBuf>,
+    branches: BranchSessions,
 }
 
 impl InMemory<'_> {
     /// Create a new in memory vcs
     #[must_use]
     pub const fn new(store: &mut BTreeMap<String, String>) -> InMemory<'_> {
-        InMemory { store }
+        InMemory {
+            store,
+            workdir: None,
+            branches: BranchSessions::new(None),
+        }
+    }
+
+    /// Pretend the config is from a repository in this working directory
+    #[must_use]
+    pub fn with_workdir(self, workdir: PathBuf) -> Self {
+        Self {
+            workdir: Some(workdir),
+            ..self
+        }
+    }
+
+    /// Pretend this branch is checked out
+    #[must_use]
+    pub fn with_branch(self, branch: &str) -> Self {
+        Self {
+            branches: BranchSessions::new(Some(branch.into())),
+            ..self
+        }
+    }
+
+    /// Save the session for the branch that's checked out
+    #[must_use]
+    pub fn scoped_to_branch(self) -> Self {
+        Self {
+            branches: self.branches.clone().scoped(),
+            ..self
+        }
+    }
+
+    fn resolve<'k>(&self, key: &'k str) -> Cow<'k, str> {
+        self.branches
+            .resolve(key, |key| self.store.contains_key(key), ToString::to_string)
+    }
+
+    fn resolve_glob<'k>(&self, glob: &'k str) -> Cow<'k, str> {
+        self.branches
+            .resolve(glob, |key| self.store.contains_key(key), Pattern::escape)
     }
 }
 
 impl Vcs for InMemory<'_> {
     fn entries(&self, glob: Option<&str>) -> Result<Vec<String>> {
-        let compiled_glob = glob.map(Pattern::new).transpose().into_diagnostic()?;
+        let glob = glob.map(|glob| self.resolve_glob(glob));
+        let compiled_glob = glob
+            .as_deref()
+            .map(Pattern::new)
+            .transpose()
+            .into_diagnostic()?;
 
         Ok(self
             .store
@@ -36,46 +89,60 @@ impl Vcs for InMemory<'_> {
                     .as_ref()
                     .is_none_or(|pattern| pattern.matches(key))
             })
-            .cloned()
+            .map(|key| self.branches.unresolve(key))
             .collect())
     }
 
     fn get_bool(&self, name: &str) -> Result<Option<bool>> {
-        match self.store.get(name) {
+        match self.store.get(self.resolve(name).as_ref()) {
             None => Ok(None),
             Some(raw_value) => Ok(Some(raw_value.parse().into_diagnostic()?)),
         }
     }
 
     fn get_str(&self, name: &str) -> Result<Option<&str>> {
-        Ok(self.store.get(name).map(String::as_str))
+        Ok(self
+            .store
+            .get(self.resolve(name).as_ref())
+            .map(String::as_str))
     }
 
     fn get_i64(&self, name: &str) -> Result<Option<i64>> {
-        match self.store.get(name) {
+        match self.store.get(self.resolve(name).as_ref()) {
             None => Ok(None),
             Some(raw_value) => Ok(Some(raw_value.parse().into_diagnostic()?)),
         }
     }
 
     fn set_str(&mut self, name: &str, value: &str) -> Result<()> {
-        self.store.insert(name.into(), value.into());
+        let name = self.resolve(name).to_string();
+        self.store.insert(name, value.into());
         Ok(())
     }
 
     fn set_i64(&mut self, name: &str, value: i64) -> Result<()> {
-        self.store.insert(name.into(), format!("{value}"));
+        let name = self.resolve(name).to_string();
+        self.store.insert(name, format!("{value}"));
         Ok(())
     }
 
     fn remove(&mut self, name: &str) -> Result<()> {
-        self.store.remove(name);
+        let name = self.resolve(name).to_string();
+        self.store.remove(&name);
         Ok(())
     }
 
     fn state(&self) -> Option<RepoState> {
         None
     }
+
+    fn workdir(&self) -> Option<&Path> {
+        self.workdir.as_deref()
+    }
+
+    fn branch(&self) -> Option<&str> {
+        self.branches.branch()
+    }
 }
 
 impl TryFrom<&'_ InMemory<'_>> for Authors<'_> {
@@ -118,20 +185,28 @@ impl TryFrom<&'_ InMemory<'_>> for Authors<'_> {
                     let name = cfg.get("name").cloned();
                     let email = cfg.get("email").cloned();
                     let signingkey: Option<String> = cfg.get("signingkey").cloned();
+                    let signingformat = cfg
+                        .get("signingformat")
+                        .and_then(|format| format.parse().ok());
 
                     match (name, email, signingkey) {
-                        (Some(name), Some(email), None) => {
-                            Some((key, Author::new(name.into(), email.into(), None)))
-                        }
+                        (Some(name), Some(email), None) => Some((
+                            key,
+                            Author::new(name.into(), email.into(), None)
+                                .with_signingformat(signingformat),
+                        )),
                         (Some(name), Some(email), Some(signingkey)) => Some((
                             key,
-                            Author::new(name.into(), email.into(), Some(signingkey.into())),
+                            Author::new(name.into(), email.into(), Some(signingkey.into()))
+                                .with_signingformat(signingformat),
                         )),
                         _ => None,
                     }
                 })
                 .map(|(key, value): (&String, Author<'_>)| (key.clone(), value))
                 .collect(),
-        ))
+        )
+        .with_groups(get_config_groups(vcs)?)
+        .with_exact_initials(get_config_exact_initials(vcs)?))
     }
 }
@@ -3,13 +3,21 @@
 pub use self::{
     commit_message_path::resolve_commit_message_path,
     config::read_toml,
-    git2::Git2,
+    exec::{ExecOutput, ExecTimedOut, run_exec},
+    git2::{Git2, checked_out_branch},
+    hooks::{MIT_HOOKS, installed_hooks, is_mit_hook},
     in_memory::InMemory,
+    revision_range::{RangeCommit, read_head_commit, read_revision_range},
     vcs::{Error, RepoState, Vcs},
 };
+pub(crate) use self::{exec::wait_with_timeout, git2::parse_initial_and_part};
 
+mod branch;
 mod commit_message_path;
 mod config;
+mod exec;
 mod git2;
+mod hooks;
 mod in_memory;
+mod revision_range;
 mod vcs;
@@ -0,0 +1,249 @@
+use std::path::PathBuf;
+
+use git2::{ErrorCode, Oid, Repository, RevparseMode, Sort, Tree};
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+/// A commit that has already been made
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RangeCommit {
+    /// The full SHA of the commit
+    pub id: String,
+    /// The commit message
+    pub message: String,
+    /// The lint config file as it was in this commit, empty if there wasn't
+    /// one
+    pub toml: String,
+}
+
+/// Read the commits in a revision range, oldest first
+///
+/// The range can either be a single revision like `HEAD`, which reads just
+/// that commit, a range like `origin/main..HEAD`, or the commits on both
+/// sides since they diverged like `origin/main...HEAD`
+///
+/// # Errors
+///
+/// If we can't find a git repository, or the range doesn't resolve to commits
+pub fn read_revision_range(path: PathBuf, range: &str) -> Result<Vec<RangeCommit>> {
+    let repository = Repository::discover(path)
+        .into_diagnostic()
+        .wrap_err("failed to work out location of repository")?;
+
+    let spec = repository
+        .revparse(range)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to find the revision {range}"))?;
+
+    let ids: Vec<Oid> = match (spec.from(), spec.to()) {
+        (Some(from), Some(to)) => {
+            let from = from.peel_to_commit().into_diagnostic()?.id();
+            let to = to.peel_to_commit().into_diagnostic()?.id();
+            let mut revwalk = repository.revwalk().into_diagnostic()?;
+            revwalk
+                .set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)
+                .into_diagnostic()?;
+            revwalk.push(to).into_diagnostic()?;
+
+            if spec.mode().contains(RevparseMode::MERGE_BASE) {
+                // `a...b` is the commits on either side since they diverged
+                revwalk.push(from).into_diagnostic()?;
+                for base in repository.merge_bases(from, to).into_diagnostic()?.iter() {
+                    revwalk.hide(*base).into_diagnostic()?;
+                }
+            } else {
+                revwalk.hide(from).into_diagnostic()?;
+            }
+
+            revwalk.collect::<Result<_, _>>().into_diagnostic()?
+        }
+        (Some(single), None) | (None, Some(single)) => {
+            vec![
+                single
+                    .peel_to_commit()
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("failed to find the revision {range}"))?
+                    .id(),
+            ]
+        }
+        (None, None) => vec![],
+    };
+
+    ids.into_iter()
+        .map(|id| {
+            let commit = repository.find_commit(id).into_diagnostic()?;
+            let tree = commit.tree().into_diagnostic()?;
+
+            Ok(RangeCommit {
+                id: id.to_string(),
+                message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+                toml: read_toml_from_tree(&repository, &tree)?,
+            })
+        })
+        .collect()
+}
+
+/// Read the commit `HEAD` points at, if there is one
+///
+/// # Errors
+///
+/// If we can't find a git repository, or can't read the commit
+pub fn read_head_commit(path: PathBuf) -> Result<Option<RangeCommit>> {
+    let repository = Repository::discover(path)
+        .into_diagnostic()
+        .wrap_err("failed to work out location of repository")?;
+
+    match repository.head() {
+        Err(error) if matches!(error.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => {
+            Ok(None)
+        }
+        head => {
+            let commit = head
+                .and_then(|head| head.peel_to_commit())
+                .into_diagnostic()?;
+            let tree = commit.tree().into_diagnostic()?;
+
+            Ok(Some(RangeCommit {
+                id: commit.id().to_string(),
+                message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+                toml: read_toml_from_tree(&repository, &tree)?,
+            }))
+        }
+    }
+}
+
+fn read_toml_from_tree(repository: &Repository, tree: &Tree<'_>) -> Result<String> {
+    [".git-mit.toml", ".git-mit.toml.dist"]
+        .into_iter()
+        .find_map(|name| tree.get_name(name))
+        .map_or_else(
+            || Ok(String::new()),
+            |entry| {
+                let blob = repository.find_blob(entry.id()).into_diagnostic()?;
+                Ok(String::from_utf8_lossy(blob.content()).into_owned())
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use git2::{Repository, Signature};
+
+    use super::read_revision_range;
+
+    fn commit(repository: &Repository, files: &[(&str, &str)], message: &str) {
+        let workdir = repository.workdir().unwrap().to_path_buf();
+        let mut index = repository.index().unwrap();
+        for (name, contents) in files {
+            fs::write(workdir.join(name), contents).unwrap();
+            index.add_path(Path::new(name)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Billie Thompson", "billie@example.com").unwrap();
+        let parent = repository
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents = parent.iter().collect::<Vec<_>>();
+
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn a_range_is_read_oldest_first_with_the_config_of_each_commit() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, &[("README.md", "hello")], "Initial commit\n");
+        commit(
+            &repository,
+            &[(".git-mit.toml", "[mit.lint]\n")],
+            "Add lint config\n",
+        );
+        commit(&repository, &[("README.md", "hello world")], "Say hello\n");
+
+        let commits = read_revision_range(directory.path().into(), "HEAD~2..HEAD").unwrap();
+
+        assert_eq!(
+            commits
+                .iter()
+                .map(|commit| commit.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Add lint config\n", "Say hello\n"],
+            "Expected the commits after the base, oldest first"
+        );
+        assert_eq!(
+            commits[1].toml, "[mit.lint]\n",
+            "Expected the config file as it was in the commit"
+        );
+    }
+
+    #[test]
+    fn a_single_revision_is_just_that_commit() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, &[("README.md", "hello")], "Initial commit\n");
+        commit(&repository, &[("README.md", "hello world")], "Say hello\n");
+
+        let commits = read_revision_range(directory.path().into(), "HEAD").unwrap();
+
+        assert_eq!(commits.len(), 1, "Expected only the named commit");
+        assert_eq!(
+            commits[0].toml, "",
+            "Expected no config when the commit doesn't have one"
+        );
+    }
+
+    #[test]
+    fn unknown_revisions_are_an_error() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, &[("README.md", "hello")], "Initial commit\n");
+
+        assert!(
+            read_revision_range(directory.path().into(), "not-a-branch").is_err(),
+            "Expected a revision that doesn't exist to be an error"
+        );
+    }
+
+    #[test]
+    fn a_symmetric_range_is_both_sides_since_they_diverged() {
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, &[("README.md", "hello")], "Initial commit\n");
+        let head = repository.head().unwrap();
+        let main = head.shorthand().unwrap().to_string();
+        repository
+            .branch("other", &head.peel_to_commit().unwrap(), false)
+            .unwrap();
+        commit(&repository, &[("README.md", "hello world")], "Say hello\n");
+        repository.set_head("refs/heads/other").unwrap();
+        repository
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit(&repository, &[("README.md", "goodbye")], "Say goodbye\n");
+
+        let mut messages = read_revision_range(directory.path().into(), &format!("{main}...HEAD"))
+            .unwrap()
+            .into_iter()
+            .map(|commit| commit.message)
+            .collect::<Vec<_>>();
+        messages.sort();
+
+        assert_eq!(
+            messages,
+            vec!["Say goodbye\n", "Say hello\n"],
+            "Expected the commits on each side, without the shared history"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::convert::Infallible;
+use std::{convert::Infallible, path::Path};
 
 use miette::{Diagnostic, Result};
 use thiserror::Error;
@@ -9,6 +9,15 @@ pub trait Vcs {
     ///
     /// If we can't read the config entries
     fn entries(&self, glob: Option<&str>) -> Result<Vec<String>>;
+    /// The entries in the config that changes are saved to, leaving out
+    /// those only in other levels, like the global config
+    ///
+    /// # Errors
+    ///
+    /// If we can't read the config entries
+    fn target_entries(&self, glob: Option<&str>) -> Result<Vec<String>> {
+        self.entries(glob)
+    }
     /// # Errors
     ///
     /// If we can't read the config, or it's not parsable into a bool
@@ -21,6 +30,15 @@ pub trait Vcs {
     ///
     /// If we can't read the config, or it's not parsable into an i64
     fn get_i64(&self, name: &str) -> Result<Option<i64>>;
+    /// The value set in the config that changes are saved to, ignoring
+    /// values that are only set at other levels, like the global config
+    ///
+    /// # Errors
+    ///
+    /// If we can't read the config, or it's not parsable into a string
+    fn get_target_str(&self, name: &str) -> Result<Option<String>> {
+        Ok(self.get_str(name)?.map(String::from))
+    }
     /// # Errors
     ///
     /// If the config fails to write
@@ -33,11 +51,33 @@ pub trait Vcs {
     ///
     /// If the config fails to write
     fn remove(&mut self, name: &str) -> Result<()>;
+    /// Remove a value, doing nothing if it isn't set
+    ///
+    /// # Errors
+    ///
+    /// If we can't read the config, or it fails to write
+    fn remove_if_set(&mut self, name: &str) -> Result<()> {
+        if self.get_str(name)?.is_some() {
+            self.remove(name)?;
+        }
+
+        Ok(())
+    }
 
     /// The state of the repository currently
     ///
     /// None if there is no repository, and we only have config
     fn state(&self) -> Option<RepoState>;
+
+    /// The working directory of the repository
+    ///
+    /// None if there is no repository, or it has no working directory
+    fn workdir(&self) -> Option<&Path>;
+
+    /// The branch that's checked out
+    ///
+    /// None if there is no repository, or `HEAD` is detached
+    fn branch(&self) -> Option<&str>;
 }
 
 /// State of the repository
@@ -26,3 +26,4 @@ pub mod mit;
 
 pub mod relates;
 pub mod scope;
+pub mod timeout;
@@ -1,6 +1,8 @@
 pub use read_lint_config::read_from_toml_or_else_vcs;
+pub use set_severity::set_severity;
 pub use set_status::set_status;
 
 mod errors;
 mod read_lint_config;
+mod set_severity;
 mod set_status;
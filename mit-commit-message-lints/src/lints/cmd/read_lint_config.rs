@@ -1,91 +1,193 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    convert::TryInto,
+use std::{collections::BTreeMap, convert::TryFrom, str::FromStr};
+
+use miette::{Result, SourceSpan};
+use mit_lint::Lint;
+use serde::Deserialize;
+
+use crate::{
+    external::Vcs,
+    lints::{
+        cmd::errors::SerialiseLintError,
+        lib::{
+            custom_lint::CustomLint,
+            custom_lints::CustomLints,
+            lint_config::LintConfig,
+            plugin::LintPlugin,
+            plugins::LintPlugins,
+            severity::{self, LintSetting, LintSeverity},
+        },
+    },
 };
 
-use miette::{IntoDiagnostic, Result, SourceSpan};
-use mit_lint::{CONFIG_KEY_PREFIX, Lint, Lints};
+#[derive(Deserialize, Default)]
+struct LintFile {
+    #[serde(default)]
+    mit: MitTable,
+}
+
+#[derive(Deserialize, Default)]
+struct MitTable {
+    #[serde(default)]
+    lint: LintTable,
+}
 
-use crate::{external::Vcs, lints::cmd::errors::SerialiseLintError};
+#[derive(Deserialize, Default)]
+struct LintTable {
+    #[serde(default)]
+    custom: BTreeMap<String, CustomLint>,
+    #[serde(default)]
+    plugin: BTreeMap<String, LintPlugin>,
+    #[serde(flatten)]
+    settings: BTreeMap<String, LintSetting>,
+}
 
 /// # Errors
 ///
-/// If we fail to parse the toml
+/// If we fail to parse the toml, a custom lint has an invalid pattern, or a
+/// severity in the VCS is not valid
+pub fn read_from_toml_or_else_vcs(config: &str, vcs: &dyn Vcs) -> Result<LintConfig> {
+    let mut severities = try_from_vcs(vcs)?;
+    let config: LintFile = toml::from_str(config).map_err(|x| SerialiseLintError {
+        src: config.to_string(),
+        message: x.to_string(),
+        span: x
+            .span()
+            .map_or_else(|| SourceSpan::new(0.into(), 0), Into::into),
+    })?;
+    let LintTable {
+        custom,
+        plugin,
+        settings,
+    } = config.mit.lint;
+
+    for (name, setting) in settings {
+        let lint = Lint::try_from(name.as_str())?;
+        severities.insert(lint, setting.into());
+    }
+
+    Ok(LintConfig::new(
+        severities,
+        CustomLints::from(custom),
+        plugins_from_toml_and_vcs(plugin, vcs)?,
+    ))
+}
+
+const PLUGIN_PREFIX: &str = "mit.lint.plugin.";
+const PLUGIN_COMMAND_SUFFIX: &str = ".command";
+const CONFIG_KEY_TRUST_PLUGINS: &str = "mit.lint.trust-plugins";
+
+/// Combine the plugins from the toml with the ones in the VCS configuration
 ///
-/// # Panics
+/// Anyone who can change the repository can change the toml, so its plugins
+/// only run if `mit.lint.trust-plugins` is set, or their command is in the
+/// VCS configuration. Otherwise they're kept, but not run.
 ///
-/// Will panic if the lint prefix isn't delimited by dots. This should never
-/// happen as it's a constant
-pub fn read_from_toml_or_else_vcs(config: &str, vcs: &dyn Vcs) -> Result<Lints> {
-    let vcs_lints = try_from_vcs(vcs)?;
-    // contains PB  // contains lint // contains config
-    let config: BTreeMap<String, BTreeMap<String, BTreeMap<String, bool>>> = toml::from_str(config)
-        .map_err(|x| SerialiseLintError {
-            src: config.to_string(),
-            message: x.to_string(),
-            span: x
-                .span()
-                .map_or_else(|| SourceSpan::new(0.into(), 0), Into::into),
-        })?;
-
-    let lint_prefix = CONFIG_KEY_PREFIX.split('.').collect::<Vec<_>>();
-    let namespace = (*lint_prefix.first().unwrap()).to_string();
-
-    let Some(config) = config.get(&namespace) else {
-        return Ok(vcs_lints);
-    };
+/// # Errors
+/// If reading from the VCS fails, or a severity is not valid
+fn plugins_from_toml_and_vcs(
+    toml: BTreeMap<String, LintPlugin>,
+    vcs: &dyn Vcs,
+) -> Result<LintPlugins> {
+    let trusted = vcs.get_bool(CONFIG_KEY_TRUST_PLUGINS)?.unwrap_or(false);
+    let configured = plugins_from_vcs(vcs)?;
+    let from_toml = LintPlugins::from(toml)
+        .into_iter()
+        .map(|plugin| match configured.get(plugin.name()) {
+            _ if trusted => plugin.trusted(),
+            Some(configured) => plugin.with_trusted_command(configured.command().to_string()),
+            None => plugin,
+        })
+        .collect();
+
+    Ok(configured.merge(&LintPlugins::new(from_toml)))
+}
 
-    let group = (*lint_prefix.get(1).unwrap()).to_string();
+/// Read the plugins from the VCS configuration
+///
+/// These are `mit.lint.plugin.<name>.command`, with an optional
+/// `mit.lint.plugin.<name>.severity` and `mit.lint.plugin.<name>.timeout`
+///
+/// # Errors
+/// If reading from the VCS fails, or a severity is not valid
+fn plugins_from_vcs(vcs: &dyn Vcs) -> Result<LintPlugins> {
+    let mut plugins = BTreeMap::new();
 
-    let Some(lint_names) = config.get(&group) else {
-        return Ok(vcs_lints);
-    };
+    for key in vcs.entries(Some("mit.lint.plugin.*"))? {
+        let Some(name) = key
+            .strip_prefix(PLUGIN_PREFIX)
+            .and_then(|rest| rest.strip_suffix(PLUGIN_COMMAND_SUFFIX))
+        else {
+            continue;
+        };
+        let Some(command) = vcs.get_str(&key)? else {
+            continue;
+        };
+        let severity = vcs
+            .get_str(&format!("{PLUGIN_PREFIX}{name}.severity"))?
+            .map(LintSeverity::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        let plugin = LintPlugin::new(name.to_string(), command.to_string(), severity);
+        let plugin = match vcs
+            .get_i64(&format!("{PLUGIN_PREFIX}{name}.timeout"))?
+            .and_then(|seconds| u64::try_from(seconds).ok())
+        {
+            Some(seconds) => plugin.with_timeout(seconds),
+            None => plugin,
+        };
 
-    let to_add: Lints = lint_names
-        .iter()
-        .filter_map(|(key, value)| if *value { Some(key as &str) } else { None })
-        .collect::<Vec<&str>>()
-        .try_into()
-        .into_diagnostic()?;
-
-    let to_remove: Lints = lint_names
-        .iter()
-        .filter_map(|(key, value)| if *value { None } else { Some(key as &str) })
-        .collect::<Vec<&str>>()
-        .try_into()
-        .into_diagnostic()?;
-
-    Ok(vcs_lints.subtract(&to_remove).merge(&to_add))
+        plugins.insert(name.to_string(), plugin);
+    }
+
+    Ok(LintPlugins::from(plugins))
 }
 
-/// Create lints from the VCS configuration
+/// Read the severity of each lint from the VCS configuration
+///
+/// Whether a lint runs is the `mit.lint.<name>` boolean, and how serious it
+/// is `mit.lint.<name>.severity`
 ///
 /// # Errors
 /// If reading from the VCS fails
-fn try_from_vcs(config: &dyn Vcs) -> Result<Lints> {
-    Ok(Lints::new(
-        Lint::all_lints()
-            .filter_map(|lint| {
-                get_config_or_default(config, lint, lint.enabled_by_default()).transpose()
-            })
-            .collect::<Result<BTreeSet<Lint>>>()?,
-    ))
+fn try_from_vcs(config: &dyn Vcs) -> Result<BTreeMap<Lint, LintSeverity>> {
+    Lint::all_lints()
+        .map(|lint| {
+            get_config_or_default(config, lint, lint.enabled_by_default().into())
+                .map(|severity| (lint, severity))
+        })
+        .collect()
 }
 
-fn get_config_or_default(config: &dyn Vcs, lint: Lint, default: bool) -> Result<Option<Lint>> {
-    Ok(config
-        .get_bool(&lint.config_key())?
-        .or(Some(default))
-        .filter(|lint_value| lint_value == &true)
-        .map(|_| lint))
+fn get_config_or_default(
+    config: &dyn Vcs,
+    lint: Lint,
+    default: LintSeverity,
+) -> Result<LintSeverity> {
+    let key = lint.config_key();
+    let enabled = config.get_bool(&key)?;
+    let severity = config
+        .get_str(&severity::config_key(&key))?
+        .map(LintSeverity::from_str)
+        .transpose()?;
+
+    Ok(match (enabled, severity) {
+        (Some(false), _) => LintSeverity::Off,
+        (_, Some(severity)) => severity,
+        (Some(true), None) => LintSeverity::Error,
+        (None, None) => default,
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use indoc::indoc;
     use mit_lint::Lint;
     use std::collections::BTreeMap;
 
-    use crate::{external::InMemory, lints::cmd::read_lint_config::read_from_toml_or_else_vcs};
+    use crate::{
+        external::InMemory,
+        lints::{LintPlugin, LintSeverity, cmd::read_lint_config::read_from_toml_or_else_vcs},
+    };
 
     #[test]
     fn explicitly_enabled_lint_from_vcs_is_included() {
@@ -98,8 +200,247 @@ mod tests {
         // With the == → != mutation, true values would be filtered out,
         // so this lint would be missing (incorrectly).
         let found = lints
+            .lints()
             .into_iter()
             .any(|l| l == Lint::PivotalTrackerIdMissing);
         assert!(found, "Enabled lint should be included in results");
     }
+
+    #[test]
+    fn toml_toggles_override_the_vcs() {
+        let mut strings = BTreeMap::new();
+        strings.insert("mit.lint.pivotal-tracker-id-missing".into(), "true".into());
+        let vcs = InMemory::new(&mut strings);
+
+        let config = read_from_toml_or_else_vcs(
+            indoc!(
+                "
+                [mit.lint]
+                \"pivotal-tracker-id-missing\" = false
+                "
+            ),
+            &vcs,
+        )
+        .unwrap();
+
+        assert!(
+            !config
+                .lints()
+                .into_iter()
+                .any(|l| l == Lint::PivotalTrackerIdMissing),
+            "Expected the toml to disable the lint enabled in the vcs"
+        );
+    }
+
+    #[test]
+    fn custom_lints_are_read_alongside_toggles() {
+        let mut strings = BTreeMap::new();
+        let vcs = InMemory::new(&mut strings);
+
+        let config = read_from_toml_or_else_vcs(
+            indoc!(
+                r#"
+                [mit.lint]
+                "pivotal-tracker-id-missing" = true
+
+                [mit.lint.custom.component-tag]
+                pattern = '^\[[a-z]+\] '
+                target = "subject"
+                severity = "warning"
+                help = "Start the subject with a component tag like `[api]`"
+                "#
+            ),
+            &vcs,
+        )
+        .unwrap();
+
+        let custom = config
+            .custom()
+            .get("component-tag")
+            .expect("Expected the custom lint to be read");
+        assert_eq!(
+            custom.pattern(),
+            r"^\[[a-z]+\] ",
+            "Expected the pattern to be read from the toml"
+        );
+        assert_eq!(
+            custom.severity(),
+            LintSeverity::Warning,
+            "Expected the severity to be read from the toml"
+        );
+        assert!(
+            config
+                .lints()
+                .into_iter()
+                .any(|l| l == Lint::PivotalTrackerIdMissing),
+            "Expected toggles to still be read next to custom lints"
+        );
+    }
+
+    #[test]
+    fn custom_lints_with_invalid_patterns_are_an_error() {
+        let mut strings = BTreeMap::new();
+        let vcs = InMemory::new(&mut strings);
+
+        let actual = read_from_toml_or_else_vcs(
+            indoc!(
+                r#"
+                [mit.lint.custom.broken]
+                pattern = "[unclosed"
+                help = "This will never work"
+                "#
+            ),
+            &vcs,
+        );
+
+        assert!(
+            actual.is_err(),
+            "Expected an invalid pattern to fail reading the config"
+        );
+    }
+
+    #[test]
+    fn severities_are_read_from_the_vcs() {
+        let mut strings = BTreeMap::new();
+        strings.insert("mit.lint.not-conventional-commit".into(), "true".into());
+        strings.insert(
+            "mit.lint.not-conventional-commit.severity".into(),
+            "warning".into(),
+        );
+        strings.insert("mit.lint.duplicated-trailers".into(), "false".into());
+        strings.insert(
+            "mit.lint.duplicated-trailers.severity".into(),
+            "warning".into(),
+        );
+        let vcs = InMemory::new(&mut strings);
+
+        let config = read_from_toml_or_else_vcs("", &vcs).unwrap();
+
+        assert_eq!(
+            config.severity(Lint::NotConventionalCommit),
+            LintSeverity::Warning,
+            "Expected the severity in the vcs to be used"
+        );
+        assert_eq!(
+            config.severity(Lint::DuplicatedTrailers),
+            LintSeverity::Off,
+            "Expected turning a lint off to win over its severity"
+        );
+        assert_eq!(
+            config.severity(Lint::SubjectLongerThan72Characters),
+            LintSeverity::Error,
+            "Expected lints enabled by default to be errors"
+        );
+    }
+
+    #[test]
+    fn severities_in_the_toml_override_the_vcs() {
+        let mut strings = BTreeMap::new();
+        strings.insert("mit.lint.not-conventional-commit".into(), "true".into());
+        let vcs = InMemory::new(&mut strings);
+
+        let config = read_from_toml_or_else_vcs(
+            indoc!(
+                "
+                [mit.lint]
+                \"not-conventional-commit\" = \"warning\"
+                "
+            ),
+            &vcs,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.severity(Lint::NotConventionalCommit),
+            LintSeverity::Warning,
+            "Expected the toml severity to win over the vcs"
+        );
+    }
+
+    #[test]
+    fn invalid_severities_in_the_vcs_are_an_error() {
+        let mut strings = BTreeMap::new();
+        strings.insert(
+            "mit.lint.not-conventional-commit.severity".into(),
+            "loud".into(),
+        );
+        let vcs = InMemory::new(&mut strings);
+
+        assert!(
+            read_from_toml_or_else_vcs("", &vcs).is_err(),
+            "Expected an unknown severity to fail reading the config"
+        );
+    }
+
+    #[test]
+    fn plugins_are_read_from_the_vcs_and_the_toml() {
+        let mut strings = BTreeMap::new();
+        strings.insert(
+            "mit.lint.plugin.spelling.command".into(),
+            "aspell-lint".into(),
+        );
+        strings.insert("mit.lint.plugin.spelling.severity".into(), "warning".into());
+        strings.insert(
+            "mit.lint.plugin.tickets.command".into(),
+            "ticket-check".into(),
+        );
+        let vcs = InMemory::new(&mut strings);
+
+        let config = read_from_toml_or_else_vcs(
+            indoc!(
+                r#"
+                [mit.lint.plugin.tickets]
+                command = "ticket-check --strict"
+                "#
+            ),
+            &vcs,
+        )
+        .unwrap();
+
+        let spelling = config
+            .plugins()
+            .get("spelling")
+            .expect("Expected the plugin from the vcs");
+        assert_eq!(
+            spelling.severity(),
+            LintSeverity::Warning,
+            "Expected the plugin severity from the vcs"
+        );
+        assert_eq!(
+            config.plugins().get("tickets").map(LintPlugin::command),
+            Some("ticket-check"),
+            "Expected the command from the vcs to be kept"
+        );
+    }
+
+    #[test]
+    fn plugins_from_the_toml_only_run_if_the_repository_is_trusted() {
+        let toml = indoc!(
+            r#"
+            [mit.lint.plugin.tickets]
+            command = "ticket-check --strict"
+            "#
+        );
+        let mut strings = BTreeMap::new();
+        let config = read_from_toml_or_else_vcs(toml, &InMemory::new(&mut strings)).unwrap();
+
+        assert_eq!(
+            config.plugins().get("tickets").map(LintPlugin::is_trusted),
+            Some(false),
+            "Expected a plugin only in the toml not to be run"
+        );
+
+        strings.insert("mit.lint.trust-plugins".into(), "true".into());
+        let config = read_from_toml_or_else_vcs(toml, &InMemory::new(&mut strings)).unwrap();
+
+        assert_eq!(
+            config
+                .plugins()
+                .get("tickets")
+                .filter(|plugin| plugin.is_trusted())
+                .map(LintPlugin::command),
+            Some("ticket-check --strict"),
+            "Expected the toml's command to be run once the repository is trusted"
+        );
+    }
 }
@@ -0,0 +1,89 @@
+use miette::Result;
+use mit_lint::Lints;
+
+use crate::{
+    external::Vcs,
+    lints::lib::severity::{self, LintSeverity},
+};
+
+/// Set the severity of lints
+///
+/// The `mit.lint.<name>` boolean is kept as the on/off switch, so versions
+/// that predate severities can still read it
+///
+/// # Errors
+///
+/// Errors if writing to the VCS config fails
+pub fn set_severity(lints: Lints, vcs: &mut dyn Vcs, severity: LintSeverity) -> Result<()> {
+    lints.config_keys().into_iter().try_for_each(|lint| {
+        vcs.set_str(&lint, &(severity != LintSeverity::Off).to_string())?;
+        vcs.set_str(&severity::config_key(&lint), &severity.to_string())
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use mit_lint::{Lint, Lints};
+
+    use crate::{
+        external::InMemory,
+        lints::{LintSeverity, cmd::set_severity::set_severity},
+    };
+
+    #[test]
+    fn we_can_set_lints_to_warn() {
+        let mut strings = BTreeMap::new();
+        strings.insert("mit.lint.pivotal-tracker-id-missing".into(), "true".into());
+        let mut config = InMemory::new(&mut strings);
+
+        set_severity(
+            Lints::from(vec![Lint::PivotalTrackerIdMissing, Lint::NotEmojiLog]),
+            &mut config,
+            LintSeverity::Warning,
+        )
+        .unwrap();
+
+        assert_eq!(
+            strings.get("mit.lint.pivotal-tracker-id-missing"),
+            Some(&"true".to_string()),
+            "Expected the existing toggle to stay a boolean"
+        );
+        assert_eq!(
+            strings.get("mit.lint.pivotal-tracker-id-missing.severity"),
+            Some(&"warning".to_string()),
+            "Expected the severity to be kept in its own key"
+        );
+        assert_eq!(
+            strings.get("mit.lint.not-emoji-log"),
+            Some(&"true".to_string()),
+            "Expected the new lint to be turned on"
+        );
+        assert_eq!(
+            strings.get("mit.lint.not-emoji-log.severity"),
+            Some(&"warning".to_string()),
+            "Expected the new lint severity to be added"
+        );
+    }
+
+    #[test]
+    fn turning_lints_off_writes_a_false_toggle() {
+        let mut strings = BTreeMap::new();
+        let mut config = InMemory::new(&mut strings);
+
+        set_severity(
+            Lints::from(vec![Lint::DuplicatedTrailers]),
+            &mut config,
+            LintSeverity::Off,
+        )
+        .unwrap();
+
+        assert_eq!(
+            strings.get("mit.lint.duplicated-trailers"),
+            Some(&"false".to_string()),
+            "Expected older versions to see the lint as disabled"
+        );
+    }
+}
@@ -0,0 +1,14 @@
+//! Where to read the lint configuration from when checking existing commits
+
+/// Which version of the lint configuration file to use
+#[derive(clap::ValueEnum, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub enum ConfigSource {
+    /// The configuration file in the working tree
+    #[default]
+    Tree,
+    /// The configuration file as it was in each commit
+    ///
+    /// Plugins still come from the working tree and git config, as any
+    /// commit could otherwise run whatever it liked
+    Commit,
+}
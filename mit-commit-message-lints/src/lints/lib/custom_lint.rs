@@ -0,0 +1,494 @@
+use std::fmt::{Display, Formatter};
+
+use mit_commit::CommitMessage;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::lints::lib::{
+    custom_problem::CustomProblem, errors::InvalidCustomLintPattern, severity::LintSeverity,
+};
+
+/// The part of the commit message a custom lint is checked against
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomLintTarget {
+    /// The first line of the commit message
+    #[default]
+    Subject,
+    /// Each paragraph after the subject
+    Body,
+    /// Each trailer, in the form `Key: value`
+    Trailer,
+}
+
+impl Display for CustomLintTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Subject => write!(f, "subject"),
+            Self::Body => write!(f, "body"),
+            Self::Trailer => write!(f, "trailers"),
+        }
+    }
+}
+
+/// Whether the pattern must be present in, or absent from, the target
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CustomLintMode {
+    /// Fail if nothing in the target matches the pattern
+    #[default]
+    Require,
+    /// Fail if anything in the target matches the pattern
+    Forbid,
+}
+
+/// A regular expression, compiled when it's read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+struct Pattern(Regex);
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl TryFrom<String> for Pattern {
+    type Error = regex::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Regex::new(&value).map(Self)
+    }
+}
+
+impl From<Pattern> for String {
+    fn from(value: Pattern) -> Self {
+        value.0.as_str().to_string()
+    }
+}
+
+/// A lint defined by the user with a regular expression
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CustomLint {
+    #[serde(skip)]
+    name: String,
+    pattern: Pattern,
+    #[serde(default)]
+    target: CustomLintTarget,
+    #[serde(default)]
+    mode: CustomLintMode,
+    #[serde(default)]
+    severity: LintSeverity,
+    help: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl CustomLint {
+    /// Create a new custom lint
+    ///
+    /// # Errors
+    ///
+    /// If the pattern is not a valid regular expression
+    pub fn new(
+        name: String,
+        pattern: String,
+        target: CustomLintTarget,
+        mode: CustomLintMode,
+        severity: LintSeverity,
+        help: String,
+        message: Option<String>,
+    ) -> Result<Self, InvalidCustomLintPattern> {
+        let pattern = match Pattern::try_from(pattern.clone()) {
+            Ok(pattern) => pattern,
+            Err(error) => {
+                return Err(InvalidCustomLintPattern {
+                    span: (0, pattern.len()).into(),
+                    src: pattern,
+                    name,
+                    message: error.to_string(),
+                });
+            }
+        };
+
+        Ok(Self {
+            name,
+            pattern,
+            target,
+            mode,
+            severity,
+            help,
+            message,
+        })
+    }
+
+    /// Give the lint the name it was declared with
+    pub(crate) fn with_name(self, name: String) -> Self {
+        Self { name, ..self }
+    }
+
+    /// The name of the lint
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The regular expression to check against
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        self.pattern.0.as_str()
+    }
+
+    /// The part of the commit message that is checked
+    #[must_use]
+    pub const fn target(&self) -> CustomLintTarget {
+        self.target
+    }
+
+    /// Whether the pattern is required or forbidden
+    #[must_use]
+    pub const fn mode(&self) -> CustomLintMode {
+        self.mode
+    }
+
+    /// How serious a failure of this lint is
+    #[must_use]
+    pub const fn severity(&self) -> LintSeverity {
+        self.severity
+    }
+
+    /// Advice shown to the user when this lint fails
+    #[must_use]
+    pub fn help(&self) -> &str {
+        &self.help
+    }
+
+    /// Run this lint on a commit message
+    #[must_use]
+    pub fn lint(&self, commit_message: &CommitMessage<'_>) -> Option<CustomProblem> {
+        if self.severity == LintSeverity::Off {
+            return None;
+        }
+
+        let regex = &self.pattern.0;
+        let text = String::from(commit_message);
+        let fragments = self.fragments(commit_message, &text);
+
+        let labels = match self.mode {
+            CustomLintMode::Require => {
+                if fragments
+                    .iter()
+                    .any(|(_, fragment)| regex.is_match(fragment))
+                {
+                    return None;
+                }
+
+                fragments
+                    .first()
+                    .map(|(offset, fragment)| {
+                        vec![(
+                            format!("Does not match `{}`", self.pattern()),
+                            *offset,
+                            fragment.len(),
+                        )]
+                    })
+                    .unwrap_or_default()
+            }
+            CustomLintMode::Forbid => {
+                let labels = fragments
+                    .iter()
+                    .flat_map(|(offset, fragment)| {
+                        regex
+                            .find_iter(fragment)
+                            .map(|found| {
+                                (
+                                    format!("Matches `{}`", self.pattern()),
+                                    offset + found.start(),
+                                    found.len(),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+
+                if labels.is_empty() {
+                    return None;
+                }
+
+                labels
+            }
+        };
+
+        Some(CustomProblem::new(
+            self.message.clone().unwrap_or_else(|| {
+                format!(
+                    "Your commit {} does not satisfy the `{}` lint",
+                    self.target, self.name
+                )
+            }),
+            self.help.clone(),
+            self.name.clone(),
+            self.severity,
+            text,
+            labels,
+        ))
+    }
+
+    /// The pieces of text this lint checks, with their byte offset in the
+    /// whole commit message
+    fn fragments(&self, commit_message: &CommitMessage<'_>, text: &str) -> Vec<(usize, String)> {
+        let subject = commit_message.get_subject().to_string();
+        let subject_offset = text.find(&subject).unwrap_or_default();
+
+        let candidates: Vec<String> = match self.target {
+            CustomLintTarget::Subject => return vec![(subject_offset, subject)],
+            CustomLintTarget::Body => without_trailers(
+                commit_message
+                    .get_body()
+                    .iter()
+                    .map(ToString::to_string)
+                    .filter(|body| !body.trim().is_empty())
+                    .collect(),
+                commit_message.get_trailers().iter().count(),
+            ),
+            CustomLintTarget::Trailer => commit_message
+                .get_trailers()
+                .iter()
+                .cloned()
+                .map(String::from)
+                .collect(),
+        };
+
+        let mut cursor = subject_offset + subject.len();
+        candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                let offset = cursor + text.get(cursor..)?.find(&candidate)?;
+                cursor = offset + candidate.len();
+                Some((offset, candidate))
+            })
+            .collect()
+    }
+}
+
+/// The body paragraphs end with the trailers, so take those lines back off
+fn without_trailers(mut paragraphs: Vec<String>, mut trailers: usize) -> Vec<String> {
+    while trailers > 0 {
+        let Some(last) = paragraphs.pop() else {
+            break;
+        };
+        let lines = last.lines().collect::<Vec<_>>();
+
+        if lines.len() > trailers {
+            paragraphs.push(lines[..lines.len() - trailers].join("\n"));
+            break;
+        }
+
+        trailers -= lines.len();
+    }
+
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use mit_commit::CommitMessage;
+
+    use super::{CustomLint, CustomLintMode, CustomLintTarget};
+    use crate::lints::lib::severity::LintSeverity;
+
+    fn custom_lint(pattern: &str, target: CustomLintTarget, mode: CustomLintMode) -> CustomLint {
+        CustomLint::new(
+            "house-rule".into(),
+            pattern.into(),
+            target,
+            mode,
+            LintSeverity::Error,
+            "Follow the house rules".into(),
+            None,
+        )
+        .expect("pattern to be valid")
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        let actual = CustomLint::new(
+            "broken".into(),
+            "[unclosed".into(),
+            CustomLintTarget::Subject,
+            CustomLintMode::Require,
+            LintSeverity::Error,
+            "Broken".into(),
+            None,
+        );
+
+        assert!(
+            actual.is_err(),
+            "Expected an invalid regular expression to be rejected"
+        );
+    }
+
+    #[test]
+    fn lints_that_are_off_never_fail() {
+        let lint = CustomLint::new(
+            "no-wip".into(),
+            "WIP".into(),
+            CustomLintTarget::Subject,
+            CustomLintMode::Forbid,
+            LintSeverity::Off,
+            "Finish the work first".into(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            lint.lint(&CommitMessage::from("Add WIP endpoint\n")),
+            None,
+            "Expected a lint that is off not to report anything"
+        );
+    }
+
+    #[test]
+    fn a_required_subject_pattern_passes_when_present() {
+        let lint = custom_lint(
+            r"^\[[a-z]+\] ",
+            CustomLintTarget::Subject,
+            CustomLintMode::Require,
+        );
+
+        assert_eq!(
+            lint.lint(&CommitMessage::from("[api] Add an endpoint\n")),
+            None,
+            "Expected no problem when the subject matches the required pattern"
+        );
+    }
+
+    #[test]
+    fn a_required_subject_pattern_fails_when_missing() {
+        let lint = custom_lint(
+            r"^\[[a-z]+\] ",
+            CustomLintTarget::Subject,
+            CustomLintMode::Require,
+        );
+
+        let problem = lint
+            .lint(&CommitMessage::from("Add an endpoint\n"))
+            .expect("Expected a problem when the subject is missing the tag");
+
+        assert_eq!(
+            problem.code(),
+            "house-rule",
+            "Expected the problem to carry the lint name"
+        );
+        assert_eq!(
+            problem.to_string(),
+            "Your commit subject does not satisfy the `house-rule` lint",
+            "Expected a default message naming the lint"
+        );
+    }
+
+    #[test]
+    fn a_forbidden_subject_pattern_labels_the_match() {
+        let lint = custom_lint("WIP", CustomLintTarget::Subject, CustomLintMode::Forbid);
+
+        let problem = lint
+            .lint(&CommitMessage::from("Add WIP endpoint\n"))
+            .expect("Expected a problem when the subject contains WIP");
+
+        let labels = miette::Diagnostic::labels(&problem)
+            .expect("Expected labels")
+            .map(|label| (label.offset(), label.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![(4, 3)],
+            "Expected the label to point at the forbidden text"
+        );
+    }
+
+    #[test]
+    fn a_forbidden_body_pattern_labels_the_match_in_the_whole_message() {
+        let lint = custom_lint("TODO", CustomLintTarget::Body, CustomLintMode::Forbid);
+        let message = indoc!(
+            "
+            Add an endpoint
+
+            This still has a TODO in it
+            "
+        );
+
+        let problem = lint
+            .lint(&CommitMessage::from(message))
+            .expect("Expected a problem when the body contains TODO");
+
+        let labels = miette::Diagnostic::labels(&problem)
+            .expect("Expected labels")
+            .map(|label| (label.offset(), label.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![(message.find("TODO").unwrap(), 4)],
+            "Expected the label offset to be relative to the whole message"
+        );
+    }
+
+    #[test]
+    fn forbidden_body_patterns_ignore_the_trailers() {
+        let lint = custom_lint("example", CustomLintTarget::Body, CustomLintMode::Forbid);
+        let message = indoc!(
+            "
+            Add an endpoint
+
+            This is the body
+
+            Co-authored-by: Someone <someone@example.com>
+            "
+        );
+
+        assert_eq!(
+            lint.lint(&CommitMessage::from(message)),
+            None,
+            "Expected the trailers not to be checked as part of the body"
+        );
+    }
+
+    #[test]
+    fn a_required_trailer_pattern_passes_when_present() {
+        let lint = custom_lint(
+            "^Signed-off-by: ",
+            CustomLintTarget::Trailer,
+            CustomLintMode::Require,
+        );
+        let message = indoc!(
+            "
+            Add an endpoint
+
+            Signed-off-by: Billie Thompson <billie@example.com>
+            "
+        );
+
+        assert_eq!(
+            lint.lint(&CommitMessage::from(message)),
+            None,
+            "Expected no problem when a trailer matches the required pattern"
+        );
+    }
+
+    #[test]
+    fn a_required_trailer_pattern_fails_without_trailers() {
+        let lint = custom_lint(
+            "^Signed-off-by: ",
+            CustomLintTarget::Trailer,
+            CustomLintMode::Require,
+        );
+
+        assert!(
+            lint.lint(&CommitMessage::from("Add an endpoint\n"))
+                .is_some(),
+            "Expected a problem when there are no trailers at all"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::{collections::BTreeMap, vec::IntoIter};
+
+use mit_commit::CommitMessage;
+
+use crate::lints::lib::{custom_lint::CustomLint, custom_problem::CustomProblem};
+
+/// Collection of user-defined lints
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct CustomLints {
+    lints: Vec<CustomLint>,
+}
+
+impl CustomLints {
+    /// Create a new collection of custom lints
+    #[must_use]
+    pub const fn new(lints: Vec<CustomLint>) -> Self {
+        Self { lints }
+    }
+
+    /// The names of the custom lints
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.lints.iter().map(CustomLint::name).collect()
+    }
+
+    /// Get a custom lint by its name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&CustomLint> {
+        self.lints.iter().find(|lint| lint.name() == name)
+    }
+
+    /// Check if there are any custom lints
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.lints.is_empty()
+    }
+
+    /// Only keep the custom lints with these names
+    #[must_use]
+    pub fn filter(&self, names: &[&str]) -> Self {
+        Self::new(
+            self.lints
+                .iter()
+                .filter(|lint| names.contains(&lint.name()))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Run all the custom lints on a commit message
+    #[must_use]
+    pub fn lint(&self, commit_message: &CommitMessage<'_>) -> Vec<CustomProblem> {
+        self.lints
+            .iter()
+            .filter_map(|lint| lint.lint(commit_message))
+            .collect()
+    }
+
+    pub(crate) fn as_map(&self) -> BTreeMap<String, CustomLint> {
+        self.lints
+            .iter()
+            .map(|lint| (lint.name().to_string(), lint.clone()))
+            .collect()
+    }
+}
+
+impl IntoIterator for CustomLints {
+    type Item = CustomLint;
+    type IntoIter = IntoIter<CustomLint>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lints.into_iter()
+    }
+}
+
+impl From<BTreeMap<String, CustomLint>> for CustomLints {
+    fn from(value: BTreeMap<String, CustomLint>) -> Self {
+        Self::new(
+            value
+                .into_iter()
+                .map(|(name, lint)| lint.with_name(name))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use mit_commit::CommitMessage;
+
+    use super::CustomLints;
+    use crate::lints::lib::{
+        custom_lint::{CustomLint, CustomLintMode, CustomLintTarget},
+        severity::LintSeverity,
+    };
+
+    fn no_wip() -> CustomLint {
+        CustomLint::new(
+            "no-wip".into(),
+            "WIP".into(),
+            CustomLintTarget::Subject,
+            CustomLintMode::Forbid,
+            LintSeverity::Error,
+            "Finish the work first".into(),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_runs_every_lint() {
+        let lints = CustomLints::new(vec![no_wip()]);
+
+        let problems = lints.lint(&CommitMessage::from("WIP: something\n"));
+
+        assert_eq!(
+            problems.len(),
+            1,
+            "Expected the failing custom lint to report a problem"
+        );
+    }
+
+    #[test]
+    fn names_come_from_the_config_keys() {
+        let mut map = BTreeMap::new();
+        let lint: CustomLint = toml::from_str(
+            r#"
+            pattern = "WIP"
+            help = "Finish the work first"
+            "#,
+        )
+        .unwrap();
+        map.insert("no-wip".to_string(), lint);
+
+        let lints = CustomLints::from(map);
+
+        assert_eq!(
+            lints.names(),
+            vec!["no-wip"],
+            "Expected the lint to be named after its key"
+        );
+    }
+
+    #[test]
+    fn it_can_be_filtered_by_name() {
+        let lints = CustomLints::new(vec![no_wip()]);
+
+        assert!(
+            lints.filter(&["something-else"]).is_empty(),
+            "Expected filtering by an unknown name to leave no lints"
+        );
+        assert_eq!(
+            lints.filter(&["no-wip"]),
+            lints,
+            "Expected filtering by a known name to keep the lint"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan, Severity, SourceCode};
+use thiserror::Error;
+
+use crate::lints::lib::severity::LintSeverity;
+
+/// A failure of a user-defined lint
+#[derive(Error, Debug, Eq, PartialEq, Clone)]
+#[error("{error}")]
+pub struct CustomProblem {
+    error: String,
+    help: String,
+    code: String,
+    severity: LintSeverity,
+    commit_message: String,
+    labels: Vec<(String, usize, usize)>,
+}
+
+impl CustomProblem {
+    /// Create a new problem for a custom lint
+    #[must_use]
+    pub const fn new(
+        error: String,
+        help: String,
+        code: String,
+        severity: LintSeverity,
+        commit_message: String,
+        labels: Vec<(String, usize, usize)>,
+    ) -> Self {
+        Self {
+            error,
+            help,
+            code,
+            severity,
+            commit_message,
+            labels,
+        }
+    }
+
+    /// The name of the lint that failed
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The severity the lint was configured with
+    #[must_use]
+    pub const fn lint_severity(&self) -> LintSeverity {
+        self.severity
+    }
+}
+
+impl Diagnostic for CustomProblem {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(&self.code))
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity.into())
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(&self.help))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        if self.commit_message.is_empty() {
+            None
+        } else {
+            Some(&self.commit_message)
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        if self.commit_message.is_empty() || self.labels.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(self.labels.iter().map(|(label, offset, len)| {
+            LabeledSpan::new(Some(label.clone()), *offset, *len)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::{Diagnostic, Severity};
+
+    use super::CustomProblem;
+    use crate::lints::lib::severity::LintSeverity;
+
+    #[test]
+    fn the_code_is_the_lint_name() {
+        let problem = CustomProblem::new(
+            "Bad subject".into(),
+            "Fix it".into(),
+            "no-wip".into(),
+            LintSeverity::Error,
+            "WIP".into(),
+            vec![("Matches".into(), 0, 3)],
+        );
+
+        assert_eq!(
+            Diagnostic::code(&problem).map(|x| x.to_string()),
+            Some("no-wip".to_string()),
+            "Expected the diagnostic code to be the custom lint name"
+        );
+    }
+
+    #[test]
+    fn the_severity_follows_the_configuration() {
+        let problem = CustomProblem::new(
+            "Bad subject".into(),
+            "Fix it".into(),
+            "no-wip".into(),
+            LintSeverity::Warning,
+            "WIP".into(),
+            vec![],
+        );
+
+        assert_eq!(
+            problem.severity(),
+            Some(Severity::Warning),
+            "Expected the diagnostic severity to follow the configured severity"
+        );
+    }
+
+    #[test]
+    fn there_are_no_labels_without_a_commit_message() {
+        let problem = CustomProblem::new(
+            "Bad subject".into(),
+            "Fix it".into(),
+            "no-wip".into(),
+            LintSeverity::Error,
+            String::new(),
+            vec![("Matches".into(), 0, 3)],
+        );
+
+        assert!(
+            problem.labels().is_none(),
+            "Expected no labels when there is no commit message to point at"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::string::FromUtf8Error;
+
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("invalid pattern in custom lint `{name}`")]
+#[diagnostic(
+    url(docsrs),
+    code(mit_commit_message_lints::lints::lib::custom_lint::invalid_pattern),
+    help("the pattern must be a valid regular expression: {message}")
+)]
+pub struct InvalidCustomLintPattern {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("invalid regular expression")]
+    pub(crate) span: SourceSpan,
+    pub(crate) name: String,
+    pub(crate) message: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("could not parse lint severity configuration")]
+#[diagnostic(
+    url(docsrs),
+    code(mit_commit_message_lints::lints::lib::severity::deserialize_lint_severity_error),
+    help("valid values are: error, warning, off")
+)]
+pub struct DeserializeLintSeverityError {
+    #[source_code]
+    pub(crate) src: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum LintPluginError {
+    #[error("lint plugin `{name}` exited with code {exit_code}")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::lints::lib::plugin::exec_failed),
+        help("the plugin should exit successfully, and report problems on stdout")
+    )]
+    ExecFailed { name: String, exit_code: i32 },
+    #[error("lint plugin `{name}` was stopped after running for {seconds}s")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::lints::lib::plugin::timed_out),
+        help("you can give it longer by setting its `timeout` in seconds")
+    )]
+    TimedOut { name: String, seconds: u64 },
+    #[error("failed to convert the output of lint plugin `{name}` to unicode")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::lints::lib::plugin::exec_utf8),
+        help("all characters must parse as utf8")
+    )]
+    ExecUtf8 {
+        name: String,
+        #[source]
+        source: FromUtf8Error,
+    },
+    #[error("could not parse the output of lint plugin `{name}`")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::lints::lib::plugin::invalid_output),
+        help("the plugin should print JSON like {{\"problems\": []}}: {message}")
+    )]
+    InvalidOutput {
+        name: String,
+        message: String,
+        #[source_code]
+        src: String,
+    },
+}
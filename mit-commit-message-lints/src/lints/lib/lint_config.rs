@@ -0,0 +1,404 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use miette::{IntoDiagnostic, Report};
+use mit_commit::CommitMessage;
+use mit_lint::{Lint, Lints, Problem};
+
+use crate::lints::lib::{
+    custom_lint::CustomLint,
+    custom_lints::CustomLints,
+    custom_problem::CustomProblem,
+    plugin::LintPlugin,
+    plugins::LintPlugins,
+    report::{LintProblem, LintReport},
+    severity::{LintSetting, LintSeverity},
+};
+
+/// The lints to run against a commit message, and how serious each one is
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LintConfig {
+    severities: BTreeMap<Lint, LintSeverity>,
+    custom: CustomLints,
+    plugins: LintPlugins,
+}
+
+impl LintConfig {
+    /// Create a new lint configuration
+    ///
+    /// Built-in lints missing from the severities are treated as off
+    #[must_use]
+    pub const fn new(
+        severities: BTreeMap<Lint, LintSeverity>,
+        custom: CustomLints,
+        plugins: LintPlugins,
+    ) -> Self {
+        Self {
+            severities,
+            custom,
+            plugins,
+        }
+    }
+
+    /// Use these plugins instead
+    #[must_use]
+    pub fn with_plugins(self, plugins: LintPlugins) -> Self {
+        Self { plugins, ..self }
+    }
+
+    /// The severity of every built-in lint
+    #[must_use]
+    pub const fn severities(&self) -> &BTreeMap<Lint, LintSeverity> {
+        &self.severities
+    }
+
+    /// The severity of a built-in lint
+    #[must_use]
+    pub fn severity(&self, lint: Lint) -> LintSeverity {
+        self.severities
+            .get(&lint)
+            .copied()
+            .unwrap_or(LintSeverity::Off)
+    }
+
+    /// The built-in lints that will be run, whatever their severity
+    #[must_use]
+    pub fn lints(&self) -> Lints {
+        self.filter(|severity| severity != LintSeverity::Off)
+    }
+
+    /// The built-in lints with the given severity
+    #[must_use]
+    pub fn lints_with_severity(&self, severity: LintSeverity) -> Lints {
+        self.filter(|candidate| candidate == severity)
+    }
+
+    /// The user-defined lints
+    #[must_use]
+    pub const fn custom(&self) -> &CustomLints {
+        &self.custom
+    }
+
+    /// The external lint plugins
+    #[must_use]
+    pub const fn plugins(&self) -> &LintPlugins {
+        &self.plugins
+    }
+
+    /// Run every lint on a commit message
+    ///
+    /// # Errors
+    ///
+    /// If a plugin fails to run
+    pub fn lint(&self, commit_message: &CommitMessage<'_>) -> Result<LintReport, Report> {
+        Ok(self.report(
+            commit_message,
+            mit_lint::lint(
+                commit_message,
+                &self.lints_with_severity(LintSeverity::Error),
+            ),
+            mit_lint::lint(
+                commit_message,
+                &self.lints_with_severity(LintSeverity::Warning),
+            ),
+            self.plugins.lint(commit_message)?,
+        ))
+    }
+
+    /// Run every lint on a commit message, running the built in lints
+    /// concurrently, and the plugins on a blocking task
+    ///
+    /// # Errors
+    ///
+    /// If a plugin fails to run
+    pub async fn async_lint(
+        &self,
+        commit_message: &CommitMessage<'_>,
+    ) -> Result<LintReport, Report> {
+        let plugins = self.plugins.clone();
+        let message = String::from(commit_message);
+        let plugin_problems =
+            tokio::task::spawn_blocking(move || plugins.lint(&CommitMessage::from(message)));
+
+        let errors = mit_lint::async_lint(
+            commit_message,
+            &self.lints_with_severity(LintSeverity::Error),
+        )
+        .await;
+        let warnings = mit_lint::async_lint(
+            commit_message,
+            &self.lints_with_severity(LintSeverity::Warning),
+        )
+        .await;
+        let plugin_problems = plugin_problems.await.into_diagnostic()??;
+
+        Ok(self.report(commit_message, errors, warnings, plugin_problems))
+    }
+
+    fn report(
+        &self,
+        commit_message: &CommitMessage<'_>,
+        errors: Vec<Problem>,
+        warnings: Vec<Problem>,
+        plugin_problems: Vec<CustomProblem>,
+    ) -> LintReport {
+        let (custom_warnings, custom_errors): (Vec<_>, Vec<_>) = self
+            .custom
+            .lint(commit_message)
+            .into_iter()
+            .chain(plugin_problems)
+            .partition(|problem| problem.lint_severity() == LintSeverity::Warning);
+
+        let errors = errors
+            .into_iter()
+            .map(|problem| Box::new(problem) as LintProblem)
+            .chain(
+                custom_errors
+                    .into_iter()
+                    .map(|problem| Box::new(problem) as LintProblem),
+            )
+            .collect();
+        let warnings = warnings
+            .into_iter()
+            .map(|problem| Box::new(problem) as LintProblem)
+            .chain(
+                custom_warnings
+                    .into_iter()
+                    .map(|problem| Box::new(problem) as LintProblem),
+            )
+            .collect();
+
+        LintReport::new(errors, warnings)
+    }
+
+    fn filter(&self, predicate: impl Fn(LintSeverity) -> bool) -> Lints {
+        Lints::new(
+            self.severities
+                .iter()
+                .filter(|(_, severity)| predicate(**severity))
+                .map(|(lint, _)| *lint)
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<LintConfig> for String {
+    type Error = Report;
+
+    fn try_from(config: LintConfig) -> Result<Self, Self::Error> {
+        let settings: BTreeMap<Self, LintSetting> = Lint::all_lints()
+            .map(|lint| (lint.to_string(), config.severity(lint).into()))
+            .collect();
+        let built_in = to_mit_toml(settings)?;
+
+        let custom = if config.custom.is_empty() {
+            Self::new()
+        } else {
+            let mut lint: BTreeMap<Self, BTreeMap<Self, CustomLint>> = BTreeMap::new();
+            lint.insert("custom".into(), config.custom.as_map());
+            format!("\n{}", to_mit_toml(lint)?)
+        };
+
+        let plugins = if config.plugins.is_empty() {
+            Self::new()
+        } else {
+            let mut lint: BTreeMap<Self, BTreeMap<Self, LintPlugin>> = BTreeMap::new();
+            lint.insert("plugin".into(), config.plugins.as_map());
+            format!("\n{}", to_mit_toml(lint)?)
+        };
+
+        Ok(format!("{built_in}{custom}{plugins}"))
+    }
+}
+
+fn to_mit_toml<T: serde::Serialize>(lint: T) -> Result<String, Report> {
+    let mut mit = BTreeMap::new();
+    mit.insert("lint", lint);
+    let mut output = BTreeMap::new();
+    output.insert("mit", mit);
+
+    toml::to_string(&output).into_diagnostic()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, convert::TryFrom};
+
+    use mit_commit::CommitMessage;
+    use mit_lint::{Lint, Lints};
+
+    use super::LintConfig;
+    use crate::lints::lib::{
+        custom_lint::{CustomLint, CustomLintMode, CustomLintTarget},
+        custom_lints::CustomLints,
+        plugin::LintPlugin,
+        plugins::LintPlugins,
+        severity::LintSeverity,
+    };
+
+    fn severities(lints: &[(Lint, LintSeverity)]) -> BTreeMap<Lint, LintSeverity> {
+        lints.iter().copied().collect()
+    }
+
+    #[test]
+    fn without_custom_lints_it_matches_the_built_in_toml() {
+        let lints = Lints::from(vec![Lint::DuplicatedTrailers]);
+        let expected = String::try_from(lints).unwrap();
+
+        let actual = String::try_from(LintConfig::new(
+            severities(&[(Lint::DuplicatedTrailers, LintSeverity::Error)]),
+            CustomLints::default(),
+            LintPlugins::default(),
+        ))
+        .unwrap();
+
+        assert_eq!(
+            expected, actual,
+            "Expected only the built-in lints when there are no custom lints"
+        );
+    }
+
+    #[test]
+    fn warnings_are_written_as_a_severity() {
+        let actual = String::try_from(LintConfig::new(
+            severities(&[(Lint::NotConventionalCommit, LintSeverity::Warning)]),
+            CustomLints::default(),
+            LintPlugins::default(),
+        ))
+        .unwrap();
+
+        assert!(
+            actual.contains("not-conventional-commit = \"warning\""),
+            "Expected the warning severity in {actual}"
+        );
+        assert!(
+            actual.contains("duplicated-trailers = false"),
+            "Expected unconfigured lints to be disabled in {actual}"
+        );
+    }
+
+    #[test]
+    fn lints_are_grouped_by_severity() {
+        let config = LintConfig::new(
+            severities(&[
+                (Lint::DuplicatedTrailers, LintSeverity::Error),
+                (Lint::NotConventionalCommit, LintSeverity::Warning),
+                (Lint::NotEmojiLog, LintSeverity::Off),
+            ]),
+            CustomLints::default(),
+            LintPlugins::default(),
+        );
+
+        assert_eq!(
+            config.lints(),
+            Lints::from(vec![Lint::DuplicatedTrailers, Lint::NotConventionalCommit]),
+            "Expected every lint that isn't off to run"
+        );
+        assert_eq!(
+            config.lints_with_severity(LintSeverity::Warning),
+            Lints::from(vec![Lint::NotConventionalCommit]),
+            "Expected only the warning lints"
+        );
+        assert_eq!(
+            config.severity(Lint::GitHubIdMissing),
+            LintSeverity::Off,
+            "Expected lints without a severity to be off"
+        );
+    }
+
+    #[test]
+    fn custom_lints_are_included_in_the_toml() {
+        let custom = CustomLints::new(vec![
+            CustomLint::new(
+                "no-wip".into(),
+                "WIP".into(),
+                CustomLintTarget::Subject,
+                CustomLintMode::Forbid,
+                LintSeverity::Warning,
+                "Finish the work first".into(),
+                None,
+            )
+            .unwrap(),
+        ]);
+
+        let actual = String::try_from(LintConfig::new(
+            BTreeMap::new(),
+            custom,
+            LintPlugins::default(),
+        ))
+        .unwrap();
+
+        assert!(
+            actual.contains("[mit.lint.custom.no-wip]"),
+            "Expected the custom lint table in {actual}"
+        );
+        assert!(
+            actual.contains("severity = \"warning\""),
+            "Expected the custom lint severity in {actual}"
+        );
+    }
+
+    #[test]
+    fn problems_are_split_by_severity() {
+        let custom = CustomLints::new(vec![
+            CustomLint::new(
+                "no-wip".into(),
+                "WIP".into(),
+                CustomLintTarget::Subject,
+                CustomLintMode::Forbid,
+                LintSeverity::Warning,
+                "Finish the work first".into(),
+                None,
+            )
+            .unwrap(),
+        ]);
+        let config = LintConfig::new(
+            severities(&[
+                (Lint::SubjectEndsWithPeriod, LintSeverity::Error),
+                (Lint::SubjectNotCapitalized, LintSeverity::Warning),
+                (Lint::NotEmojiLog, LintSeverity::Off),
+            ]),
+            custom,
+            LintPlugins::default(),
+        );
+
+        let report = config
+            .lint(&CommitMessage::from("add WIP endpoint.\n"))
+            .unwrap();
+
+        assert_eq!(
+            report.errors().len(),
+            1,
+            "Expected only the lint set to error to be an error"
+        );
+        assert_eq!(
+            report.warnings().len(),
+            2,
+            "Expected the built-in and custom warnings, but not the lint that is off"
+        );
+    }
+
+    #[test]
+    fn plugins_are_included_in_the_toml() {
+        let plugins = LintPlugins::new(vec![LintPlugin::new(
+            "spelling".into(),
+            "spellcheck --json".into(),
+            LintSeverity::Warning,
+        )]);
+
+        let actual = String::try_from(LintConfig::new(
+            BTreeMap::new(),
+            CustomLints::default(),
+            plugins,
+        ))
+        .unwrap();
+
+        assert!(
+            actual.contains("[mit.lint.plugin.spelling]"),
+            "Expected the plugin table in {actual}"
+        );
+        assert!(
+            actual.contains("command = \"spellcheck --json\""),
+            "Expected the plugin command in {actual}"
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Domain objects for lint configuration
+
+pub mod config_source;
+pub(crate) mod custom_lint;
+pub(crate) mod custom_lints;
+pub(crate) mod custom_problem;
+mod errors;
+pub(crate) mod lint_config;
+pub(crate) mod plugin;
+pub(crate) mod plugins;
+pub(crate) mod report;
+pub mod severity;
@@ -0,0 +1,494 @@
+use std::{
+    io::{ErrorKind, Write},
+    process::{ChildStdin, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use miette::{IntoDiagnostic, Result};
+use mit_commit::CommitMessage;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    external::wait_with_timeout,
+    lints::lib::{custom_problem::CustomProblem, errors::LintPluginError, severity::LintSeverity},
+};
+
+/// How long a plugin is given to run if it doesn't have its own timeout
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+
+/// An external program that lints commit messages
+///
+/// The program is given the commit message as JSON on stdin, and prints the
+/// problems it found as JSON on stdout.
+///
+/// Plugins read from a `.git-mit.toml` aren't trusted, as anyone who can
+/// change the repository could make them run anything. They're not run
+/// unless the repository is trusted in the git config.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct LintPlugin {
+    #[serde(skip)]
+    name: String,
+    command: String,
+    #[serde(default)]
+    severity: LintSeverity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(skip)]
+    trusted: bool,
+}
+
+/// What a plugin receives on stdin
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PluginInput {
+    /// The whole commit message, which the spans in the output refer to
+    pub message: String,
+    /// The first line of the commit message
+    pub subject: String,
+    /// Everything after the subject, ending with the trailers if there are
+    /// any
+    pub body: String,
+    /// The trailers at the end of the commit message
+    pub trailers: Vec<PluginTrailer>,
+    /// The character comments start with, if there are any comments
+    pub comment_char: Option<char>,
+}
+
+/// A trailer given to a plugin
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PluginTrailer {
+    /// The trailer key, for example `Co-authored-by`
+    pub key: String,
+    /// The trailer value
+    pub value: String,
+}
+
+/// What a plugin prints on stdout
+#[derive(Debug, Eq, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct PluginOutput {
+    /// The problems found, an empty list if the commit message is fine
+    #[serde(default)]
+    pub problems: Vec<PluginProblem>,
+}
+
+/// A problem found by a plugin
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PluginProblem {
+    /// A short identifier for the problem
+    pub code: String,
+    /// What is wrong
+    pub message: String,
+    /// How to fix it
+    #[serde(default)]
+    pub help: String,
+    /// The parts of the commit message the problem is about
+    #[serde(default)]
+    pub labels: Vec<PluginLabel>,
+}
+
+/// A span of the commit message, in bytes
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PluginLabel {
+    /// Text shown next to the span
+    pub label: String,
+    /// Byte offset from the start of the commit message
+    pub offset: usize,
+    /// Length of the span in bytes
+    pub length: usize,
+}
+
+impl From<&CommitMessage<'_>> for PluginInput {
+    fn from(commit_message: &CommitMessage<'_>) -> Self {
+        Self {
+            message: String::from(commit_message),
+            subject: commit_message.get_subject().to_string(),
+            body: commit_message.get_body().to_string(),
+            trailers: commit_message
+                .get_trailers()
+                .iter()
+                .map(|trailer| PluginTrailer {
+                    key: trailer.get_key(),
+                    value: trailer.get_value(),
+                })
+                .collect(),
+            comment_char: commit_message.get_comment_char(),
+        }
+    }
+}
+
+impl LintPlugin {
+    /// Create a new plugin
+    #[must_use]
+    pub const fn new(name: String, command: String, severity: LintSeverity) -> Self {
+        Self {
+            name,
+            command,
+            severity,
+            timeout: None,
+            trusted: true,
+        }
+    }
+
+    pub(crate) fn with_name(self, name: String) -> Self {
+        Self { name, ..self }
+    }
+
+    /// Stop the plugin if it runs for longer than this many seconds
+    #[must_use]
+    pub fn with_timeout(self, seconds: u64) -> Self {
+        Self {
+            timeout: Some(seconds),
+            ..self
+        }
+    }
+
+    /// Run the plugin, even if it came from a `.git-mit.toml`
+    #[must_use]
+    pub fn trusted(self) -> Self {
+        Self {
+            trusted: true,
+            ..self
+        }
+    }
+
+    /// Run a different command, trusting it as it came from the git config
+    #[must_use]
+    pub(crate) fn with_trusted_command(self, command: String) -> Self {
+        Self {
+            command,
+            trusted: true,
+            ..self
+        }
+    }
+
+    /// The name of the plugin
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The command that is run
+    #[must_use]
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// How serious the problems this plugin finds are
+    #[must_use]
+    pub const fn severity(&self) -> LintSeverity {
+        self.severity
+    }
+
+    /// Whether the plugin will be run
+    ///
+    /// Plugins from a `.git-mit.toml` are only run if the repository is
+    /// trusted, or their command is in the git config
+    #[must_use]
+    pub const fn is_trusted(&self) -> bool {
+        self.trusted
+    }
+
+    /// How long the plugin is given to run before it's stopped
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT_SECONDS))
+    }
+
+    /// Run the plugin on a commit message
+    ///
+    /// # Errors
+    ///
+    /// If the command can't be run, exits unsuccessfully, takes longer than
+    /// its timeout, or prints something that isn't the expected JSON
+    pub fn lint(&self, commit_message: &CommitMessage<'_>) -> Result<Vec<CustomProblem>> {
+        if self.severity == LintSeverity::Off || !self.trusted {
+            return Ok(vec![]);
+        }
+
+        let input = PluginInput::from(commit_message);
+        let stdout = self.exec(&serde_json::to_vec(&input).into_diagnostic()?)?;
+        let output: PluginOutput =
+            serde_json::from_str(&stdout).map_err(|source| LintPluginError::InvalidOutput {
+                name: self.name.clone(),
+                message: source.to_string(),
+                src: stdout.clone(),
+            })?;
+
+        Ok(output
+            .problems
+            .into_iter()
+            .map(|problem| {
+                CustomProblem::new(
+                    problem.message,
+                    problem.help,
+                    problem.code,
+                    self.severity,
+                    input.message.clone(),
+                    problem
+                        .labels
+                        .into_iter()
+                        .filter(|label| is_span_of(&input.message, label))
+                        .map(|label| (label.label, label.offset, label.length))
+                        .collect(),
+                )
+            })
+            .collect())
+    }
+
+    fn exec(&self, input: &[u8]) -> Result<String> {
+        let commandline = shell_words::split(&self.command).into_diagnostic()?;
+        let mut child = Command::new(commandline.first().unwrap_or(&String::new()))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .args(commandline.iter().skip(1))
+            .spawn()
+            .into_diagnostic()?;
+
+        // Write on another thread, so a plugin that prints before it has read
+        // everything doesn't block on us while we block on it
+        let stdin = child.stdin.take();
+        let output = thread::scope(|scope| {
+            let writer = scope.spawn(|| write_input(stdin, input));
+            let output = wait_with_timeout(child, Some(self.timeout()));
+            writer
+                .join()
+                .unwrap_or_else(|_| Err(std::io::Error::other("writing the input panicked")))
+                .into_diagnostic()?;
+            output
+        })?
+        .ok_or_else(|| LintPluginError::TimedOut {
+            name: self.name.clone(),
+            seconds: self.timeout().as_secs(),
+        })?;
+        if !output.success() {
+            return Err(LintPluginError::ExecFailed {
+                name: self.name.clone(),
+                exit_code: output.exit_code.unwrap_or(-1),
+            }
+            .into());
+        }
+
+        String::from_utf8(output.stdout).map_err(|source| {
+            LintPluginError::ExecUtf8 {
+                name: self.name.clone(),
+                source,
+            }
+            .into()
+        })
+    }
+}
+
+fn write_input(stdin: Option<ChildStdin>, input: &[u8]) -> std::io::Result<()> {
+    // Plugins that don't need the input may exit without reading it
+    match stdin.map_or(Ok(()), |mut stdin| stdin.write_all(input)) {
+        Err(error) if error.kind() != ErrorKind::BrokenPipe => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// If a label from a plugin is a span of the message that we can point at
+fn is_span_of(message: &str, label: &PluginLabel) -> bool {
+    label
+        .offset
+        .checked_add(label.length)
+        .is_some_and(|end| message.get(label.offset..end).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use indoc::indoc;
+    use mit_commit::CommitMessage;
+
+    use super::{LintPlugin, PluginInput, PluginLabel, PluginTrailer, is_span_of};
+    use crate::lints::lib::severity::LintSeverity;
+
+    #[test]
+    fn the_input_describes_the_commit_message() {
+        let message = indoc!(
+            "
+            Add an endpoint
+
+            It does a thing
+
+            Relates-to: #123
+            "
+        );
+
+        let input = PluginInput::from(&CommitMessage::from(message));
+
+        assert_eq!(
+            input.subject, "Add an endpoint",
+            "Expected the subject to be the first line"
+        );
+        assert_eq!(
+            input.trailers,
+            vec![PluginTrailer {
+                key: "Relates-to".into(),
+                value: "#123".into(),
+            }],
+            "Expected the trailers to be split into keys and values"
+        );
+        assert_eq!(
+            input.message, message,
+            "Expected the whole message to be included for the spans"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn problems_are_read_from_stdout() {
+        let plugin = LintPlugin::new(
+            "spelling".into(),
+            r#"sh -c 'cat > /dev/null; echo "{\"problems\": [{\"code\": \"misspelt\", \"message\": \"Misspelt word\", \"help\": \"Check your spelling\", \"labels\": [{\"label\": \"here\", \"offset\": 4, \"length\": 8}]}]}"'"#.into(),
+            LintSeverity::Warning,
+        );
+
+        let problems = plugin
+            .lint(&CommitMessage::from("Add endpiont\n"))
+            .expect("Expected the plugin to run");
+
+        assert_eq!(problems.len(), 1, "Expected the problem from the plugin");
+        assert_eq!(
+            problems[0].code(),
+            "misspelt",
+            "Expected the code from the plugin"
+        );
+        assert_eq!(
+            problems[0].lint_severity(),
+            LintSeverity::Warning,
+            "Expected the severity configured for the plugin"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn the_plugin_receives_the_input_on_stdin() {
+        let plugin = LintPlugin::new(
+            "echo".into(),
+            r#"sh -c 'grep -q "\"subject\":\"Add endpoint\"" && echo "{\"problems\": []}"'"#.into(),
+            LintSeverity::Error,
+        );
+
+        let problems = plugin
+            .lint(&CommitMessage::from("Add endpoint\n"))
+            .expect("Expected the plugin to find the subject on stdin");
+
+        assert!(problems.is_empty(), "Expected no problems to be reported");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn failing_plugins_are_an_error() {
+        let plugin = LintPlugin::new("broken".into(), "false".into(), LintSeverity::Error);
+
+        assert!(
+            plugin.lint(&CommitMessage::from("Add endpoint\n")).is_err(),
+            "Expected a non-zero exit code to be an error"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn invalid_output_is_an_error() {
+        let plugin = LintPlugin::new(
+            "chatty".into(),
+            "sh -c 'cat > /dev/null; echo hello'".into(),
+            LintSeverity::Error,
+        );
+
+        assert!(
+            plugin.lint(&CommitMessage::from("Add endpoint\n")).is_err(),
+            "Expected output that isn't JSON to be an error"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn slow_plugins_are_stopped() {
+        let plugin =
+            LintPlugin::new("slow".into(), "sleep 10".into(), LintSeverity::Error).with_timeout(1);
+
+        let started = Instant::now();
+        let result = plugin.lint(&CommitMessage::from("Add endpoint\n"));
+
+        assert!(result.is_err(), "Expected the plugin to time out");
+        assert!(
+            started.elapsed().as_secs() < 5,
+            "Expected the plugin to be stopped at the timeout"
+        );
+    }
+
+    #[test]
+    fn untrusted_plugins_are_not_run() {
+        let plugin: LintPlugin =
+            toml::from_str(r#"command = "this-command-does-not-exist""#).unwrap();
+
+        assert_eq!(
+            plugin.lint(&CommitMessage::from("Add endpoint\n")).unwrap(),
+            vec![],
+            "Expected a plugin read from a file not to be run until it's trusted"
+        );
+    }
+
+    #[test]
+    fn plugins_that_are_off_are_not_run() {
+        let plugin = LintPlugin::new(
+            "missing".into(),
+            "this-command-does-not-exist".into(),
+            LintSeverity::Off,
+        );
+
+        assert_eq!(
+            plugin.lint(&CommitMessage::from("Add endpoint\n")).unwrap(),
+            vec![],
+            "Expected a plugin that is off to not be run"
+        );
+    }
+
+    #[test]
+    fn labels_outside_the_message_are_not_spans() {
+        let label = |offset, length| PluginLabel {
+            label: "here".into(),
+            offset,
+            length,
+        };
+
+        assert!(
+            is_span_of("Add café", &label(4, 5)),
+            "Expected a span ending on a character boundary to be kept"
+        );
+        assert!(
+            !is_span_of("Add café", &label(4, 4)),
+            "Expected a span ending inside a character to be dropped"
+        );
+        assert!(
+            !is_span_of("Add café", &label(usize::MAX, 2)),
+            "Expected a span that overflows to be dropped"
+        );
+        assert!(
+            !is_span_of("Add café", &label(4, 10)),
+            "Expected a span past the end of the message to be dropped"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn plugins_may_print_before_reading_their_input() {
+        let plugin = LintPlugin::new(
+            "eager".into(),
+            r#"sh -c 'head -c 1000000 /dev/zero | tr "\0" " "; cat > /dev/null; echo "{\"problems\": []}"'"#
+                .into(),
+            LintSeverity::Error,
+        );
+        let message = format!("Add endpoint\n\n{}\n", "word ".repeat(100_000));
+
+        let problems = plugin
+            .lint(&CommitMessage::from(message))
+            .expect("Expected the plugin to run without blocking");
+
+        assert!(problems.is_empty(), "Expected no problems to be reported");
+    }
+}
@@ -0,0 +1,160 @@
+use std::{collections::BTreeMap, vec::IntoIter};
+
+use miette::Result;
+use mit_commit::CommitMessage;
+
+use crate::lints::lib::{
+    custom_problem::CustomProblem, plugin::LintPlugin, severity::LintSeverity,
+};
+
+/// Collection of external lint plugins
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct LintPlugins {
+    plugins: Vec<LintPlugin>,
+}
+
+impl LintPlugins {
+    /// Create a new collection of plugins
+    #[must_use]
+    pub const fn new(plugins: Vec<LintPlugin>) -> Self {
+        Self { plugins }
+    }
+
+    /// The names of the plugins
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.plugins.iter().map(LintPlugin::name).collect()
+    }
+
+    /// The names of the plugins that won't run because they came from a
+    /// `.git-mit.toml` and aren't trusted
+    #[must_use]
+    pub fn untrusted(&self) -> Vec<&str> {
+        self.plugins
+            .iter()
+            .filter(|plugin| !plugin.is_trusted() && plugin.severity() != LintSeverity::Off)
+            .map(LintPlugin::name)
+            .collect()
+    }
+
+    /// Get a plugin by its name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&LintPlugin> {
+        self.plugins.iter().find(|plugin| plugin.name() == name)
+    }
+
+    /// Check if there are any plugins
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Only keep the plugins with these names
+    #[must_use]
+    pub fn filter(&self, names: &[&str]) -> Self {
+        Self::new(
+            self.plugins
+                .iter()
+                .filter(|plugin| names.contains(&plugin.name()))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Add plugins, replacing any existing plugins with the same name
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        let mut plugins = self.as_map();
+        plugins.extend(other.as_map());
+        plugins.into()
+    }
+
+    /// Run all the plugins on a commit message
+    ///
+    /// # Errors
+    ///
+    /// If any of the plugins fail to run
+    pub fn lint(&self, commit_message: &CommitMessage<'_>) -> Result<Vec<CustomProblem>> {
+        self.plugins
+            .iter()
+            .map(|plugin| plugin.lint(commit_message))
+            .collect::<Result<Vec<_>>>()
+            .map(|problems| problems.into_iter().flatten().collect())
+    }
+
+    pub(crate) fn as_map(&self) -> BTreeMap<String, LintPlugin> {
+        self.plugins
+            .iter()
+            .map(|plugin| (plugin.name().to_string(), plugin.clone()))
+            .collect()
+    }
+}
+
+impl IntoIterator for LintPlugins {
+    type Item = LintPlugin;
+    type IntoIter = IntoIter<LintPlugin>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.plugins.into_iter()
+    }
+}
+
+impl From<BTreeMap<String, LintPlugin>> for LintPlugins {
+    fn from(value: BTreeMap<String, LintPlugin>) -> Self {
+        Self::new(
+            value
+                .into_iter()
+                .map(|(name, plugin)| plugin.with_name(name))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::LintPlugins;
+    use crate::lints::lib::{plugin::LintPlugin, severity::LintSeverity};
+
+    #[test]
+    fn names_come_from_the_config_keys() {
+        let plugin: LintPlugin = toml::from_str(r#"command = "spellcheck""#).unwrap();
+        let mut map = BTreeMap::new();
+        map.insert("spelling".to_string(), plugin);
+
+        let plugins = LintPlugins::from(map);
+
+        assert_eq!(
+            plugins.names(),
+            vec!["spelling"],
+            "Expected the plugin to be named after its key"
+        );
+    }
+
+    #[test]
+    fn merging_replaces_plugins_with_the_same_name() {
+        let base = LintPlugins::new(vec![
+            LintPlugin::new("spelling".into(), "aspell".into(), LintSeverity::Error),
+            LintPlugin::new("tickets".into(), "ticket-check".into(), LintSeverity::Error),
+        ]);
+        let overrides = LintPlugins::new(vec![LintPlugin::new(
+            "spelling".into(),
+            "hunspell".into(),
+            LintSeverity::Warning,
+        )]);
+
+        let merged = base.merge(&overrides);
+
+        assert_eq!(
+            merged.get("spelling").map(LintPlugin::command),
+            Some("hunspell"),
+            "Expected the later plugin to win"
+        );
+        assert_eq!(
+            merged.names(),
+            vec!["spelling", "tickets"],
+            "Expected plugins that aren't overridden to be kept"
+        );
+    }
+}
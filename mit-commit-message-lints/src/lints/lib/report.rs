@@ -0,0 +1,37 @@
+use miette::Diagnostic;
+
+/// A single problem found by any kind of lint
+pub type LintProblem = Box<dyn Diagnostic + Send + Sync>;
+
+/// The problems found in a commit message, split by how serious they are
+#[derive(Debug, Default)]
+pub struct LintReport {
+    errors: Vec<LintProblem>,
+    warnings: Vec<LintProblem>,
+}
+
+impl LintReport {
+    /// Create a new report
+    #[must_use]
+    pub const fn new(errors: Vec<LintProblem>, warnings: Vec<LintProblem>) -> Self {
+        Self { errors, warnings }
+    }
+
+    /// Problems that should stop the commit
+    #[must_use]
+    pub fn errors(&self) -> &[LintProblem] {
+        &self.errors
+    }
+
+    /// Problems that should be reported, but not stop the commit
+    #[must_use]
+    pub fn warnings(&self) -> &[LintProblem] {
+        &self.warnings
+    }
+
+    /// Split the report into its errors and warnings
+    #[must_use]
+    pub fn into_parts(self) -> (Vec<LintProblem>, Vec<LintProblem>) {
+        (self.errors, self.warnings)
+    }
+}
@@ -0,0 +1,196 @@
+//! How serious a lint failure is
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::lints::lib::errors::DeserializeLintSeverityError;
+
+/// The severity a lint is reported with
+#[derive(
+    clap::ValueEnum,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintSeverity {
+    /// Report the problem as an error
+    #[default]
+    Error,
+    /// Report the problem as a warning, without blocking the commit
+    Warning,
+    /// Do not run the lint
+    Off,
+}
+
+/// The git config key a lint's severity is kept in, next to the
+/// `mit.lint.<name>` boolean that turns it on or off
+pub(crate) fn config_key(enabled_key: &str) -> String {
+    format!("{enabled_key}.severity")
+}
+
+const ERROR_DISPLAY: &str = "error";
+const WARNING_DISPLAY: &str = "warning";
+const OFF_DISPLAY: &str = "off";
+
+impl FromStr for LintSeverity {
+    type Err = DeserializeLintSeverityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            ERROR_DISPLAY => Ok(Self::Error),
+            WARNING_DISPLAY => Ok(Self::Warning),
+            OFF_DISPLAY => Ok(Self::Off),
+            _ => Err(DeserializeLintSeverityError { src: s.into() }),
+        }
+    }
+}
+
+impl Display for LintSeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "{ERROR_DISPLAY}"),
+            Self::Warning => write!(f, "{WARNING_DISPLAY}"),
+            Self::Off => write!(f, "{OFF_DISPLAY}"),
+        }
+    }
+}
+
+impl From<bool> for LintSeverity {
+    fn from(enabled: bool) -> Self {
+        if enabled { Self::Error } else { Self::Off }
+    }
+}
+
+/// How a lint is written in the toml, either the older on/off toggle or a
+/// severity
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum LintSetting {
+    Toggle(bool),
+    Severity(LintSeverity),
+}
+
+impl From<LintSetting> for LintSeverity {
+    fn from(setting: LintSetting) -> Self {
+        match setting {
+            LintSetting::Toggle(enabled) => enabled.into(),
+            LintSetting::Severity(severity) => severity,
+        }
+    }
+}
+
+impl From<LintSeverity> for LintSetting {
+    fn from(severity: LintSeverity) -> Self {
+        match severity {
+            LintSeverity::Error => Self::Toggle(true),
+            LintSeverity::Off => Self::Toggle(false),
+            LintSeverity::Warning => Self::Severity(severity),
+        }
+    }
+}
+
+impl From<LintSeverity> for miette::Severity {
+    fn from(severity: LintSeverity) -> Self {
+        match severity {
+            LintSeverity::Error => Self::Error,
+            LintSeverity::Warning => Self::Warning,
+            LintSeverity::Off => Self::Advice,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{LintSetting, LintSeverity};
+
+    #[test]
+    fn error_is_the_default() {
+        assert_eq!(
+            LintSeverity::default(),
+            LintSeverity::Error,
+            "Expected lints to be errors unless configured otherwise"
+        );
+    }
+
+    #[test]
+    fn converts_to_miette_severity() {
+        assert_eq!(
+            miette::Severity::from(LintSeverity::Warning),
+            miette::Severity::Warning,
+            "Expected a warning lint to render as a miette warning"
+        );
+        assert_eq!(
+            miette::Severity::from(LintSeverity::Error),
+            miette::Severity::Error,
+            "Expected an error lint to render as a miette error"
+        );
+    }
+
+    #[test]
+    fn from_str_accepts_severities() {
+        assert_eq!(
+            LintSeverity::from_str("Warning").unwrap(),
+            LintSeverity::Warning,
+            "Expected 'Warning' to parse as a warning (case insensitive)"
+        );
+    }
+
+    #[test]
+    fn from_str_rejects_unknown() {
+        assert!(
+            LintSeverity::from_str("loud").is_err(),
+            "Expected parsing an unknown severity to return an error"
+        );
+        assert!(
+            LintSeverity::from_str("true").is_err(),
+            "Expected toggles to be left to the git config boolean parsing"
+        );
+    }
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        for severity in [
+            LintSeverity::Error,
+            LintSeverity::Warning,
+            LintSeverity::Off,
+        ] {
+            assert_eq!(
+                LintSeverity::from_str(&severity.to_string()).unwrap(),
+                severity,
+                "Expected {severity} to survive a round trip"
+            );
+        }
+    }
+
+    #[test]
+    fn settings_prefer_toggles_where_they_are_enough() {
+        assert_eq!(
+            LintSetting::from(LintSeverity::Error),
+            LintSetting::Toggle(true),
+            "Expected errors to be written as enabled"
+        );
+        assert_eq!(
+            LintSetting::from(LintSeverity::Off),
+            LintSetting::Toggle(false),
+            "Expected off to be written as disabled"
+        );
+        assert_eq!(
+            LintSetting::from(LintSeverity::Warning),
+            LintSetting::Severity(LintSeverity::Warning),
+            "Expected warnings to be written as a severity"
+        );
+    }
+}
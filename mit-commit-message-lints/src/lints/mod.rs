@@ -1,5 +1,17 @@
 //! Interactions with the lints config
 
-pub use cmd::{read_from_toml_or_else_vcs, set_status};
+pub use cmd::{read_from_toml_or_else_vcs, set_severity, set_status};
+pub use lib::{
+    config_source::ConfigSource,
+    custom_lint::{CustomLint, CustomLintMode, CustomLintTarget},
+    custom_lints::CustomLints,
+    custom_problem::CustomProblem,
+    lint_config::LintConfig,
+    plugin::{LintPlugin, PluginInput, PluginLabel, PluginOutput, PluginProblem, PluginTrailer},
+    plugins::LintPlugins,
+    report::{LintProblem, LintReport},
+    severity::LintSeverity,
+};
 
 mod cmd;
+pub mod lib;
@@ -0,0 +1,99 @@
+use miette::{Result, WrapErr};
+
+use crate::{
+    external::Vcs,
+    mit::cmd::{
+        CONFIG_KEY_EXPIRES, CONFIG_KEY_EXPIRES_TIMEOUT, once_authors::clear_once_authors,
+        previous_identity::restore_previous_identity, session_author::forget_session_author,
+        set_commit_authors::remove_coauthors,
+    },
+    relates::clear_relates_to,
+};
+
+/// End the current pairing session
+///
+/// Removes the co-authors, when they expire, the copy of the author kept
+/// with the session, any authors for just the next commit, and the
+/// relates-to. If `restore_identity` is set
+/// the `user.name`, `user.email`, `user.signingkey` and `gpg.format` from
+/// before the session are put back too.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn clear_commit_authors(config: &mut dyn Vcs, restore_identity: bool) -> Result<()> {
+    remove_coauthors(config)?;
+    clear_once_authors(config)?;
+    config
+        .remove_if_set(CONFIG_KEY_EXPIRES)
+        .wrap_err("failed to remove author expiry time")?;
+    config
+        .remove_if_set(CONFIG_KEY_EXPIRES_TIMEOUT)
+        .wrap_err("failed to remove author expiry window")?;
+    forget_session_author(config)?;
+    clear_relates_to(config)?;
+
+    if restore_identity {
+        restore_previous_identity(config)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{external::InMemory, mit::clear_commit_authors};
+
+    fn session() -> BTreeMap<String, String> {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("user.name".into(), "Billie Thompson".into());
+        buffer.insert("user.email".into(), "billie@example.com".into());
+        buffer.insert("user.signingkey".into(), "0A46826A".into());
+        buffer.insert("mit.author.expires".into(), "1700000000".into());
+        buffer.insert("mit.author.user.email".into(), "billie@example.com".into());
+        buffer.insert("mit.author.coauthors.0.name".into(), "Annie Example".into());
+        buffer.insert(
+            "mit.author.coauthors.0.email".into(),
+            "annie@example.com".into(),
+        );
+        buffer.insert("mit.relate.to".into(), "[#12345678]".into());
+        buffer.insert("mit.relate.expires".into(), "1700000000".into());
+        buffer
+    }
+
+    #[test]
+    fn clearing_removes_the_coauthors_expiry_and_relates_to() {
+        let mut buffer = session();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        clear_commit_authors(&mut vcs, false).unwrap();
+
+        assert_eq!(
+            buffer.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["user.email", "user.name", "user.signingkey"],
+            "Expected only the git author to be left"
+        );
+    }
+
+    #[test]
+    fn clearing_can_remove_the_session_identity() {
+        let mut buffer = session();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        clear_commit_authors(&mut vcs, true).unwrap();
+
+        assert!(buffer.is_empty(), "Expected everything to be removed");
+    }
+
+    #[test]
+    fn clearing_when_there_is_no_session_does_nothing() {
+        let mut buffer = BTreeMap::new();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        clear_commit_authors(&mut vcs, true).unwrap();
+
+        assert!(buffer.is_empty(), "Expected nothing to be added");
+    }
+}
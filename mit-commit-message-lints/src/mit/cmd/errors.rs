@@ -40,6 +40,52 @@ pub enum Error {
     #[error("no timeout set")]
     #[diagnostic(url(docsrs), code(git_mit::errors::git_mit_error::no_timeout_set))]
     NoTimeoutSet,
+    /// There's already an author with these initials
+    #[error("there's already an author with the initials {initial}")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::mit::cmd::errors::error::author_already_exists),
+        help("remove the existing author first with `git mit-config mit remove {initial}`")
+    )]
+    AuthorAlreadyExists {
+        /// The initials that are already used
+        initial: String,
+    },
+    /// The author is also saved in a level of the config we aren't changing
+    #[error("the author with the initials {initial} is also saved in another scope")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::mit::cmd::errors::error::author_in_other_scope),
+        help("try again with a different `--scope`, like `--scope global`")
+    )]
+    AuthorInOtherScope {
+        /// The initials of the author
+        initial: String,
+    },
+    /// The author files are only directories, with no files in them to change
+    #[error("can't change the authors in {path}, there are no files to write to")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::mit::cmd::errors::error::author_file_is_directory),
+        help("give a file to change with `--config`")
+    )]
+    AuthorFileIsDirectory {
+        /// The author files given
+        path: String,
+    },
+    /// Changing the author would have changed more of the file than them
+    #[error("can't change the authors in {path} without changing other authors")]
+    #[diagnostic(
+        url(docsrs),
+        code(mit_commit_message_lints::mit::cmd::errors::error::author_file_not_editable),
+        help(
+            "part of the file is written in a way we can't safely edit, change it by hand instead"
+        )
+    )]
+    AuthorFileNotEditable {
+        /// The file that was left as it was
+        path: String,
+    },
     /// Expected a mit file path, didn't find one
     #[error("expected a mit file path, didn't find one")]
     #[diagnostic(url(docsrs), code(git_mit::errors::git_mit_error::author_file_not_set))]
@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use miette::{Result, WrapErr};
+use time::OffsetDateTime;
+
+use crate::{
+    external::Vcs,
+    mit::{
+        ExpiryMode,
+        cmd::{
+            CONFIG_KEY_EXPIRES, CONFIG_KEY_EXPIRES_TIMEOUT, CONFIG_KEY_RELATE_EXPIRES,
+            CONFIG_KEY_RELATE_EXPIRES_TIMEOUT,
+            get_config_expiry_mode::{get_config_expiry_mode, get_config_relates_to_expiry_mode},
+        },
+    },
+    timeout::{Timeout, Workday, get_config_workday},
+};
+
+/// Keep a session going after a commit
+///
+/// When `mit.author.expiry-mode` is `sliding` the timeout the authors were
+/// set with is worked out again from now, and the same for the relates-to
+/// when `mit.relate.expiry-mode` is. A length of time pushes the expiry
+/// back, while a time of day or the end of the day or week stays where it
+/// was. Sessions that have already expired are left as they are.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn extend_session(config: &mut dyn Vcs) -> Result<()> {
+    let now = OffsetDateTime::now_utc();
+    let workday = get_config_workday(config)?;
+
+    if get_config_expiry_mode(config)? == ExpiryMode::Sliding {
+        slide(
+            config,
+            CONFIG_KEY_EXPIRES,
+            CONFIG_KEY_EXPIRES_TIMEOUT,
+            now,
+            &workday,
+        )
+        .wrap_err("failed to extend the authors' expiry time")?;
+    }
+
+    if get_config_relates_to_expiry_mode(config)? == ExpiryMode::Sliding {
+        slide(
+            config,
+            CONFIG_KEY_RELATE_EXPIRES,
+            CONFIG_KEY_RELATE_EXPIRES_TIMEOUT,
+            now,
+            &workday,
+        )
+        .wrap_err("failed to extend the relates-to expiry time")?;
+    }
+
+    Ok(())
+}
+
+fn slide(
+    config: &mut dyn Vcs,
+    expires_key: &str,
+    timeout_key: &str,
+    now: OffsetDateTime,
+    workday: &Workday,
+) -> Result<()> {
+    let (Some(expires), Some(timeout)) =
+        (config.get_i64(expires_key)?, config.get_str(timeout_key)?)
+    else {
+        return Ok(());
+    };
+
+    if expires <= now.unix_timestamp() {
+        return Ok(());
+    }
+
+    let expires_in = Timeout::from_str(timeout)?.duration_from(now, workday);
+    let extended = i64::try_from(expires_in.as_secs())
+        .ok()
+        .and_then(|seconds| now.unix_timestamp().checked_add(seconds))
+        .unwrap_or(i64::MAX);
+
+    config.set_i64(expires_key, expires.max(extended))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use time::{Date, Month, OffsetDateTime, Time, UtcOffset, Weekday};
+
+    use super::slide;
+    use crate::{external::InMemory, mit::extend_session, timeout::Workday};
+
+    fn session(expires_in: i64, timeout: &str) -> BTreeMap<String, String> {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let mut buffer = BTreeMap::new();
+        for prefix in ["mit.author", "mit.relate"] {
+            buffer.insert(format!("{prefix}.expires"), (now + expires_in).to_string());
+            buffer.insert(format!("{prefix}.expires-timeout"), timeout.into());
+        }
+        buffer
+    }
+
+    fn remaining(buffer: &BTreeMap<String, String>, key: &str) -> i64 {
+        buffer[key].parse::<i64>().unwrap() - OffsetDateTime::now_utc().unix_timestamp()
+    }
+
+    #[test]
+    fn fixed_sessions_are_not_extended() {
+        let mut buffer = session(60, "1h");
+        let before = buffer.clone();
+
+        extend_session(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert_eq!(
+            buffer, before,
+            "Expected nothing to change when the expiry is fixed"
+        );
+    }
+
+    #[test]
+    fn sliding_sessions_are_extended_by_their_timeout() {
+        let mut buffer = session(60, "1h");
+        buffer.insert("mit.author.expiry-mode".into(), "sliding".into());
+
+        extend_session(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert!(
+            remaining(&buffer, "mit.author.expires") > 3500,
+            "Expected the authors to expire an hour from now"
+        );
+        assert!(
+            remaining(&buffer, "mit.relate.expires") <= 60,
+            "Expected the relates-to to be left alone unless it's sliding too"
+        );
+    }
+
+    #[test]
+    fn relates_to_can_slide_too() {
+        let mut buffer = session(60, "1h");
+        buffer.insert("mit.relate.expiry-mode".into(), "sliding".into());
+
+        extend_session(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert!(
+            remaining(&buffer, "mit.relate.expires") > 3500,
+            "Expected the relates-to to expire an hour from now"
+        );
+    }
+
+    #[test]
+    fn expired_sessions_stay_expired() {
+        let mut buffer = session(-60, "1h");
+        buffer.insert("mit.author.expiry-mode".into(), "sliding".into());
+
+        extend_session(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert!(
+            remaining(&buffer, "mit.author.expires") < 0,
+            "Expected an expired session not to be brought back"
+        );
+    }
+
+    #[test]
+    fn calendar_timeouts_keep_their_deadline() {
+        // Wednesday, 16:00 in UTC
+        let now = Date::from_calendar_date(2024, Month::March, 6)
+            .unwrap()
+            .with_hms(16, 0, 0)
+            .unwrap()
+            .assume_utc();
+        let end_of_day = now.replace_time(Time::from_hms(17, 0, 0).unwrap());
+        let workday = Workday {
+            end: Time::from_hms(17, 0, 0).unwrap(),
+            days: vec![Weekday::Monday, Weekday::Friday],
+            offset: UtcOffset::UTC,
+        };
+        let mut buffer = BTreeMap::new();
+        buffer.insert(
+            "mit.author.expires".into(),
+            end_of_day.unix_timestamp().to_string(),
+        );
+        buffer.insert("mit.author.expires-timeout".into(), "end-of-day".into());
+
+        slide(
+            &mut InMemory::new(&mut buffer),
+            "mit.author.expires",
+            "mit.author.expires-timeout",
+            now,
+            &workday,
+        )
+        .unwrap();
+
+        assert_eq!(
+            buffer["mit.author.expires"],
+            end_of_day.unix_timestamp().to_string(),
+            "Expected the session to still end when the workday does"
+        );
+    }
+}
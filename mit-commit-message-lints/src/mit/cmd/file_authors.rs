@@ -0,0 +1,201 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use miette::{IntoDiagnostic, Result};
+
+use crate::mit::{
+    Author, Authors, AuthorsDocument,
+    cmd::{errors::Error::AuthorFileNotEditable, get_authors::writable_author_file},
+};
+
+/// Add or update an author in an authors file, keeping its formatting
+///
+/// The author is changed in the file they came from, or added to the last
+/// one when there is a list of author files. The file is created if it
+/// doesn't exist yet, as YAML if it has a `.yml` or `.yaml` extension,
+/// otherwise as TOML
+///
+/// # Errors
+///
+/// If the file can't be read or written, isn't a valid authors file, or
+/// can't be changed without changing other authors too
+pub fn set_file_authors(path: &str, initial: &str, author: &Author<'_>) -> Result<()> {
+    let path = writable_author_file(path, initial)?;
+    let mut document = read_document(&path)?;
+    let mut expected: Authors<'_> = document.authors()?;
+    let identities = expected
+        .authors
+        .get(initial)
+        .map(|existing| existing.identities().to_vec())
+        .unwrap_or_default();
+    expected
+        .authors
+        .insert(initial.into(), author.clone().with_identities(identities));
+    document.set(initial, author);
+    check_edit(&path, &document, &expected)?;
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent).into_diagnostic()?;
+    }
+
+    fs::write(&path, document.to_string()).into_diagnostic()
+}
+
+/// Remove an author from an authors file, keeping its formatting
+///
+/// The author is removed from the file they came from when there is a list
+/// of author files. Returns whether the author was in the file
+///
+/// # Errors
+///
+/// If the file can't be read or written, isn't a valid authors file, or
+/// can't be changed without changing other authors too
+pub fn remove_file_authors(path: &str, initial: &str) -> Result<bool> {
+    let path = writable_author_file(path, initial)?;
+    let mut document = read_document(&path)?;
+    let mut expected = document.authors()?;
+    expected.authors.remove(initial);
+
+    if !document.remove(initial) {
+        return Ok(false);
+    }
+
+    check_edit(&path, &document, &expected)?;
+    fs::write(&path, document.to_string()).into_diagnostic()?;
+    Ok(true)
+}
+
+/// Make sure the edited file reads back as the authors we expect, so a file
+/// we can't edit properly is left alone rather than corrupted
+fn check_edit(path: &str, document: &AuthorsDocument, expected: &Authors<'_>) -> Result<()> {
+    let edited = document
+        .authors()
+        .map_err(|_| AuthorFileNotEditable { path: path.into() })?;
+
+    if edited.authors != expected.authors || edited.groups() != expected.groups() {
+        return Err(AuthorFileNotEditable { path: path.into() }.into());
+    }
+
+    Ok(())
+}
+
+fn read_document(path: &str) -> Result<AuthorsDocument> {
+    let contents = match fs::read_to_string(path) {
+        Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+        result => result.into_diagnostic()?,
+    };
+    let prefer_yaml = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension == "yml" || extension == "yaml");
+
+    Ok(AuthorsDocument::parse(&contents, prefer_yaml)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use indoc::indoc;
+
+    use super::{remove_file_authors, set_file_authors};
+    use crate::mit::{Author, Authors};
+
+    #[test]
+    fn authors_can_be_added_to_and_removed_from_a_new_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("git-mit").join("mit.yml");
+        let path = path.to_str().unwrap();
+        let jane = Author::new("Jane Doe".into(), "jane@example.com".into(), None);
+
+        set_file_authors(path, "jd", &jane).unwrap();
+
+        let contents = fs::read_to_string(path).unwrap();
+        assert!(
+            contents.starts_with("---"),
+            "Expected a new .yml file to be written as YAML"
+        );
+        assert_eq!(
+            Authors::try_from(contents.as_str()).unwrap().get(&["jd"]),
+            vec![&jane],
+            "Expected the author to be in the file"
+        );
+        assert!(
+            remove_file_authors(path, "jd").unwrap(),
+            "Expected the author to be found"
+        );
+        assert!(
+            !remove_file_authors(path, "jd").unwrap(),
+            "Expected the author to be gone"
+        );
+    }
+
+    #[test]
+    fn files_that_cant_be_edited_safely_are_left_alone() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("mit.yml");
+        let contents = indoc!(
+            "
+            ---
+            bt: &billie
+              name: Billie Thompson
+              email: billie@example.com
+            bb: *billie
+            "
+        );
+        fs::write(&path, contents).unwrap();
+        let path = path.to_str().unwrap();
+        let jane = Author::new("Jane Doe".into(), "jane@example.com".into(), None);
+
+        assert!(
+            set_file_authors(path, "bt", &jane).is_err(),
+            "Expected an error when changing an anchor would change its aliases"
+        );
+        assert!(
+            remove_file_authors(path, "bt").is_err(),
+            "Expected an error when removing an anchor would break its aliases"
+        );
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            contents,
+            "Expected the file to be left as it was"
+        );
+    }
+
+    #[test]
+    fn authors_with_identities_can_be_changed() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("mit.toml");
+        fs::write(
+            &path,
+            indoc!(
+                r#"
+                [bt]
+                name = "Billie Thompson"
+                email = "billie@example.com"
+
+                [[bt.identities]]
+                remote = "*github.com:company/*"
+                email = "billie.thompson@company.example.com"
+                "#
+            ),
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+        let billie = Author::new("Billie Thompson".into(), "billie@example.net".into(), None);
+
+        set_file_authors(path, "bt", &billie).unwrap();
+
+        let contents = fs::read_to_string(path).unwrap();
+        let authors = Authors::try_from(contents.as_str()).unwrap();
+        let saved = authors.get(&["bt"]);
+        assert_eq!(
+            saved.first().map(|author| author.email()),
+            Some("billie@example.net"),
+            "Expected the author to be changed"
+        );
+        assert_eq!(
+            saved.first().map(|author| author.identities().len()),
+            Some(1),
+            "Expected the author to keep their identities"
+        );
+    }
+}
@@ -1,13 +1,12 @@
 use std::{
     convert::TryFrom,
-    fs,
-    path::PathBuf,
-    process::{Command, Stdio},
+    env, fs,
+    path::{Path, PathBuf},
 };
 
 use miette::{IntoDiagnostic, Result};
 
-use crate::mit::Authors;
+use crate::{external::run_exec, mit::Authors};
 
 /// A generic structure to pass around details needed to get authors
 #[derive(Debug, Clone)]
@@ -39,25 +38,98 @@ pub trait AuthorArgs {
 
 /// Get authors from config
 ///
+/// The author file can be a list of files and directories, separated like
+/// `PATH` is. Directories contain `.toml`, `.yml` and `.yaml` files, read in
+/// order of their names. Authors in later files take precedence, and each
+/// author remembers which file it came from.
+///
 /// # Errors
 ///
 /// miette error on failure of command
-pub fn get_authors<'a>(args: &'a dyn AuthorArgs) -> Result<Authors<'a>> {
-    let toml = args
-        .author_command()
-        .map_or_else(|| from_file(args), from_exec)?;
-    let authors: Authors<'a> = Authors::try_from(toml)?;
-    Ok(authors)
-}
+pub fn get_authors(args: &dyn AuthorArgs) -> Result<Authors<'_>> {
+    if let Some(command) = args.author_command() {
+        return Ok(Authors::try_from(from_exec(command)?)?.with_source(command));
+    }
+
+    let paths = args
+        .author_file()
+        .map_or_else(|| Err(super::errors::Error::AuthorFileNotSet), Ok)?;
 
-fn from_file(args: &dyn AuthorArgs) -> Result<String> {
-    args.author_file()
-        .map_or_else(|| Err(super::errors::Error::AuthorFileNotSet.into()), Ok)
-        .and_then(|path| match path {
-            "$HOME/.config/git-mit/mit.toml" => author_file_path(),
-            _ => Ok(path.into()),
+    author_files(paths)?
+        .into_iter()
+        .try_fold(Authors::default(), |authors, path| {
+            let contents = fs::read_to_string(&path).into_diagnostic()?;
+            Ok(authors.merge(&Authors::try_from(contents)?.with_source(&path)))
         })
-        .and_then(|path| fs::read_to_string(path).into_diagnostic())
+}
+
+/// The files to read authors from, in the order they are merged
+fn author_files(paths: &str) -> Result<Vec<String>> {
+    let mut files = vec![];
+
+    for path in env::split_paths(paths) {
+        let path = PathBuf::from(resolve_author_file(&path.to_string_lossy())?);
+
+        if path.is_dir() {
+            let mut entries = fs::read_dir(&path)
+                .into_diagnostic()?
+                .map(|entry| entry.map(|entry| entry.path()).into_diagnostic())
+                .collect::<Result<Vec<_>>>()?;
+            entries.retain(|entry| {
+                entry.is_file()
+                    && entry.extension().is_some_and(|extension| {
+                        extension == "toml" || extension == "yml" || extension == "yaml"
+                    })
+            });
+            entries.sort();
+            files.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| entry.to_string_lossy().to_string()),
+            );
+        } else {
+            files.push(path.to_string_lossy().to_string());
+        }
+    }
+
+    Ok(files)
+}
+
+/// The author file that changes to an author are written to
+///
+/// This is the file the author came from, so they are edited rather than
+/// defined a second time. Authors that aren't in any of the files go in the
+/// last one, as it takes precedence.
+///
+/// # Errors
+///
+/// If one of the author files can't be read, or there are no files to write
+/// to, only empty directories
+pub fn writable_author_file(paths: &str, initial: &str) -> Result<String> {
+    let files = author_files(paths)?;
+    let mut authors = Authors::default();
+
+    for path in files.iter().filter(|path| Path::new(path).is_file()) {
+        let contents = fs::read_to_string(path).into_diagnostic()?;
+        authors = authors.merge(&Authors::try_from(contents)?.with_source(path));
+    }
+
+    if let Some(source) = authors.sources().get(initial) {
+        return Ok(source.clone());
+    }
+
+    files
+        .last()
+        .cloned()
+        .ok_or_else(|| super::errors::Error::AuthorFileIsDirectory { path: paths.into() }.into())
+}
+
+/// Replace the default authors file location with the real path
+pub fn resolve_author_file(path: &str) -> Result<String> {
+    match path {
+        "$HOME/.config/git-mit/mit.toml" => author_file_path(),
+        _ => Ok(path.into()),
+    }
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -85,21 +157,16 @@ fn author_file_path() -> Result<String> {
 }
 
 fn from_exec(command: &str) -> Result<String> {
-    let commandline = shell_words::split(command).into_diagnostic()?;
-    Command::new(commandline.first().unwrap_or(&String::new()))
-        .stderr(Stdio::inherit())
-        .args(commandline.iter().skip(1))
-        .output()
-        .into_diagnostic()
-        .and_then(|output| {
-            String::from_utf8(output.stdout).map_err(|source| {
-                super::errors::Error::ExecUtf8 {
-                    source,
-                    command: command.to_string(),
-                }
-                .into()
-            })
-        })
+    let current_dir = env::current_dir().into_diagnostic()?;
+    let output = run_exec(command, &current_dir, None)?;
+
+    String::from_utf8(output.stdout).map_err(|source| {
+        super::errors::Error::ExecUtf8 {
+            source,
+            command: command.to_string(),
+        }
+        .into()
+    })
 }
 
 #[cfg(test)]
@@ -145,7 +212,99 @@ mod tests {
         let _ = std::fs::remove_file(&temp_file);
     }
 
-    use super::AuthorArgs;
+    use super::{AuthorArgs, writable_author_file};
+
+    #[test]
+    fn authors_can_come_from_a_list_of_files_and_directories() {
+        let directory = tempfile::tempdir().unwrap();
+        let company = directory.path().join("company.toml");
+        let team = directory.path().join("mit.d");
+        std::fs::create_dir(&team).unwrap();
+        std::fs::write(
+            &company,
+            "[bt]\nname = \"Billie Thompson\"\nemail = \"billie@example.com\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            team.join("a.yml"),
+            "---\nbt:\n  name: Billie Thompson\n  email: bt@example.com\n",
+        )
+        .unwrap();
+        std::fs::write(
+            team.join("b.toml"),
+            "[se]\nname = \"Someone Else\"\nemail = \"someone@example.com\"\n",
+        )
+        .unwrap();
+        std::fs::write(team.join("notes.txt"), "Not an authors file").unwrap();
+
+        let paths = std::env::join_paths([&company, &team]).unwrap();
+        let paths = paths.to_str().unwrap();
+        let args = GenericArgs {
+            author_command: None,
+            author_file: Some(paths),
+        };
+        let authors = get_authors(&args).unwrap();
+
+        assert_eq!(
+            authors.get(&["bt"])[0].email(),
+            "bt@example.com",
+            "Expected files later in the list to take precedence"
+        );
+        assert_eq!(
+            authors.sources().get("se"),
+            Some(&team.join("b.toml").to_string_lossy().to_string()),
+            "Expected the author to remember which file it came from"
+        );
+        assert_eq!(
+            authors.conflicts().get("bt").map(Vec::len),
+            Some(2),
+            "Expected the different definitions of bt to be a conflict"
+        );
+    }
+
+    #[test]
+    fn authors_are_written_to_the_file_they_came_from() {
+        let directory = tempfile::tempdir().unwrap();
+        let company = directory.path().join("company.toml");
+        let team = directory.path().join("mit.d");
+        std::fs::create_dir(&team).unwrap();
+        std::fs::write(
+            &company,
+            "[bt]\nname = \"Billie Thompson\"\nemail = \"billie@example.com\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            team.join("a.toml"),
+            "[se]\nname = \"Someone Else\"\nemail = \"someone@example.com\"\n",
+        )
+        .unwrap();
+        std::fs::write(team.join("b.yml"), "---\n").unwrap();
+
+        let paths = std::env::join_paths([&company, &team]).unwrap();
+        let paths = paths.to_str().unwrap();
+
+        assert_eq!(
+            writable_author_file(paths, "bt").unwrap(),
+            company.to_string_lossy(),
+            "Expected an author to be changed where they are defined"
+        );
+        assert_eq!(
+            writable_author_file(paths, "jd").unwrap(),
+            team.join("b.yml").to_string_lossy(),
+            "Expected new authors to go in the last file, even in a directory"
+        );
+        assert!(
+            writable_author_file(directory.path().join("empty").to_str().unwrap(), "jd").is_ok(),
+            "Expected a file that doesn't exist yet to be written to"
+        );
+
+        let empty = directory.path().join("empty.d");
+        std::fs::create_dir(&empty).unwrap();
+        assert!(
+            writable_author_file(empty.to_str().unwrap(), "jd").is_err(),
+            "Expected an error when there's no file to write to"
+        );
+    }
 
     #[test]
     fn author_command_passes_through_some_value() {
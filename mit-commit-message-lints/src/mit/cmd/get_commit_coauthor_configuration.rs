@@ -1,15 +1,22 @@
-use std::borrow::Cow;
-
 use miette::{IntoDiagnostic, Result};
 use time::OffsetDateTime;
 
 use crate::{
     external::Vcs,
-    mit::{Author, AuthorState, cmd::CONFIG_KEY_EXPIRES},
+    mit::{
+        Author, AuthorState,
+        cmd::{
+            CONFIG_KEY_EXPIRES,
+            vcs::{author_for_repository, get_vcs_coauthors},
+        },
+    },
 };
 
 /// Get the co-authors that are currently defined for this vcs config source
 ///
+/// Co-authors with identities for other repositories are given as the one
+/// that matches this repository
+///
 /// # Errors
 ///
 /// Will fail if reading or writing from the VCS config fails, or it contains
@@ -34,29 +41,10 @@ pub fn get_commit_coauthor_configuration(config: &dyn Vcs) -> Result<AuthorState
 }
 
 fn get_vcs_authors(config: &'_ dyn Vcs) -> Result<Vec<Author<'_>>> {
-    let co_author_names = get_vcs_coauthor_names(config)?;
-    let co_author_emails = get_vcs_coauthor_emails(config)?;
-
-    Ok(co_author_names
-        .into_iter()
-        .zip(co_author_emails)
-        .filter_map(new_author)
-        .collect())
-}
-
-fn new_author<'a>(parameters: (Option<Cow<'a, str>>, Option<Cow<'a, str>>)) -> Option<Author<'a>> {
-    match parameters {
-        (Some(name), Some(email)) => Some(Author::new(name, email, None)),
-        _ => None,
-    }
-}
-
-fn get_vcs_coauthor_names(config: &'_ dyn Vcs) -> Result<Vec<Option<Cow<'_, str>>>> {
-    super::vcs::get_vcs_coauthors_config(config, "name")
-}
-
-fn get_vcs_coauthor_emails(config: &'_ dyn Vcs) -> Result<Vec<Option<Cow<'_, str>>>> {
-    super::vcs::get_vcs_coauthors_config(config, "email")
+    get_vcs_coauthors(config)?
+        .iter()
+        .map(|author| author_for_repository(config, author))
+        .collect()
 }
 
 #[cfg(test)]
@@ -137,6 +125,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn coauthors_use_the_identity_for_this_repository() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert(
+            super::CONFIG_KEY_EXPIRES.into(),
+            format!("{}", epoch_with_offset(add_100_seconds)),
+        );
+        buffer.insert(
+            "mit.author.coauthors.0.email".into(),
+            "annie@example.com".into(),
+        );
+        buffer.insert("mit.author.coauthors.0.name".into(), "Annie Example".into());
+        buffer.insert(
+            "mit.author.coauthors.0.identity.0.path".into(),
+            "/oss/*".into(),
+        );
+        buffer.insert(
+            "mit.author.coauthors.0.identity.0.email".into(),
+            "annie@home.example.com".into(),
+        );
+        let vcs = InMemory::new(&mut buffer).with_workdir("/oss/project".into());
+
+        let actual = get_commit_coauthor_configuration(&vcs).expect("Failed to read VCS config");
+        let AuthorState::Some(authors) = actual else {
+            panic!("Expected there to be co-authors, instead got {actual:?}");
+        };
+
+        assert_eq!(
+            authors.iter().map(Author::email).collect::<Vec<_>>(),
+            vec!["annie@home.example.com"],
+            "Expected the identity matching the repository path to be used"
+        );
+    }
+
     fn add_100_seconds(x: Duration) -> Duration {
         x.add(Duration::from_secs(100))
     }
@@ -181,6 +203,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn the_branch_session_is_preferred() {
+        let mut buffer = BTreeMap::from([
+            (
+                "mit.author.expires".to_string(),
+                format!("{}", epoch_with_offset(add_100_seconds)),
+            ),
+            (
+                "mit.author.coauthors.0.email".to_string(),
+                "annie@example.com".to_string(),
+            ),
+            (
+                "mit.author.coauthors.0.name".to_string(),
+                "Annie Example".to_string(),
+            ),
+            (
+                "mit.branch.feature.author.expires".to_string(),
+                format!("{}", epoch_with_offset(subtract_100_seconds)),
+            ),
+        ]);
+
+        let vcs = InMemory::new(&mut buffer).with_branch("feature");
+
+        let actual = get_commit_coauthor_configuration(&vcs).expect("Failed to read VCS config");
+
+        assert!(
+            matches!(actual, AuthorState::Timeout(_)),
+            "Expected the branch's expired session rather than the repository's, instead got {actual:?}"
+        );
+    }
+
     fn epoch_with_offset(x: fn(Duration) -> Duration) -> i64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
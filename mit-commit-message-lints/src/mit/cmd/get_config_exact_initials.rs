@@ -0,0 +1,50 @@
+use miette::Result;
+
+use crate::external::Vcs;
+
+const CONFIG_KEY_EXACT_INITIALS: &str = "mit.author.exact-initials";
+
+/// Whether initials must be given exactly as they are configured
+///
+/// Defaults to `false`, meaning case is ignored and unique prefixes are
+/// accepted
+///
+/// # Errors
+///
+/// If the config value isn't a boolean
+pub fn get_config_exact_initials(store: &dyn Vcs) -> Result<bool> {
+    Ok(store
+        .get_bool(CONFIG_KEY_EXACT_INITIALS)?
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::get_config_exact_initials;
+    use crate::external::InMemory;
+
+    #[test]
+    fn initials_are_not_exact_by_default() {
+        let mut buffer = BTreeMap::new();
+        let vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            !get_config_exact_initials(&vcs).unwrap(),
+            "Expected loose matching when nothing is configured"
+        );
+    }
+
+    #[test]
+    fn exact_initials_can_be_turned_on() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.author.exact-initials".into(), "true".into());
+        let vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            get_config_exact_initials(&vcs).unwrap(),
+            "Expected exact matching when it is turned on"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use miette::Result;
+
+use crate::{external::Vcs, mit::lib::expiry_mode::ExpiryMode};
+
+/// Get whether committing extends the authors' session
+///
+/// Returns `ExpiryMode::Fixed` when it's not configured.
+///
+/// # Errors
+///
+/// Returns an error if reading the git config fails, or if the stored
+/// value isn't a valid expiry mode.
+pub fn get_config_expiry_mode(store: &dyn Vcs) -> Result<ExpiryMode> {
+    read(store, super::CONFIG_KEY_EXPIRY_MODE)
+}
+
+/// Get whether committing extends the relates-to too
+///
+/// Returns `ExpiryMode::Fixed` when it's not configured.
+///
+/// # Errors
+///
+/// Returns an error if reading the git config fails, or if the stored
+/// value isn't a valid expiry mode.
+pub fn get_config_relates_to_expiry_mode(store: &dyn Vcs) -> Result<ExpiryMode> {
+    read(store, super::CONFIG_KEY_RELATE_EXPIRY_MODE)
+}
+
+fn read(store: &dyn Vcs, key: &str) -> Result<ExpiryMode> {
+    match store.get_str(key)? {
+        Some(mode) => Ok(mode.parse()?),
+        None => Ok(ExpiryMode::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        external::InMemory,
+        mit::{ExpiryMode, get_config_expiry_mode, get_config_relates_to_expiry_mode},
+    };
+
+    #[test]
+    fn expiry_is_fixed_when_not_set() {
+        let mut buffer = BTreeMap::new();
+        let vcs_config = InMemory::new(&mut buffer);
+
+        assert_eq!(
+            (
+                get_config_expiry_mode(&vcs_config).unwrap(),
+                get_config_relates_to_expiry_mode(&vcs_config).unwrap()
+            ),
+            (ExpiryMode::Fixed, ExpiryMode::Fixed),
+            "Expected expiry to be fixed when it's not configured"
+        );
+    }
+
+    #[test]
+    fn invalid_expiry_modes_are_errors() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.author.expiry-mode".into(), "forever".into());
+        let vcs_config = InMemory::new(&mut buffer);
+
+        assert!(
+            get_config_expiry_mode(&vcs_config).is_err(),
+            "Expected an error when the expiry mode is set to an invalid value"
+        );
+    }
+}
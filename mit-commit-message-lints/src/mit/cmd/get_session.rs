@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use miette::Result;
+use time::OffsetDateTime;
+
+use crate::{
+    external::Vcs,
+    mit::{
+        Author, AuthorState, cmd::CONFIG_KEY_EXPIRES, get_commit_coauthor_configuration,
+        get_config_rotation, lib::session::Session,
+    },
+    relates::get_relate_to_configuration,
+};
+
+/// Get a summary of the pairing session currently defined for this vcs config
+/// source
+///
+/// Co-authors are only included while the session hasn't expired
+///
+/// # Errors
+///
+/// Will fail if reading from the VCS config fails, or it contains data in an
+/// incorrect format
+pub fn get_session(config: &dyn Vcs) -> Result<Session<'_>> {
+    let expires = config.get_i64(CONFIG_KEY_EXPIRES)?;
+    let remaining =
+        expires.map(|expires| (expires - OffsetDateTime::now_utc().unix_timestamp()).max(0));
+    let coauthors = match get_commit_coauthor_configuration(config)? {
+        AuthorState::Some(coauthors) => coauthors,
+        AuthorState::Timeout(_) | AuthorState::None => vec![],
+    };
+
+    let branch = match config.branch() {
+        Some(branch)
+            if config
+                .get_i64(&format!("mit.branch.{branch}.author.expires"))?
+                .is_some() =>
+        {
+            Some(branch.to_string())
+        }
+        _ => None,
+    };
+
+    Ok(Session {
+        author: get_vcs_user(config)?,
+        coauthors,
+        branch,
+        expires,
+        remaining,
+        relates_to: get_relate_to_configuration(config)?.map(|relates| relates.to().to_string()),
+        rotation: get_config_rotation(config)?,
+        hooks: BTreeMap::new(),
+    })
+}
+
+fn get_vcs_user(config: &dyn Vcs) -> Result<Option<Author<'_>>> {
+    let name = config.get_str("user.name")?;
+    let email = config.get_str("user.email")?;
+    let signingkey = config.get_str("user.signingkey")?;
+
+    Ok(name
+        .zip(email)
+        .map(|(name, email)| Author::new(name.into(), email.into(), signingkey.map(Into::into))))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use time::OffsetDateTime;
+
+    use crate::{
+        external::InMemory,
+        mit::{Author, RotationOption, get_session},
+    };
+
+    #[test]
+    fn an_active_session_has_everyone_in_it() {
+        let expires = OffsetDateTime::now_utc().unix_timestamp() + 600;
+        let mut buffer = BTreeMap::new();
+        buffer.insert("user.name".into(), "Billie Thompson".into());
+        buffer.insert("user.email".into(), "billie@example.com".into());
+        buffer.insert("mit.author.expires".into(), format!("{expires}"));
+        buffer.insert("mit.author.coauthors.0.name".into(), "Annie Example".into());
+        buffer.insert(
+            "mit.author.coauthors.0.email".into(),
+            "annie@example.com".into(),
+        );
+        buffer.insert("mit.relate.expires".into(), format!("{expires}"));
+        buffer.insert("mit.relate.to".into(), "[#12345678]".into());
+        buffer.insert("mit.author.rotate".into(), "round-robin".into());
+        let vcs = InMemory::new(&mut buffer);
+
+        let session = get_session(&vcs).expect("Failed to read VCS config");
+
+        assert_eq!(
+            session.author,
+            Some(Author::new(
+                "Billie Thompson".into(),
+                "billie@example.com".into(),
+                None
+            )),
+            "Expected the primary author to come from the git user"
+        );
+        assert_eq!(
+            session.coauthors,
+            vec![Author::new(
+                "Annie Example".into(),
+                "annie@example.com".into(),
+                None
+            )],
+            "Expected the co-authors to be included"
+        );
+        assert!(
+            session.remaining.is_some_and(|remaining| remaining > 590),
+            "Expected about 10 minutes to be remaining, got {:?}",
+            session.remaining
+        );
+        assert_eq!(
+            session.relates_to.as_deref(),
+            Some("[#12345678]"),
+            "Expected the relates-to to be included"
+        );
+        assert_eq!(
+            session.rotation,
+            Some(RotationOption::RoundRobin),
+            "Expected the rotation to be included"
+        );
+    }
+
+    #[test]
+    fn sessions_kept_for_a_branch_say_so() {
+        let expires = OffsetDateTime::now_utc().unix_timestamp() + 600;
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.author.expires".into(), format!("{expires}"));
+        buffer.insert(
+            "mit.branch.feature.author.expires".into(),
+            format!("{}", expires + 600),
+        );
+
+        let feature = InMemory::new(&mut buffer).with_branch("feature");
+        let session = get_session(&feature).expect("Failed to read VCS config");
+        assert_eq!(
+            (session.branch.as_deref(), session.expires),
+            (Some("feature"), Some(expires + 600)),
+            "Expected the session kept for the branch"
+        );
+
+        let main = InMemory::new(&mut buffer).with_branch("main");
+        let session = get_session(&main).expect("Failed to read VCS config");
+        assert_eq!(
+            (session.branch.as_deref(), session.expires),
+            (None, Some(expires)),
+            "Expected the repository's session on other branches"
+        );
+    }
+
+    #[test]
+    fn an_expired_session_has_no_coauthors() {
+        let expires = OffsetDateTime::now_utc().unix_timestamp() - 600;
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.author.expires".into(), format!("{expires}"));
+        buffer.insert("mit.author.coauthors.0.name".into(), "Annie Example".into());
+        buffer.insert(
+            "mit.author.coauthors.0.email".into(),
+            "annie@example.com".into(),
+        );
+        let vcs = InMemory::new(&mut buffer);
+
+        let session = get_session(&vcs).expect("Failed to read VCS config");
+
+        assert!(
+            session.coauthors.is_empty(),
+            "Expected no co-authors once the session has expired"
+        );
+        assert_eq!(
+            session.remaining,
+            Some(0),
+            "Expected no time to be remaining"
+        );
+    }
+
+    #[test]
+    fn there_is_no_expiry_if_there_has_never_been_a_session() {
+        let mut buffer = BTreeMap::new();
+        let vcs = InMemory::new(&mut buffer);
+
+        let session = get_session(&vcs).expect("Failed to read VCS config");
+
+        assert_eq!(session.expires, None, "Expected no expiry");
+        assert_eq!(session.author, None, "Expected no primary author");
+    }
+}
@@ -1,13 +1,32 @@
 //! Mit commands
 const CONFIG_KEY_EXPIRES: &str = "mit.author.expires";
+const CONFIG_KEY_EXPIRES_TIMEOUT: &str = "mit.author.expires-timeout";
+const CONFIG_KEY_EXPIRY_MODE: &str = "mit.author.expiry-mode";
+const CONFIG_KEY_GROUP_PREFIX: &str = "mit.author.group.";
+pub(crate) const CONFIG_KEY_RELATE_EXPIRES: &str = "mit.relate.expires";
+pub(crate) const CONFIG_KEY_RELATE_EXPIRES_TIMEOUT: &str = "mit.relate.expires-timeout";
+const CONFIG_KEY_RELATE_EXPIRY_MODE: &str = "mit.relate.expiry-mode";
 const CONFIG_KEY_ROTATION: &str = "mit.author.rotate";
+const CONFIG_KEY_SIGNING_FORMAT: &str = "gpg.format";
 
+pub(crate) mod clear_commit_authors;
+pub(crate) mod extend_session;
+pub(crate) mod file_authors;
 pub(crate) mod get_authors;
 pub(crate) mod get_commit_coauthor_configuration;
+pub(crate) mod get_config_exact_initials;
+pub(crate) mod get_session;
+pub(crate) mod once_authors;
+pub(crate) mod previous_identity;
+pub(crate) mod remove_config_authors;
+pub(crate) mod session_author;
 pub(crate) mod set_commit_authors;
 pub(crate) mod set_config_authors;
+pub(crate) mod set_config_group;
 
 pub mod errors;
+/// Whether committing keeps a session going
+pub mod get_config_expiry_mode;
 pub mod get_config_non_clean_behaviour;
 
 /// Configuration for rotating primary author across commits
@@ -17,6 +36,8 @@ pub mod get_config_non_clean_behaviour;
 pub mod get_config_rotation;
 /// Rotate the primary author among configured authors
 pub mod rotate_authors;
+/// Choose whether committing keeps a session going
+pub mod set_config_expiry_mode;
 pub mod set_config_non_clean_behaviour;
 /// Configuration for rotating primary author across commits
 pub mod set_config_rotation;
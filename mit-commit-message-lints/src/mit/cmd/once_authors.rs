@@ -0,0 +1,132 @@
+use miette::{Result, WrapErr};
+
+use crate::{
+    external::Vcs,
+    mit::{Author, lib::once_authors::OnceAuthors},
+};
+
+const CONFIG_KEY_ONCE_HEAD: &str = "mit.author.once.head";
+
+/// Set the co-authors for just the next commit
+///
+/// `head` is the commit `HEAD` points at now, so we can tell when the next
+/// commit has been made.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn set_once_authors(
+    config: &mut dyn Vcs,
+    authors: &[&Author<'_>],
+    head: Option<&str>,
+) -> Result<()> {
+    clear_once_authors(config)?;
+
+    authors
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, author)| {
+            config.set_str(&format!("mit.author.once.{index}.name"), author.name())?;
+            config.set_str(&format!("mit.author.once.{index}.email"), author.email())
+        })
+        .and_then(|()| config.set_str(CONFIG_KEY_ONCE_HEAD, head.unwrap_or_default()))
+        .wrap_err("failed to set the authors for the next commit")
+}
+
+/// Get the co-authors for just the next commit, if there are any
+///
+/// # Errors
+///
+/// If reading the VCS config fails
+pub fn get_once_authors(config: &dyn Vcs) -> Result<Option<OnceAuthors<'_>>> {
+    let Some(head) = config.get_str(CONFIG_KEY_ONCE_HEAD)? else {
+        return Ok(None);
+    };
+
+    let mut authors = vec![];
+    for index in 0.. {
+        let name = config.get_str(&format!("mit.author.once.{index}.name"))?;
+        let email = config.get_str(&format!("mit.author.once.{index}.email"))?;
+
+        match (name, email) {
+            (Some(name), Some(email)) => authors.push(Author::new(name.into(), email.into(), None)),
+            _ => break,
+        }
+    }
+
+    Ok(Some(OnceAuthors {
+        authors,
+        head: Some(head).filter(|head| !head.is_empty()).map(String::from),
+    }))
+}
+
+/// Remove the co-authors for just the next commit
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn clear_once_authors(config: &mut dyn Vcs) -> Result<()> {
+    config
+        .entries(Some("mit.author.once.*"))?
+        .iter()
+        .try_for_each(|key| config.remove(key))
+        .wrap_err("failed to remove the authors for the next commit")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        external::InMemory,
+        mit::{Author, clear_once_authors, get_once_authors, set_once_authors},
+    };
+
+    #[test]
+    fn once_authors_can_be_read_back() {
+        let mut buffer = BTreeMap::new();
+        let mut vcs = InMemory::new(&mut buffer);
+        let author = Author::new("Someone Else".into(), "someone@example.com".into(), None);
+
+        set_once_authors(&mut vcs, &[&author], Some("aaaa")).unwrap();
+        let once = get_once_authors(&vcs).unwrap().unwrap();
+
+        assert_eq!(once.authors, vec![author], "Expected the authors back");
+        assert_eq!(
+            once.head.as_deref(),
+            Some("aaaa"),
+            "Expected the head to be remembered"
+        );
+    }
+
+    #[test]
+    fn once_authors_are_separate_from_the_session() {
+        let mut buffer = BTreeMap::new();
+        let mut vcs = InMemory::new(&mut buffer);
+        let author = Author::new("Someone Else".into(), "someone@example.com".into(), None);
+
+        set_once_authors(&mut vcs, &[&author], None).unwrap();
+
+        assert!(
+            buffer.keys().all(|key| key.starts_with("mit.author.once.")),
+            "Expected only once keys to be written, got {:?}",
+            buffer.keys()
+        );
+    }
+
+    #[test]
+    fn once_authors_can_be_cleared() {
+        let mut buffer = BTreeMap::new();
+        let mut vcs = InMemory::new(&mut buffer);
+        let author = Author::new("Someone Else".into(), "someone@example.com".into(), None);
+        set_once_authors(&mut vcs, &[&author], None).unwrap();
+
+        clear_once_authors(&mut vcs).unwrap();
+
+        assert_eq!(
+            get_once_authors(&vcs).unwrap(),
+            None,
+            "Expected no once authors"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use miette::{Result, WrapErr};
+
+use crate::{external::Vcs, mit::get_commit_coauthor_configuration};
+
+const CONFIG_KEY_PREVIOUS_SAVED: &str = "mit.author.previous.saved";
+const IDENTITY_KEYS: [(&str, &str); 4] = [
+    ("user.name", "mit.author.previous.name"),
+    ("user.email", "mit.author.previous.email"),
+    ("user.signingkey", "mit.author.previous.signingkey"),
+    ("gpg.format", "mit.author.previous.signingformat"),
+];
+
+/// Save the `user.*` identity and `gpg.format` before a pairing session
+/// replaces them
+///
+/// Nothing is saved if there's already a saved identity, or a session is in
+/// progress, so the identity saved is always the one from before pairing
+/// started. Only values set in the config the session is saved to are kept,
+/// so an identity from the global config isn't copied into the repository.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn save_previous_identity(config: &mut dyn Vcs) -> Result<()> {
+    if has_previous_identity(config)? || get_commit_coauthor_configuration(config)?.is_some() {
+        return Ok(());
+    }
+
+    IDENTITY_KEYS
+        .iter()
+        .try_for_each(|(user_key, previous_key)| {
+            if let Some(value) = config.get_target_str(user_key)? {
+                config.set_str(previous_key, &value)?;
+            }
+
+            Ok(())
+        })
+        .and_then(|()| config.set_str(CONFIG_KEY_PREVIOUS_SAVED, "true"))
+        .wrap_err("failed to save the git author from before the session")
+}
+
+/// Put back the `user.*` identity and `gpg.format` from before the pairing
+/// session
+///
+/// If no identity was saved, the session's identity is removed, so the one
+/// from your global git config is used again.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn restore_previous_identity(config: &mut dyn Vcs) -> Result<()> {
+    let saved = has_previous_identity(config)?;
+
+    IDENTITY_KEYS
+        .iter()
+        .try_for_each(|(user_key, previous_key)| {
+            match config.get_str(previous_key)?.map(String::from) {
+                Some(value) if saved => config.set_str(user_key, &value)?,
+                _ => config.remove_if_set(user_key)?,
+            }
+
+            config.remove_if_set(previous_key)
+        })
+        .and_then(|()| config.remove_if_set(CONFIG_KEY_PREVIOUS_SAVED))
+        .wrap_err("failed to restore the git author from before the session")
+}
+
+/// Use the `user.*` identity and `gpg.format` from before pairing again,
+/// while keeping them saved
+///
+/// Nothing is changed unless an identity was saved, as otherwise git-mit
+/// hasn't replaced the identity.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn reapply_previous_identity(config: &mut dyn Vcs) -> Result<()> {
+    if !has_previous_identity(config)? {
+        return Ok(());
+    }
+
+    IDENTITY_KEYS
+        .iter()
+        .try_for_each(|(user_key, previous_key)| {
+            match config.get_str(previous_key)?.map(String::from) {
+                Some(value) => config.set_str(user_key, &value),
+                None => config.remove_if_set(user_key),
+            }
+        })
+        .wrap_err("failed to put back the git author from before the session")
+}
+
+/// Restore the identity from before the session, if the session has expired
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn restore_previous_identity_if_expired(config: &mut dyn Vcs) -> Result<()> {
+    if get_commit_coauthor_configuration(config)?.is_timeout() && has_previous_identity(config)? {
+        restore_previous_identity(config)?;
+    }
+
+    Ok(())
+}
+
+fn has_previous_identity(config: &dyn Vcs) -> Result<bool> {
+    Ok(config
+        .get_bool(CONFIG_KEY_PREVIOUS_SAVED)?
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use time::OffsetDateTime;
+
+    use super::{
+        restore_previous_identity, restore_previous_identity_if_expired, save_previous_identity,
+    };
+    use crate::external::InMemory;
+
+    fn solo() -> BTreeMap<String, String> {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("user.name".into(), "Solo Developer".into());
+        buffer.insert("user.email".into(), "solo@example.com".into());
+        buffer
+    }
+
+    #[test]
+    fn the_identity_is_saved() {
+        let mut buffer = solo();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        save_previous_identity(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer.get("mit.author.previous.name"),
+            Some(&"Solo Developer".to_string()),
+            "Expected the name to be saved"
+        );
+        assert_eq!(
+            buffer.get("mit.author.previous.email"),
+            Some(&"solo@example.com".to_string()),
+            "Expected the email to be saved"
+        );
+        assert_eq!(
+            buffer.get("mit.author.previous.signingkey"),
+            None,
+            "Expected no signing key to be saved when there wasn't one"
+        );
+    }
+
+    #[test]
+    fn an_identity_that_is_already_saved_is_not_replaced() {
+        let mut buffer = solo();
+        let mut vcs = InMemory::new(&mut buffer);
+        save_previous_identity(&mut vcs).unwrap();
+        buffer.insert("user.name".into(), "Pair Programmer".into());
+        let mut vcs = InMemory::new(&mut buffer);
+
+        save_previous_identity(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer.get("mit.author.previous.name"),
+            Some(&"Solo Developer".to_string()),
+            "Expected the identity from before the session to be kept"
+        );
+    }
+
+    #[test]
+    fn the_identity_is_not_saved_during_a_session() {
+        let mut buffer = solo();
+        buffer.insert(
+            "mit.author.expires".into(),
+            format!("{}", OffsetDateTime::now_utc().unix_timestamp() + 600),
+        );
+        let mut vcs = InMemory::new(&mut buffer);
+
+        save_previous_identity(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer.get("mit.author.previous.saved"),
+            None,
+            "Expected nothing to be saved"
+        );
+    }
+
+    #[test]
+    fn the_identity_is_restored() {
+        let mut buffer = solo();
+        let mut vcs = InMemory::new(&mut buffer);
+        save_previous_identity(&mut vcs).unwrap();
+        buffer.insert("user.name".into(), "Pair Programmer".into());
+        buffer.insert("user.email".into(), "pair@example.com".into());
+        buffer.insert("user.signingkey".into(), "0A46826A".into());
+        buffer.insert("gpg.format".into(), "ssh".into());
+        let mut vcs = InMemory::new(&mut buffer);
+
+        restore_previous_identity(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer,
+            solo(),
+            "Expected the identity and signing format to be restored, and the saved copy removed"
+        );
+    }
+
+    #[test]
+    fn the_session_identity_is_removed_if_nothing_was_saved() {
+        let mut buffer = solo();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        restore_previous_identity(&mut vcs).unwrap();
+
+        assert!(buffer.is_empty(), "Expected the identity to be removed");
+    }
+
+    #[test]
+    fn the_identity_is_restored_once_the_session_expires() {
+        let mut buffer = solo();
+        let mut vcs = InMemory::new(&mut buffer);
+        save_previous_identity(&mut vcs).unwrap();
+        buffer.insert("user.name".into(), "Pair Programmer".into());
+        buffer.insert(
+            "mit.author.expires".into(),
+            format!("{}", OffsetDateTime::now_utc().unix_timestamp() - 600),
+        );
+        let mut vcs = InMemory::new(&mut buffer);
+
+        restore_previous_identity_if_expired(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer.get("user.name"),
+            Some(&"Solo Developer".to_string()),
+            "Expected the identity to be restored"
+        );
+    }
+
+    #[test]
+    fn the_identity_is_left_alone_while_the_session_is_active() {
+        let mut buffer = solo();
+        let mut vcs = InMemory::new(&mut buffer);
+        save_previous_identity(&mut vcs).unwrap();
+        buffer.insert("user.name".into(), "Pair Programmer".into());
+        buffer.insert(
+            "mit.author.expires".into(),
+            format!("{}", OffsetDateTime::now_utc().unix_timestamp() + 600),
+        );
+        let mut vcs = InMemory::new(&mut buffer);
+
+        restore_previous_identity_if_expired(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer.get("user.name"),
+            Some(&"Pair Programmer".to_string()),
+            "Expected the session's identity to be kept"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use miette::Result;
+
+use crate::{
+    external::{Vcs, parse_initial_and_part},
+    mit::{
+        cmd::{
+            CONFIG_KEY_GROUP_PREFIX,
+            errors::Error::{AuthorAlreadyExists, AuthorInOtherScope},
+        },
+        set_config_group,
+    },
+};
+
+/// Remove an author saved with `set_config_authors`
+///
+/// Only the level of the config that changes are saved to is changed.
+/// Returns whether any of their configuration was found
+///
+/// # Errors
+///
+/// If the author is also saved in another level of the config, or on read
+/// or write failure
+pub fn remove_config_authors(store: &mut dyn Vcs, initial: &str) -> Result<bool> {
+    let keys = target_author_keys(store, initial)?;
+
+    for (key, _) in &keys {
+        store.remove(key)?;
+    }
+
+    Ok(!keys.is_empty())
+}
+
+/// Move an author saved with `set_config_authors` to new initials
+///
+/// Only the level of the config that changes are saved to is read and
+/// changed, including which groups that include the author are updated to
+/// use the new initials. Returns whether any of their configuration was found
+///
+/// # Errors
+///
+/// If there's already an author with the new initials, the author is also
+/// saved in another level of the config, or on read or write failure
+pub fn rename_config_authors(store: &mut dyn Vcs, from: &str, to: &str) -> Result<bool> {
+    if !author_keys(&store.entries(Some("mit.author.config.*"))?, to).is_empty() {
+        return Err(AuthorAlreadyExists {
+            initial: to.to_string(),
+        }
+        .into());
+    }
+
+    let keys = target_author_keys(store, from)?;
+
+    for (key, part) in &keys {
+        if let Some(value) = store.get_target_str(key)? {
+            store.set_str(&format!("mit.author.config.{to}.{part}"), &value)?;
+        }
+        store.remove(key)?;
+    }
+
+    for key in store.target_entries(Some("mit.author.group.*"))? {
+        let (Some(name), Some(members)) = (
+            key.strip_prefix(CONFIG_KEY_GROUP_PREFIX),
+            store.get_target_str(&key)?,
+        ) else {
+            continue;
+        };
+
+        if members.split_whitespace().any(|member| member == from) {
+            let renamed: Vec<&str> = members
+                .split_whitespace()
+                .map(|member| if member == from { to } else { member })
+                .collect();
+            set_config_group(store, name, &renamed)?;
+        }
+    }
+
+    Ok(!keys.is_empty())
+}
+
+/// The author's keys in the level of the config we change, checking there
+/// are none in other levels before anything is written
+fn target_author_keys(store: &dyn Vcs, initial: &str) -> Result<Vec<(String, String)>> {
+    let everywhere = author_keys(&store.entries(Some("mit.author.config.*"))?, initial);
+    let target = author_keys(&store.target_entries(Some("mit.author.config.*"))?, initial);
+
+    // Keys set in more than one level are listed once for each
+    if everywhere.len() > target.len() {
+        return Err(AuthorInOtherScope {
+            initial: initial.to_string(),
+        }
+        .into());
+    }
+
+    Ok(target)
+}
+
+fn author_keys(entries: &[String], initial: &str) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .filter_map(|key| {
+            parse_initial_and_part(key)
+                .ok()
+                .filter(|(found, _)| found == initial)
+                .map(|(_, part)| (key.clone(), part))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use git2::{Config, ConfigLevel};
+
+    use super::{remove_config_authors, rename_config_authors};
+    use crate::{
+        external::{Git2, InMemory, Vcs},
+        mit::cmd::errors::Error,
+    };
+
+    fn roster() -> BTreeMap<String, String> {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.author.config.bt.name".into(), "Billie Thompson".into());
+        buffer.insert(
+            "mit.author.config.bt.email".into(),
+            "billie@example.com".into(),
+        );
+        buffer.insert("mit.author.config.bt.signingkey".into(), "0A46826A".into());
+        buffer.insert("mit.author.config.b.t.name".into(), "Bee Tee".into());
+        buffer.insert(
+            "mit.author.config.b.t.email".into(),
+            "bee@example.com".into(),
+        );
+        buffer.insert("mit.author.group.pair".into(), "bt b.t".into());
+        buffer
+    }
+
+    #[test]
+    fn removing_an_author_removes_all_their_config() {
+        let mut buffer = roster();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            remove_config_authors(&mut vcs, "bt").unwrap(),
+            "Expected the author to be found"
+        );
+        assert_eq!(
+            buffer.keys().cloned().collect::<Vec<_>>(),
+            vec![
+                "mit.author.config.b.t.email".to_string(),
+                "mit.author.config.b.t.name".to_string(),
+                "mit.author.group.pair".to_string(),
+            ],
+            "Expected only the author's keys to be removed"
+        );
+    }
+
+    #[test]
+    fn removing_an_author_with_dotted_initials() {
+        let mut buffer = roster();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            remove_config_authors(&mut vcs, "b.t").unwrap(),
+            "Expected the dotted author to be found"
+        );
+        assert!(
+            buffer.contains_key("mit.author.config.bt.name")
+                && !buffer.contains_key("mit.author.config.b.t.name"),
+            "Expected only the dotted author to be removed"
+        );
+    }
+
+    #[test]
+    fn removing_an_unknown_author_reports_it_was_not_found() {
+        let mut buffer = roster();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            !remove_config_authors(&mut vcs, "zz").unwrap(),
+            "Expected nothing to be found"
+        );
+    }
+
+    #[test]
+    fn renaming_an_author_moves_their_config_and_groups() {
+        let mut buffer = roster();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            rename_config_authors(&mut vcs, "bt", "billie").unwrap(),
+            "Expected the author to be found"
+        );
+        assert_eq!(
+            buffer.get("mit.author.config.billie.signingkey"),
+            Some(&"0A46826A".to_string()),
+            "Expected the signing key to move to the new initials"
+        );
+        assert!(
+            !buffer.contains_key("mit.author.config.bt.name"),
+            "Expected the old initials to be removed"
+        );
+        assert_eq!(
+            buffer.get("mit.author.group.pair"),
+            Some(&"billie b.t".to_string()),
+            "Expected the group to use the new initials"
+        );
+    }
+
+    #[test]
+    fn renaming_onto_an_existing_author_fails() {
+        let mut buffer = roster();
+        let mut vcs = InMemory::new(&mut buffer);
+
+        assert!(
+            rename_config_authors(&mut vcs, "bt", "b.t").is_err(),
+            "Expected an error rather than overwriting an author"
+        );
+        assert_eq!(buffer, roster(), "Expected nothing to change");
+    }
+
+    #[test]
+    fn authors_in_another_level_are_not_copied_into_this_one() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut config = Config::new().unwrap();
+        config
+            .add_file(&directory.path().join("global"), ConfigLevel::Global, false)
+            .unwrap();
+        config
+            .add_file(&directory.path().join("local"), ConfigLevel::Local, false)
+            .unwrap();
+        let mut global = config.open_level(ConfigLevel::Global).unwrap();
+        global
+            .set_str("mit.author.config.bt.name", "Billie Thompson")
+            .unwrap();
+        global.set_str("mit.author.group.pair", "bt se").unwrap();
+        let target = config.open_level(ConfigLevel::Local).unwrap();
+        let mut vcs = Git2::new(config, None).unwrap().writing_to(target);
+
+        assert!(
+            rename_config_authors(&mut vcs, "bt", "billie").is_err(),
+            "Expected an author from another level to be an error"
+        );
+        assert!(
+            remove_config_authors(&mut vcs, "bt").is_err(),
+            "Expected removing an author from another level to be an error"
+        );
+        assert_eq!(
+            vcs.target_entries(None).unwrap(),
+            Vec::<String>::new(),
+            "Expected nothing to be written to this level"
+        );
+    }
+
+    #[test]
+    fn authors_only_in_the_system_config_are_not_removed_from_the_global_one() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut config = Config::new().unwrap();
+        config
+            .add_file(&directory.path().join("system"), ConfigLevel::System, false)
+            .unwrap();
+        config
+            .add_file(&directory.path().join("global"), ConfigLevel::Global, false)
+            .unwrap();
+        config
+            .open_level(ConfigLevel::System)
+            .unwrap()
+            .set_str("mit.author.config.bt.name", "Billie Thompson")
+            .unwrap();
+        let mut vcs = Git2::global(config).unwrap();
+
+        assert!(
+            matches!(
+                remove_config_authors(&mut vcs, "bt")
+                    .unwrap_err()
+                    .downcast_ref::<Error>(),
+                Some(Error::AuthorInOtherScope { .. })
+            ),
+            "Expected an author only in the system config to be in another scope"
+        );
+        assert_eq!(
+            vcs.get_str("mit.author.config.bt.name").unwrap(),
+            Some("Billie Thompson"),
+            "Expected the author to still be configured"
+        );
+    }
+}
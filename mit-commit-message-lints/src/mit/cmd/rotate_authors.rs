@@ -3,7 +3,10 @@ use rand::seq::SliceRandom;
 
 use crate::external::Vcs;
 use crate::mit::cmd::set_commit_authors::{remove_coauthors, set_vcs_coauthor, set_vcs_user};
-use crate::mit::{Author, cmd::vcs::get_vcs_coauthors_config};
+use crate::mit::{
+    Author,
+    cmd::{CONFIG_KEY_SIGNING_FORMAT, vcs::get_vcs_coauthors},
+};
 
 /// Rotate the primary author among configured authors
 ///
@@ -30,33 +33,23 @@ pub fn rotate_authors(config: &mut dyn Vcs, strategy: crate::mit::RotationOption
     let primary_name = config.get_str("user.name")?.map(String::from);
     let primary_email = config.get_str("user.email")?.map(String::from);
     let primary_signingkey = config.get_str("user.signingkey")?.map(String::from);
+    let primary_signingformat = config
+        .get_str(CONFIG_KEY_SIGNING_FORMAT)?
+        .and_then(|format| format.parse().ok());
 
     let primary = match (primary_name, primary_email, primary_signingkey) {
-        (Some(name), Some(email), signingkey) => Some(Author::new(
-            name.into(),
-            email.into(),
-            signingkey.map(Into::into),
-        )),
+        (Some(name), Some(email), signingkey) => Some(
+            Author::new(name.into(), email.into(), signingkey.map(Into::into))
+                .with_signingformat(primary_signingformat),
+        ),
         _ => return Ok(()), // No primary author, nothing to rotate
     };
 
-    // Read coauthors
-    let coauthor_emails: Vec<String> = get_vcs_coauthors_config(config, "email")?
+    // Read coauthors, with their signing keys and identities, so whoever
+    // becomes primary signs with their own key
+    let coauthors: Vec<Author<'static>> = get_vcs_coauthors(config)?
         .into_iter()
-        .filter_map(|x| x.map(|s| s.to_string()))
-        .collect();
-
-    let coauthors: Vec<Author> = get_vcs_coauthors_config(config, "name")?
-        .into_iter()
-        .filter_map(|x| x.map(|s| s.to_string()))
-        .zip(coauthor_emails)
-        .filter_map(|(name, email)| {
-            if name.is_empty() || email.is_empty() {
-                None
-            } else {
-                Some(Author::new(name.into(), email.into(), None))
-            }
-        })
+        .map(Author::into_owned)
         .collect();
 
     // Build full author list
@@ -98,7 +91,8 @@ mod tests {
     use miette::Result;
 
     use crate::external::InMemory;
-    use crate::mit::{Author, set_commit_authors};
+    use crate::mit::{Author, SigningFormat, set_commit_authors};
+    use crate::timeout::Timeout;
 
     #[test]
     fn rotate_authors_rotates_three_authors() -> Result<()> {
@@ -113,7 +107,7 @@ mod tests {
             set_commit_authors(
                 &mut vcs_config,
                 &[&author_1, &author_2, &author_3],
-                Duration::from_hours(1),
+                &Timeout::In(Duration::from_hours(1)),
             )?;
         }
 
@@ -176,7 +170,11 @@ mod tests {
         {
             let mut vcs_config = InMemory::new(&mut buffer);
             let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-            set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1))?;
+            set_commit_authors(
+                &mut vcs_config,
+                &[&author],
+                &Timeout::In(Duration::from_hours(1)),
+            )?;
         }
 
         {
@@ -239,7 +237,7 @@ mod tests {
             set_commit_authors(
                 &mut vcs_config,
                 &[&author_1, &author_2],
-                Duration::from_hours(1),
+                &Timeout::In(Duration::from_hours(1)),
             )?;
         }
 
@@ -317,7 +315,7 @@ mod tests {
             set_commit_authors(
                 &mut vcs_config,
                 &[&author_1, &author_2, &author_3],
-                Duration::from_hours(1),
+                &Timeout::In(Duration::from_hours(1)),
             )?;
         }
 
@@ -363,7 +361,11 @@ mod tests {
         {
             let mut vcs_config = InMemory::new(&mut buffer);
             let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-            set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1))?;
+            set_commit_authors(
+                &mut vcs_config,
+                &[&author],
+                &Timeout::In(Duration::from_hours(1)),
+            )?;
         }
 
         {
@@ -418,4 +420,117 @@ mod tests {
 
         Ok(())
     }
+
+    fn signing_authors() -> [Author<'static>; 3] {
+        [
+            Author::new(
+                "Billie Thompson".into(),
+                "billie@example.com".into(),
+                Some("0A46826A".into()),
+            ),
+            Author::new(
+                "Somebody Else".into(),
+                "someone@example.com".into(),
+                Some("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIB someone@example.com".into()),
+            ),
+            Author::new(
+                "Annie Example".into(),
+                "annie@example.com".into(),
+                Some("annie.pem".into()),
+            )
+            .with_signingformat(Some(SigningFormat::X509)),
+        ]
+    }
+
+    fn primary_signing(buffer: &BTreeMap<String, String>) -> (String, String, String) {
+        let get = |key: &str| buffer.get(key).cloned().unwrap_or_default();
+
+        (get("user.email"), get("user.signingkey"), get("gpg.format"))
+    }
+
+    fn expected_signing(email: &str) -> (String, String, String) {
+        signing_authors()
+            .iter()
+            .find(|author| author.email() == email)
+            .map(|author| {
+                (
+                    author.email().to_string(),
+                    author.signingkey().unwrap_or_default().to_string(),
+                    author
+                        .signingformat()
+                        .map(|format| format.to_string())
+                        .unwrap_or_default(),
+                )
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn round_robin_rotation_keeps_each_authors_signing_key() -> Result<()> {
+        let mut buffer = BTreeMap::new();
+        let authors = signing_authors();
+        {
+            let mut vcs_config = InMemory::new(&mut buffer);
+            set_commit_authors(
+                &mut vcs_config,
+                &authors.iter().collect::<Vec<_>>(),
+                &Timeout::In(Duration::from_hours(1)),
+            )?;
+        }
+
+        for expected in [
+            "someone@example.com",
+            "annie@example.com",
+            "billie@example.com",
+        ] {
+            {
+                let mut vcs_config = InMemory::new(&mut buffer);
+                crate::mit::cmd::rotate_authors::rotate_authors(
+                    &mut vcs_config,
+                    crate::mit::RotationOption::RoundRobin,
+                )?;
+            }
+
+            assert_eq!(
+                primary_signing(&buffer),
+                expected_signing(expected),
+                "Expected {expected} to sign with their own key and format after rotation"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn random_rotation_keeps_each_authors_signing_key() -> Result<()> {
+        let mut buffer = BTreeMap::new();
+        let authors = signing_authors();
+        {
+            let mut vcs_config = InMemory::new(&mut buffer);
+            set_commit_authors(
+                &mut vcs_config,
+                &authors.iter().collect::<Vec<_>>(),
+                &Timeout::In(Duration::from_hours(1)),
+            )?;
+        }
+
+        for _ in 0..20 {
+            {
+                let mut vcs_config = InMemory::new(&mut buffer);
+                crate::mit::cmd::rotate_authors::rotate_authors(
+                    &mut vcs_config,
+                    crate::mit::RotationOption::Random,
+                )?;
+            }
+
+            let (email, _, _) = primary_signing(&buffer);
+            assert_eq!(
+                primary_signing(&buffer),
+                expected_signing(&email),
+                "Expected {email} to sign with their own key and format after rotation"
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,180 @@
+use miette::{Result, WrapErr};
+
+use crate::{
+    external::Vcs,
+    mit::{cmd::previous_identity::reapply_previous_identity, get_commit_coauthor_configuration},
+};
+
+const SESSION_AUTHOR_KEYS: [(&str, &str); 4] = [
+    ("user.name", "mit.author.user.name"),
+    ("user.email", "mit.author.user.email"),
+    ("user.signingkey", "mit.author.user.signingkey"),
+    ("gpg.format", "mit.author.user.signingformat"),
+];
+
+/// Keep a copy of the `user.*` identity and `gpg.format` with the session
+///
+/// Sessions can be kept for a branch, so this is what's put back when you
+/// switch to it.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn save_session_author(config: &mut dyn Vcs) -> Result<()> {
+    copy(config, |(user_key, session_key)| (user_key, session_key))
+        .wrap_err("failed to save the git author with the session")
+}
+
+/// Put back the `user.*` identity and `gpg.format` of the session for the
+/// branch that's checked out
+///
+/// If there's no session in progress, or it didn't keep a copy of its
+/// author, the identity from before pairing is put back instead, so the
+/// author of another branch's session isn't used.
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn restore_session_author(config: &mut dyn Vcs) -> Result<()> {
+    if !get_commit_coauthor_configuration(config)?.is_some()
+        || config.get_str("mit.author.user.email")?.is_none()
+    {
+        return reapply_previous_identity(config);
+    }
+
+    copy(config, |(user_key, session_key)| (session_key, user_key))
+        .wrap_err("failed to restore the git author of the session")
+}
+
+/// Remove the copy of the author kept with the session
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn forget_session_author(config: &mut dyn Vcs) -> Result<()> {
+    SESSION_AUTHOR_KEYS
+        .iter()
+        .try_for_each(|(_, session_key)| {
+            if config.get_str(session_key)?.is_some() {
+                config.remove(session_key)
+            } else {
+                Ok(())
+            }
+        })
+        .wrap_err("failed to remove the git author kept with the session")
+}
+
+fn copy(
+    config: &mut dyn Vcs,
+    direction: impl Fn((&'static str, &'static str)) -> (&'static str, &'static str),
+) -> Result<()> {
+    SESSION_AUTHOR_KEYS.iter().try_for_each(|keys| {
+        let (from, to) = direction(*keys);
+        match config.get_str(from)?.map(String::from) {
+            Some(value) => config.set_str(to, &value),
+            None if config.get_str(to)?.is_some() => config.remove(to),
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use time::OffsetDateTime;
+
+    use crate::{
+        external::InMemory,
+        mit::cmd::session_author::{restore_session_author, save_session_author},
+    };
+
+    #[test]
+    fn the_author_of_an_active_session_is_restored() {
+        let mut buffer = BTreeMap::from([
+            ("user.name".to_string(), "Billie Thompson".to_string()),
+            ("user.email".to_string(), "billie@example.com".to_string()),
+            ("user.signingkey".to_string(), "0A46826A".to_string()),
+        ]);
+        save_session_author(&mut InMemory::new(&mut buffer)).unwrap();
+
+        buffer.insert("user.name".into(), "Someone Else".into());
+        buffer.insert("user.email".into(), "someone@example.com".into());
+        buffer.remove("user.signingkey");
+        buffer.insert(
+            "mit.author.expires".into(),
+            (OffsetDateTime::now_utc().unix_timestamp() + 60).to_string(),
+        );
+        restore_session_author(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert_eq!(
+            (
+                buffer.get("user.name").map(String::as_str),
+                buffer.get("user.email").map(String::as_str),
+                buffer.get("user.signingkey").map(String::as_str),
+            ),
+            (
+                Some("Billie Thompson"),
+                Some("billie@example.com"),
+                Some("0A46826A")
+            ),
+            "Expected the session's author to be put back"
+        );
+    }
+
+    #[test]
+    fn expired_sessions_are_not_restored() {
+        let mut buffer = BTreeMap::from([
+            ("mit.author.user.name".to_string(), "Billie".to_string()),
+            (
+                "mit.author.user.email".to_string(),
+                "billie@example.com".to_string(),
+            ),
+            ("user.name".to_string(), "Someone Else".to_string()),
+            (
+                "mit.author.expires".to_string(),
+                (OffsetDateTime::now_utc().unix_timestamp() - 60).to_string(),
+            ),
+        ]);
+
+        restore_session_author(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert_eq!(
+            buffer.get("user.name").map(String::as_str),
+            Some("Someone Else"),
+            "Expected the identity to be left alone once the session has expired"
+        );
+    }
+
+    #[test]
+    fn sessions_without_a_copy_of_their_author_use_the_one_from_before() {
+        let mut buffer = BTreeMap::from([
+            ("user.name".to_string(), "Someone Else".to_string()),
+            ("user.email".to_string(), "someone@example.com".to_string()),
+            ("user.signingkey".to_string(), "0A46826A".to_string()),
+            ("mit.author.previous.saved".to_string(), "true".to_string()),
+            ("mit.author.previous.name".to_string(), "Solo".to_string()),
+            (
+                "mit.author.previous.email".to_string(),
+                "solo@example.com".to_string(),
+            ),
+            (
+                "mit.author.expires".to_string(),
+                (OffsetDateTime::now_utc().unix_timestamp() + 60).to_string(),
+            ),
+        ]);
+
+        restore_session_author(&mut InMemory::new(&mut buffer)).unwrap();
+
+        assert_eq!(
+            (
+                buffer.get("user.name").map(String::as_str),
+                buffer.get("user.email").map(String::as_str),
+                buffer.get("user.signingkey").map(String::as_str),
+                buffer.get("mit.author.previous.saved").map(String::as_str),
+            ),
+            (Some("Solo"), Some("solo@example.com"), None, Some("true")),
+            "Expected the identity from before pairing to be used, and kept saved"
+        );
+    }
+}
@@ -1,19 +1,32 @@
 use std::{
+    collections::BTreeSet,
     convert::TryInto,
-    ops::Add,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use miette::{IntoDiagnostic, Result, WrapErr};
+use miette::{IntoDiagnostic, Result, WrapErr, miette};
 
 use crate::{
     external::Vcs,
     mit::{
         Author,
-        cmd::{CONFIG_KEY_EXPIRES, errors::Error::NoAuthorsToSet, vcs::has_vcs_coauthor},
+        cmd::{
+            CONFIG_KEY_EXPIRES, CONFIG_KEY_EXPIRES_TIMEOUT, CONFIG_KEY_SIGNING_FORMAT,
+            errors::Error::NoAuthorsToSet,
+            previous_identity::save_previous_identity,
+            session_author::save_session_author,
+            vcs::{author_for_repository, has_vcs_coauthor, set_vcs_coauthor_identities},
+        },
     },
+    timeout::{Timeout, get_config_workday},
 };
 
+/// The identity from before the session is saved under
+/// `mit.author.previous`, so it can be restored later.
+///
+/// Authors with identities for other repositories are set using the one
+/// that matches this repository.
+///
 /// # Errors
 ///
 /// If writing to the git mit file fails for some reason.
@@ -21,20 +34,22 @@ use crate::{
 pub fn set_commit_authors(
     config: &mut dyn Vcs,
     authors: &[&Author<'_>],
-    expires_in: Duration,
+    timeout: &Timeout,
 ) -> Result<()> {
     let (first_author, others) = authors.split_first().ok_or(NoAuthorsToSet)?;
+    let expiry_time = expiry_time(config, timeout)?;
 
+    save_previous_identity(config)?;
     remove_coauthors(config)?;
     set_vcs_user(config, first_author)?;
     set_vcs_coauthors(config, others)?;
-    set_vcs_expires_time(config, expires_in)?;
+    set_vcs_expires_time(config, expiry_time, timeout)?;
 
     Ok(())
 }
 
 pub fn remove_coauthors(config: &mut dyn Vcs) -> Result<()> {
-    get_defined_vcs_coauthor_keys(config)
+    get_defined_vcs_coauthor_keys(config)?
         .into_iter()
         .try_for_each(|key| config.remove(&key))?;
 
@@ -42,8 +57,13 @@ pub fn remove_coauthors(config: &mut dyn Vcs) -> Result<()> {
 }
 
 #[allow(clippy::maybe_infinite_iter)]
-fn get_defined_vcs_coauthor_keys(config: &dyn Vcs) -> Vec<String> {
-    (0..)
+fn get_defined_vcs_coauthor_keys(config: &dyn Vcs) -> Result<BTreeSet<String>> {
+    let other_keys = config
+        .entries(Some("mit.author.coauthors.*"))?
+        .into_iter()
+        .filter(|key| key.starts_with("mit.author.coauthors."));
+
+    Ok((0..)
         .take_while(|index| has_vcs_coauthor(config, *index))
         .flat_map(|index| {
             [
@@ -51,7 +71,8 @@ fn get_defined_vcs_coauthor_keys(config: &dyn Vcs) -> Vec<String> {
                 format!("mit.author.coauthors.{index}.email"),
             ]
         })
-        .collect()
+        .chain(other_keys)
+        .collect())
 }
 
 fn set_vcs_coauthors(config: &mut dyn Vcs, authors: &[&Author<'_>]) -> Result<()> {
@@ -62,8 +83,29 @@ fn set_vcs_coauthors(config: &mut dyn Vcs, authors: &[&Author<'_>]) -> Result<()
 }
 
 pub fn set_vcs_coauthor(config: &mut dyn Vcs, index: usize, author: &Author<'_>) -> Result<()> {
-    set_vcs_coauthor_name(config, index, author)?;
-    set_vcs_coauthor_email(config, index, author)?;
+    let identity = author_for_repository(config, author)?;
+    set_vcs_coauthor_name(config, index, &identity)?;
+    set_vcs_coauthor_email(config, index, &identity)?;
+    set_vcs_coauthor_signing_key(config, index, &identity)?;
+    set_vcs_coauthor_identities(config, index, author.identities())?;
+
+    Ok(())
+}
+
+fn set_vcs_coauthor_signing_key(
+    config: &mut dyn Vcs,
+    index: usize,
+    author: &Author<'_>,
+) -> Result<()> {
+    if let Some(key) = author.signingkey() {
+        config.set_str(&format!("mit.author.coauthors.{index}.signingkey"), key)?;
+    }
+    if let Some(format) = author.signingformat() {
+        config.set_str(
+            &format!("mit.author.coauthors.{index}.signingformat"),
+            &format.to_string(),
+        )?;
+    }
 
     Ok(())
 }
@@ -82,9 +124,11 @@ fn set_vcs_coauthor_email(config: &mut dyn Vcs, index: usize, author: &Author<'_
 }
 
 pub fn set_vcs_user(config: &mut dyn Vcs, author: &Author<'_>) -> Result<()> {
+    let author = &author_for_repository(config, author)?;
     config.set_str("user.name", author.name())?;
     config.set_str("user.email", author.email())?;
     set_author_signing_key(config, author)?;
+    save_session_author(config)?;
 
     Ok(())
 }
@@ -93,28 +137,53 @@ fn set_author_signing_key(config: &mut dyn Vcs, author: &Author<'_>) -> Result<(
     if let Some(key) = author.signingkey() {
         config
             .set_str("user.signingkey", key)
-            .wrap_err("failed to set git author's signing key ")
-    } else {
-        if config.get_str("user.signingkey")?.is_some() {
-            config
-                .remove("user.signingkey")
-                .wrap_err("failed to remove git author's signing key")?;
-        }
-        Ok(())
+            .wrap_err("failed to set git author's signing key ")?;
+    } else if config.get_str("user.signingkey")?.is_some() {
+        config
+            .remove("user.signingkey")
+            .wrap_err("failed to remove git author's signing key")?;
     }
+
+    set_author_signing_format(config, author)
 }
 
-fn set_vcs_expires_time(config: &mut dyn Vcs, expires_in: Duration) -> Result<()> {
+fn set_author_signing_format(config: &mut dyn Vcs, author: &Author<'_>) -> Result<()> {
+    match author.signingformat() {
+        Some(format) if author.signingkey().is_some() => config
+            .set_str(CONFIG_KEY_SIGNING_FORMAT, &format.to_string())
+            .wrap_err("failed to set git author's signing format"),
+        _ if config.get_str(CONFIG_KEY_SIGNING_FORMAT)?.is_some() => config
+            .remove(CONFIG_KEY_SIGNING_FORMAT)
+            .wrap_err("failed to remove git author's signing format"),
+        _ => Ok(()),
+    }
+}
+
+fn expiry_time(config: &dyn Vcs, timeout: &Timeout) -> Result<i64> {
+    let expires_in = timeout.duration(&get_config_workday(config)?);
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .into_diagnostic()?;
-    let expiry_time = now.add(expires_in).as_secs().try_into().into_diagnostic()?;
+
+    now.checked_add(expires_in)
+        .ok_or_else(|| miette!("Expiration time overflow"))?
+        .as_secs()
+        .try_into()
+        .into_diagnostic()
+}
+
+fn set_vcs_expires_time(config: &mut dyn Vcs, expiry_time: i64, timeout: &Timeout) -> Result<()> {
     config
         .set_i64(CONFIG_KEY_EXPIRES, expiry_time)
-        .wrap_err("failed to set author expiry time")
+        .wrap_err("failed to set author expiry time")?;
+    // Kept so a sliding expiry can be worked out again after each commit
+    config
+        .set_str(CONFIG_KEY_EXPIRES_TIMEOUT, &timeout.to_string())
+        .wrap_err("failed to set author expiry timeout")
 }
 
 #[cfg(test)]
+#[allow(clippy::uninlined_format_args)]
 mod tests {
     use std::{
         collections::BTreeMap,
@@ -128,7 +197,8 @@ mod tests {
 
     use crate::{
         external::{InMemory, RepoState, Vcs},
-        mit::{Author, set_commit_authors},
+        mit::{Author, Identity, set_commit_authors},
+        timeout::Timeout,
     };
 
     struct FailingVcs;
@@ -173,6 +243,14 @@ mod tests {
         fn state(&self) -> Option<RepoState> {
             None
         }
+
+        fn workdir(&self) -> Option<&std::path::Path> {
+            None
+        }
+
+        fn branch(&self) -> Option<&str> {
+            None
+        }
     }
 
     #[test]
@@ -182,7 +260,11 @@ mod tests {
         let mut vcs_config = InMemory::new(&mut buffer);
 
         let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-        let actual = set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &[&author],
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
         assert_eq!(
@@ -207,7 +289,11 @@ mod tests {
             "billie@example.com".into(),
             Some("0A46826A".into()),
         );
-        let actual = set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &[&author],
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
         assert_eq!(
@@ -225,7 +311,11 @@ mod tests {
         let mut vcs_config = InMemory::new(&mut buffer);
 
         let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-        let actual = set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &[&author],
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
         assert_eq!(
@@ -235,6 +325,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn authors_use_the_identity_matching_the_repository() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert(
+            "remote.origin.url".into(),
+            "git@github.com:company/project.git".into(),
+        );
+        let mut vcs_config = InMemory::new(&mut buffer);
+
+        let identity = Identity {
+            remote: Some("*:company/*".into()),
+            path: None,
+            name: None,
+            email: "billie@company.example.com".into(),
+            signingkey: None,
+            signingformat: None,
+        };
+        let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None)
+            .with_identities(vec![identity]);
+        let coauthor = Author::new("Somebody Else".into(), "somebody@example.com".into(), None)
+            .with_identities(vec![Identity {
+                remote: None,
+                path: Some("/oss/**".into()),
+                name: None,
+                email: "somebody@home.example.com".into(),
+                signingkey: None,
+                signingformat: None,
+            }]);
+
+        set_commit_authors(
+            &mut vcs_config,
+            &[&author, &coauthor],
+            &Timeout::In(Duration::from_hours(1)),
+        )
+        .unwrap();
+
+        assert_eq!(
+            (
+                buffer.get("user.email").map(String::as_str),
+                buffer
+                    .get("mit.author.coauthors.0.email")
+                    .map(String::as_str),
+                buffer
+                    .get("mit.author.coauthors.0.identity.0.path")
+                    .map(String::as_str)
+            ),
+            (
+                Some("billie@company.example.com"),
+                Some("somebody@example.com"),
+                Some("/oss/**")
+            ),
+            "Expected the matching identity to be used, and the co-author's identities kept"
+        );
+    }
+
     #[test]
     fn multiple_authors_become_coauthors() {
         let mut buffer = BTreeMap::new();
@@ -245,7 +390,11 @@ mod tests {
         let author_3 = Author::new("Annie Example".into(), "annie@example.com".into(), None);
         let inputs = vec![&author_1, &author_2, &author_3];
 
-        let actual = set_commit_authors(&mut vcs_config, &inputs, Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &inputs,
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
         assert_eq!(
@@ -307,7 +456,11 @@ mod tests {
         let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
         let inputs = vec![&author];
 
-        let actual = set_commit_authors(&mut vcs_config, &inputs, Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &inputs,
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
         assert_eq!(
@@ -332,13 +485,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn expiry_times_too_far_away_are_an_error() {
+        let mut buffer = BTreeMap::new();
+        let mut vcs_config = InMemory::new(&mut buffer);
+
+        let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
+        let actual = set_commit_authors(&mut vcs_config, &[&author], &Timeout::In(Duration::MAX));
+
+        assert!(
+            actual.is_err(),
+            "Expected an expiry that can't be stored to be an error"
+        );
+        assert!(buffer.is_empty(), "Expected nothing to be changed");
+    }
+
     #[test]
     fn sets_the_expiry_time() {
         let mut buffer = BTreeMap::new();
         let mut vcs_config = InMemory::new(&mut buffer);
 
         let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-        let actual = set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &[&author],
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
 
@@ -375,6 +547,11 @@ mod tests {
             sec59min,
             actual_expire_time
         );
+        assert_eq!(
+            buffer.get("mit.author.expires-timeout"),
+            Some(&"3600s".to_string()),
+            "Expected the timeout to be kept for sliding expiry"
+        );
     }
 
     #[test]
@@ -382,7 +559,11 @@ mod tests {
         let mut vcs_config = FailingVcs;
 
         let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-        let actual = set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &[&author],
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         assert!(
             actual.is_err(),
@@ -424,6 +605,14 @@ mod tests {
         fn state(&self) -> Option<RepoState> {
             None
         }
+
+        fn workdir(&self) -> Option<&std::path::Path> {
+            None
+        }
+
+        fn branch(&self) -> Option<&str> {
+            None
+        }
     }
 
     #[test]
@@ -431,7 +620,11 @@ mod tests {
         let mut vcs_config = ExpiryFailingVcs;
 
         let author = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
-        let actual = set_commit_authors(&mut vcs_config, &[&author], Duration::from_hours(1));
+        let actual = set_commit_authors(
+            &mut vcs_config,
+            &[&author],
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         let err = actual.expect_err("expected set_commit_authors to fail with ExpiryFailingVcs");
         let err_msg = format!("{err:#?}");
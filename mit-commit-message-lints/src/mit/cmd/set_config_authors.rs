@@ -11,19 +11,20 @@ pub fn set_config_authors(store: &mut dyn Vcs, initial: &str, author: &Author<'_
     )?;
     store.set_str(&format!("mit.author.config.{initial}.name"), author.name())?;
 
-    if let Some(signingkey) = author.signingkey() {
-        store.set_str(
-            &format!("mit.author.config.{initial}.signingkey"),
-            signingkey,
-        )?;
-    } else {
-        let key = format!("mit.author.config.{initial}.signingkey");
-        if store.get_str(&key)?.is_some() {
-            store.remove(&key)?;
+    let signingformat = author.signingformat().map(|format| format.to_string());
+    [
+        ("signingkey", author.signingkey()),
+        ("signingformat", signingformat.as_deref()),
+    ]
+    .into_iter()
+    .try_for_each(|(part, value)| {
+        let key = format!("mit.author.config.{initial}.{part}");
+        match value {
+            Some(value) => store.set_str(&key, value),
+            None if store.get_str(&key)?.is_some() => store.remove(&key),
+            None => Ok(()),
         }
-    }
-
-    Ok(())
+    })
 }
 
 #[cfg(test)]
@@ -85,6 +86,14 @@ mod tests {
         fn state(&self) -> Option<RepoState> {
             None
         }
+
+        fn workdir(&self) -> Option<&std::path::Path> {
+            None
+        }
+
+        fn branch(&self) -> Option<&str> {
+            None
+        }
     }
 
     #[test]
@@ -0,0 +1,52 @@
+use miette::Result;
+
+use crate::{external::Vcs, mit::lib::expiry_mode::ExpiryMode};
+
+/// Set whether committing extends the authors' session
+///
+/// # Errors
+///
+/// Returns an error if writing to the git config fails.
+pub fn set_config_expiry_mode(store: &mut dyn Vcs, mode: ExpiryMode) -> Result<()> {
+    store.set_str(super::CONFIG_KEY_EXPIRY_MODE, &mode.to_string())
+}
+
+/// Set whether committing extends the relates-to too
+///
+/// # Errors
+///
+/// Returns an error if writing to the git config fails.
+pub fn set_config_relates_to_expiry_mode(store: &mut dyn Vcs, mode: ExpiryMode) -> Result<()> {
+    store.set_str(super::CONFIG_KEY_RELATE_EXPIRY_MODE, &mode.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use miette::Result;
+
+    use crate::{
+        external::InMemory,
+        mit::{ExpiryMode, get_config_expiry_mode, set_config_expiry_mode},
+    };
+
+    #[test]
+    fn set_config_expiry_mode_writes_sliding_and_reads_back() -> Result<()> {
+        let mut buffer = BTreeMap::new();
+        set_config_expiry_mode(&mut InMemory::new(&mut buffer), ExpiryMode::Sliding)?;
+
+        assert_eq!(
+            buffer.get("mit.author.expiry-mode"),
+            Some(&"sliding".to_string()),
+            "Expected the expiry mode to be set to 'sliding'"
+        );
+        assert_eq!(
+            get_config_expiry_mode(&InMemory::new(&mut buffer))?,
+            ExpiryMode::Sliding,
+            "Expected to read back the sliding expiry mode after writing it"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use miette::Result;
+
+use crate::{external::Vcs, mit::cmd::CONFIG_KEY_GROUP_PREFIX};
+
+/// Save a named group of initials
+///
+/// # Errors
+///
+/// On write failure
+pub fn set_config_group(store: &mut dyn Vcs, name: &str, initials: &[&str]) -> Result<()> {
+    store.set_str(
+        &format!("{CONFIG_KEY_GROUP_PREFIX}{name}"),
+        &initials.join(" "),
+    )
+}
+
+/// Read the named groups of initials
+///
+/// # Errors
+///
+/// On read failure
+pub fn get_config_groups(store: &dyn Vcs) -> Result<BTreeMap<String, Vec<String>>> {
+    store
+        .entries(Some("mit.author.group.*"))?
+        .into_iter()
+        .filter_map(|key| {
+            key.strip_prefix(CONFIG_KEY_GROUP_PREFIX)
+                .map(|name| (name.to_string(), key.clone()))
+        })
+        .map(|(name, key)| {
+            Ok((
+                name,
+                store
+                    .get_str(&key)?
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{get_config_groups, set_config_group};
+    use crate::external::InMemory;
+
+    #[test]
+    fn groups_are_stored_as_space_separated_initials() {
+        let mut strings = BTreeMap::new();
+        let mut vcs = InMemory::new(&mut strings);
+
+        set_config_group(&mut vcs, "platform", &["ae", "se", "bt"]).unwrap();
+
+        assert_eq!(
+            strings.get("mit.author.group.platform"),
+            Some(&"ae se bt".to_string()),
+            "Expected the group to be saved under its name"
+        );
+    }
+
+    #[test]
+    fn groups_can_be_read_back() {
+        let mut strings = BTreeMap::new();
+        strings.insert("mit.author.group.platform".into(), "ae se bt".into());
+        strings.insert("mit.author.config.bt.name".into(), "Billie".into());
+        let vcs = InMemory::new(&mut strings);
+
+        let mut expected = BTreeMap::new();
+        expected.insert(
+            "platform".to_string(),
+            vec!["ae".to_string(), "se".to_string(), "bt".to_string()],
+        );
+        assert_eq!(
+            get_config_groups(&vcs).unwrap(),
+            expected,
+            "Expected only the groups, split into initials"
+        );
+    }
+}
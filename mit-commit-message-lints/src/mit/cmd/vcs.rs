@@ -1,20 +1,40 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeMap};
 
 use miette::Result;
 
-use crate::external::Vcs;
+use crate::{
+    external::Vcs,
+    mit::{Author, Identity},
+};
 
-pub fn get_vcs_coauthors_config<'a>(
-    config: &'a dyn Vcs,
-    key: &'a str,
-) -> Result<Vec<Option<Cow<'a, str>>>> {
+/// Everything the session has saved about each co-author, in order
+#[allow(clippy::maybe_infinite_iter)]
+pub fn get_vcs_coauthors(config: &dyn Vcs) -> Result<Vec<Author<'_>>> {
     (0..)
         .take_while(|index| has_vcs_coauthor(config, *index))
-        .map(|index| get_vcs_coauthor_config(config, key, index))
-        .collect::<Result<Vec<Option<_>>>>()
+        .map(|index| get_vcs_coauthor(config, index))
+        .collect::<Result<Vec<_>>>()
+        .map(|coauthors| coauthors.into_iter().flatten().collect())
+}
+
+fn get_vcs_coauthor(config: &dyn Vcs, index: usize) -> Result<Option<Author<'_>>> {
+    let name = get_vcs_coauthor_config(config, "name", index)?;
+    let email = get_vcs_coauthor_config(config, "email", index)?;
+    let signingkey = get_vcs_coauthor_config(config, "signingkey", index)?;
+    let signingformat = get_vcs_coauthor_config(config, "signingformat", index)?
+        .and_then(|format| format.parse().ok());
+
+    match (name, email) {
+        (Some(name), Some(email)) if !name.is_empty() && !email.is_empty() => Ok(Some(
+            Author::new(name, email, signingkey)
+                .with_signingformat(signingformat)
+                .with_identities(get_vcs_coauthor_identities(config, index)?),
+        )),
+        _ => Ok(None),
+    }
 }
 
-pub fn has_vcs_coauthor(config: &dyn Vcs, index: i32) -> bool {
+pub fn has_vcs_coauthor(config: &dyn Vcs, index: usize) -> bool {
     let email = get_vcs_coauthor_config(config, "email", index);
     let name = get_vcs_coauthor_config(config, "name", index);
 
@@ -23,10 +43,103 @@ pub fn has_vcs_coauthor(config: &dyn Vcs, index: i32) -> bool {
 
 pub fn get_vcs_coauthor_config<'a>(
     config: &'a dyn Vcs,
-    key: &'a str,
-    index: i32,
+    key: &str,
+    index: usize,
 ) -> Result<Option<Cow<'a, str>>> {
     config
         .get_str(&format!("mit.author.coauthors.{index}.{key}"))
         .map(|x| x.map(std::convert::Into::into))
 }
+
+/// The identities a co-author uses in other repositories, so the right one
+/// can be picked for whichever repository the commit is in
+fn get_vcs_coauthor_identities(config: &dyn Vcs, index: usize) -> Result<Vec<Identity<'static>>> {
+    let prefix = format!("mit.author.coauthors.{index}.identity.");
+    let mut fields: BTreeMap<usize, BTreeMap<String, String>> = BTreeMap::new();
+
+    for key in config.entries(Some(&format!("{prefix}*")))? {
+        let Some((position, field)) = key
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.split_once('.'))
+        else {
+            continue;
+        };
+        let Ok(position) = position.parse() else {
+            continue;
+        };
+        if let Some(value) = config.get_str(&key)? {
+            fields
+                .entry(position)
+                .or_default()
+                .insert(field.to_string(), value.to_string());
+        }
+    }
+
+    Ok(fields
+        .into_values()
+        .filter_map(|mut identity| {
+            Some(Identity {
+                remote: identity.remove("remote").map(Into::into),
+                path: identity.remove("path").map(Into::into),
+                name: identity.remove("name").map(Into::into),
+                email: identity.remove("email")?.into(),
+                signingkey: identity.remove("signingkey").map(Into::into),
+                signingformat: identity
+                    .remove("signingformat")
+                    .and_then(|format| format.parse().ok()),
+            })
+        })
+        .collect())
+}
+
+pub fn set_vcs_coauthor_identities(
+    config: &mut dyn Vcs,
+    index: usize,
+    identities: &[Identity<'_>],
+) -> Result<()> {
+    identities
+        .iter()
+        .enumerate()
+        .try_for_each(|(position, identity)| {
+            let signingformat = identity.signingformat.map(|format| format.to_string());
+            [
+                ("remote", identity.remote.as_deref()),
+                ("path", identity.path.as_deref()),
+                ("name", identity.name.as_deref()),
+                ("email", Some(identity.email.as_ref())),
+                ("signingkey", identity.signingkey.as_deref()),
+                ("signingformat", signingformat.as_deref()),
+            ]
+            .into_iter()
+            .filter_map(|(field, value)| value.map(|value| (field, value)))
+            .try_for_each(|(field, value)| {
+                config.set_str(
+                    &format!("mit.author.coauthors.{index}.identity.{position}.{field}"),
+                    value,
+                )
+            })
+        })
+}
+
+/// The author as they should appear in the repository this config is from
+pub fn author_for_repository<'a>(config: &dyn Vcs, author: &Author<'a>) -> Result<Author<'a>> {
+    if author.identities().is_empty() {
+        return Ok(author.clone());
+    }
+
+    let remotes = config
+        .entries(Some("remote.*.url"))?
+        .into_iter()
+        .filter(|key| {
+            key.strip_prefix("remote.")
+                .and_then(|rest| rest.rsplit_once('.'))
+                .is_some_and(|(_, field)| field == "url")
+        })
+        .map(|key| config.get_str(&key).map(|url| url.map(String::from)))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    Ok(author.for_repository(config.workdir(), &remotes))
+}
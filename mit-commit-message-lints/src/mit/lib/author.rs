@@ -1,7 +1,11 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, convert::TryFrom, path::Path};
 
 use serde::{Deserialize, Serialize};
 
+use crate::mit::lib::{
+    errors::ParseAuthorError, identity::Identity, signing_format::SigningFormat,
+};
+
 /// An author that might be developing
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Author<'a> {
@@ -9,6 +13,10 @@ pub struct Author<'a> {
     email: Cow<'a, str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     signingkey: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signingformat: Option<SigningFormat>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    identities: Vec<Identity<'a>>,
 }
 
 impl<'a> Author<'a> {
@@ -23,9 +31,26 @@ impl<'a> Author<'a> {
             name,
             email,
             signingkey,
+            signingformat: None,
+            identities: Vec::new(),
+        }
+    }
+
+    /// Say what kind of key the signing key is
+    #[must_use]
+    pub fn with_signingformat(self, signingformat: Option<SigningFormat>) -> Self {
+        Self {
+            signingformat,
+            ..self
         }
     }
 
+    /// Add the identities the author uses in particular repositories
+    #[must_use]
+    pub fn with_identities(self, identities: Vec<Identity<'a>>) -> Self {
+        Self { identities, ..self }
+    }
+
     /// The author name
     #[must_use]
     pub fn name(&self) -> &str {
@@ -43,6 +68,122 @@ impl<'a> Author<'a> {
     pub fn signingkey(&self) -> Option<&str> {
         self.signingkey.as_deref()
     }
+
+    /// The kind of key the signing key is
+    ///
+    /// If the author doesn't say, SSH keys are recognised from the key, and
+    /// otherwise it's left to git's configuration
+    #[must_use]
+    pub fn signingformat(&self) -> Option<SigningFormat> {
+        self.signingformat
+            .or_else(|| self.signingkey().and_then(SigningFormat::detect))
+    }
+
+    /// The identities the author uses in particular repositories
+    #[must_use]
+    pub fn identities(&self) -> &[Identity<'a>] {
+        &self.identities
+    }
+
+    /// The author as they should appear in a repository
+    ///
+    /// The first identity that matches the repository's path or remotes is
+    /// used, otherwise the author is used as they are
+    #[must_use]
+    pub fn for_repository(&self, path: Option<&Path>, remotes: &[String]) -> Self {
+        let Some(identity) = self
+            .identities
+            .iter()
+            .find(|identity| identity.matches(path, remotes))
+        else {
+            return self.clone();
+        };
+
+        Self {
+            name: identity.name.clone().unwrap_or_else(|| self.name.clone()),
+            email: identity.email.clone(),
+            signingkey: identity
+                .signingkey
+                .clone()
+                .or_else(|| self.signingkey.clone()),
+            signingformat: if identity.signingkey.is_some() {
+                identity.signingformat
+            } else {
+                self.signingformat
+            },
+            identities: self.identities.clone(),
+        }
+    }
+
+    /// Copy anything borrowed, so the author can outlive where they were
+    /// read from
+    #[must_use]
+    pub fn into_owned(self) -> Author<'static> {
+        Author {
+            name: Cow::Owned(self.name.into_owned()),
+            email: Cow::Owned(self.email.into_owned()),
+            signingkey: self.signingkey.map(|key| Cow::Owned(key.into_owned())),
+            signingformat: self.signingformat,
+            identities: self
+                .identities
+                .into_iter()
+                .map(Identity::into_owned)
+                .collect(),
+        }
+    }
+
+    /// Whether this is the author's email, or the email of one of their
+    /// identities
+    ///
+    /// Emails are compared ignoring case
+    #[must_use]
+    pub fn has_email(&self, email: &str) -> bool {
+        self.email.eq_ignore_ascii_case(email)
+            || self
+                .identities
+                .iter()
+                .any(|identity| identity.email.eq_ignore_ascii_case(email))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Author<'a> {
+    type Error = ParseAuthorError;
+
+    /// Parse an author written like `Jane Doe <jane@example.com>`, the same
+    /// way they're written in a `Co-authored-by` trailer
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let error = |offset: usize, length: usize, reason: &str| ParseAuthorError {
+            src: input.into(),
+            span: (offset, length).into(),
+            reason: reason.into(),
+        };
+
+        let Some(open) = input.find('<') else {
+            return Err(error(0, input.len(), "missing an email in angle brackets"));
+        };
+        let Some(close) = input.rfind('>').filter(|close| *close > open) else {
+            return Err(error(open, input.len() - open, "missing a closing `>`"));
+        };
+        if !input[close + 1..].trim().is_empty() {
+            return Err(error(
+                close + 1,
+                input.len() - close - 1,
+                "unexpected text after the email",
+            ));
+        }
+
+        let name = input[..open].trim();
+        let email = input[open + 1..close].trim();
+
+        if name.is_empty() {
+            return Err(error(0, open, "missing a name"));
+        }
+        if !email.contains('@') {
+            return Err(error(open, close - open + 1, "not an email address"));
+        }
+
+        Ok(Self::new(name.into(), email.into(), None))
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +227,84 @@ mod tests {
             "Expected the author's signing key to be '0A46826A'"
         );
     }
+
+    #[test]
+    fn the_matching_identity_is_used_in_a_repository() {
+        let author = Author::new(
+            "Billie Thompson".into(),
+            "billie@example.com".into(),
+            Some("0A46826A".into()),
+        )
+        .with_identities(vec![Identity {
+            remote: Some("*github.com:company/*".into()),
+            path: None,
+            name: None,
+            email: "billie.thompson@company.example.com".into(),
+            signingkey: None,
+            signingformat: None,
+        }]);
+        let company = vec!["git@github.com:company/project.git".to_string()];
+
+        let resolved = author.for_repository(None, &company);
+        assert_eq!(
+            (resolved.name(), resolved.email(), resolved.signingkey()),
+            (
+                "Billie Thompson",
+                "billie.thompson@company.example.com",
+                Some("0A46826A")
+            ),
+            "Expected the identity's email, keeping the rest of the author"
+        );
+        assert_eq!(
+            author.for_repository(None, &[]).email(),
+            "billie@example.com",
+            "Expected the default email when no identity matches"
+        );
+        assert!(
+            author.has_email("Billie.Thompson@company.example.com"),
+            "Expected identity emails to belong to the author"
+        );
+    }
+
+    #[test]
+    fn authors_can_be_parsed_from_a_trailer_value() {
+        let author = Author::try_from("Jane Doe <jane@example.com>").unwrap();
+
+        assert_eq!(
+            author,
+            Author::new("Jane Doe".into(), "jane@example.com".into(), None),
+            "Expected the name and email to be parsed"
+        );
+    }
+
+    #[test]
+    fn authors_without_an_email_are_not_parsed() {
+        let error = Author::try_from("Jane Doe").unwrap_err();
+
+        assert_eq!(
+            error.reason, "missing an email in angle brackets",
+            "Expected the reason to explain the email is missing"
+        );
+    }
+
+    #[test]
+    fn authors_without_a_name_are_not_parsed() {
+        let error = Author::try_from("<jane@example.com>").unwrap_err();
+
+        assert_eq!(
+            error.reason, "missing a name",
+            "Expected the reason to explain the name is missing"
+        );
+    }
+
+    #[test]
+    fn authors_with_an_invalid_email_are_not_parsed() {
+        let error = Author::try_from("Jane Doe <jane>").unwrap_err();
+
+        assert_eq!(
+            (error.reason.as_str(), error.span.offset(), error.span.len()),
+            ("not an email address", 9, 6),
+            "Expected the email to be labelled"
+        );
+    }
 }
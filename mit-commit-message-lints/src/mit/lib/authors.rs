@@ -1,8 +1,10 @@
 use std::{
-    collections::{BTreeMap, HashSet, btree_map::IntoIter},
+    collections::{BTreeMap, BTreeSet, HashSet, btree_map::IntoIter},
     convert::TryFrom,
 };
 
+use serde::{Deserialize, Deserializer, Serialize, de::Error};
+
 use crate::mit::lib::{
     author::Author,
     errors::{DeserializeAuthorsError, SerializeAuthorsError},
@@ -13,41 +15,330 @@ use crate::mit::lib::{
 pub struct Authors<'a> {
     /// A btree of the authors
     pub authors: BTreeMap<String, Author<'a>>,
+    groups: BTreeMap<String, Vec<String>>,
+    exact_initials: bool,
+    sources: BTreeMap<String, String>,
+    conflicts: BTreeMap<String, Vec<String>>,
+}
+
+/// The layout of an authors file
+#[derive(Serialize, Deserialize)]
+struct AuthorsFile<'a> {
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        deserialize_with = "deserialize_groups"
+    )]
+    groups: BTreeMap<String, Vec<String>>,
+    #[serde(flatten)]
+    authors: BTreeMap<String, Author<'a>>,
+}
+
+/// Read the groups, saying so if they look like an author from before
+/// `groups` was reserved
+fn deserialize_groups<'de, D>(deserializer: D) -> Result<BTreeMap<String, Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum GroupsKey {
+        Groups(BTreeMap<String, Vec<String>>),
+        Author(Author<'static>),
+    }
+
+    match GroupsKey::deserialize(deserializer)? {
+        GroupsKey::Groups(groups) => Ok(groups),
+        GroupsKey::Author(author) => Err(D::Error::custom(format!(
+            "`groups` is used for groups of initials, so {} can't have the initials `groups`, give them different initials",
+            author.name()
+        ))),
+    }
+}
+
+/// Whether an authors file is YAML, checked the same way as when it's read
+pub fn is_yaml(input: &str) -> bool {
+    serde_yaml::from_str::<AuthorsFile<'_>>(input).is_ok()
+}
+
+impl<'a> From<AuthorsFile<'a>> for Authors<'a> {
+    fn from(file: AuthorsFile<'a>) -> Self {
+        Self::new(file.authors).with_groups(file.groups)
+    }
 }
 
 impl<'a> Authors<'a> {
     /// From a list of initials get the ones that aren't in our config
+    ///
+    /// Groups are expanded, so any members of a group that aren't configured
+    /// are also missing
     #[must_use]
-    pub fn missing_initials(&'a self, authors_initials: Vec<&'a str>) -> Vec<&'a str> {
+    pub fn missing_initials(&'a self, authors_initials: &[&'a str]) -> Vec<&'a str> {
         let configured: HashSet<_> = self.authors.keys().map(String::as_str).collect();
-        let from_cli: HashSet<_> = authors_initials.into_iter().collect();
+        let from_cli: HashSet<_> = self.expand(authors_initials).into_iter().collect();
         from_cli.difference(&configured).copied().collect()
     }
 
     /// Create a new author collection
     #[must_use]
     pub const fn new(authors: BTreeMap<String, Author<'a>>) -> Self {
-        Self { authors }
+        Self {
+            authors,
+            groups: BTreeMap::new(),
+            exact_initials: false,
+            sources: BTreeMap::new(),
+            conflicts: BTreeMap::new(),
+        }
+    }
+
+    /// Named groups of initials, so a whole team can be selected at once
+    #[must_use]
+    pub const fn groups(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.groups
     }
 
-    /// Get some authors by their initials
+    /// Only accept initials that are exactly as configured, rather than
+    /// ignoring case and accepting a unique prefix
     #[must_use]
-    pub fn get(&self, author_initials: &'a [&'a str]) -> Vec<&'a Author<'_>> {
+    pub const fn exact_initials(&self) -> bool {
+        self.exact_initials
+    }
+
+    /// Where each author was defined, by initial
+    #[must_use]
+    pub const fn sources(&self) -> &BTreeMap<String, String> {
+        &self.sources
+    }
+
+    /// The sources that define the same initials differently, by initial
+    #[must_use]
+    pub const fn conflicts(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.conflicts
+    }
+
+    /// Add named groups of initials to the collection
+    #[must_use]
+    pub fn with_groups(self, groups: BTreeMap<String, Vec<String>>) -> Self {
+        Self { groups, ..self }
+    }
+
+    /// Record where all the authors in this collection were defined
+    #[must_use]
+    pub fn with_source(self, source: &str) -> Self {
+        let sources = self
+            .authors
+            .keys()
+            .map(|initial| (initial.clone(), source.to_string()))
+            .collect();
+        Self { sources, ..self }
+    }
+
+    /// Only accept initials that are exactly as configured
+    #[must_use]
+    pub fn with_exact_initials(self, exact_initials: bool) -> Self {
+        Self {
+            exact_initials,
+            ..self
+        }
+    }
+
+    /// Get some authors by their initials, or the name of a group
+    #[must_use]
+    pub fn get(&'a self, author_initials: &[&'a str]) -> Vec<&'a Author<'a>> {
+        self.expand(author_initials)
+            .into_iter()
+            .filter_map(|initial| self.authors.get(initial))
+            .collect()
+    }
+
+    /// Find the initials of the author with an email address, including the
+    /// emails of their identities
+    ///
+    /// Emails are compared ignoring case
+    #[must_use]
+    pub fn initial_for_email(&self, email: &str) -> Option<&str> {
+        self.authors
+            .iter()
+            .find(|(_, author)| author.has_email(email))
+            .map(|(initial, _)| initial.as_str())
+    }
+
+    /// List the authors with some of them first, in the order given
+    ///
+    /// Authors are matched by email, and the rest follow ordered by their
+    /// initials
+    #[must_use]
+    pub fn with_first(&self, first: &[&Author<'_>]) -> Vec<(&str, &Author<'a>)> {
+        let leading: Vec<&str> = first
+            .iter()
+            .filter_map(|author| self.initial_for_email(author.email()))
+            .fold(vec![], |mut leading, initial| {
+                if !leading.contains(&initial) {
+                    leading.push(initial);
+                }
+                leading
+            });
+
+        leading
+            .iter()
+            .filter_map(|initial| self.authors.get_key_value(*initial))
+            .chain(
+                self.authors
+                    .iter()
+                    .filter(|(initial, _)| !leading.contains(&initial.as_str())),
+            )
+            .map(|(initial, author)| (initial.as_str(), author))
+            .collect()
+    }
+
+    /// Replace group names with the initials of their members
+    ///
+    /// An author with the same initials as a group takes precedence, and each
+    /// initial only appears once, in the order it was first seen
+    #[must_use]
+    pub fn expand(&'a self, author_initials: &[&'a str]) -> Vec<&'a str> {
         author_initials
             .iter()
-            .filter_map(|initial| self.authors.get(*initial))
+            .map(|initial| self.resolve(initial))
+            .flat_map(|initial| match self.groups.get(initial) {
+                Some(members) if !self.authors.contains_key(initial) => {
+                    members.iter().map(String::as_str).collect()
+                }
+                _ => vec![initial],
+            })
+            .fold(vec![], |mut expanded, initial| {
+                if !expanded.contains(&initial) {
+                    expanded.push(initial);
+                }
+                expanded
+            })
+    }
+
+    /// Find the configured initials or group name that was meant
+    ///
+    /// Unless exact initials are required, case is ignored and a prefix that
+    /// only matches one configured name is accepted. If nothing matches, or
+    /// the match is ambiguous, the initial is returned as it was given
+    fn resolve(&'a self, initial: &'a str) -> &'a str {
+        if self.exact_initials
+            || initial.is_empty()
+            || self.authors.contains_key(initial)
+            || self.groups.contains_key(initial)
+        {
+            return initial;
+        }
+
+        let lowercase = initial.to_lowercase();
+        let names: BTreeSet<&str> = self
+            .authors
+            .keys()
+            .chain(self.groups.keys())
+            .map(String::as_str)
+            .collect();
+        let same: Vec<&str> = names
+            .iter()
+            .copied()
+            .filter(|name| name.to_lowercase() == lowercase)
+            .collect();
+        let candidates = if same.is_empty() {
+            names
+                .iter()
+                .copied()
+                .filter(|name| name.to_lowercase().starts_with(&lowercase))
+                .collect()
+        } else {
+            same
+        };
+
+        match candidates.as_slice() {
+            [only] => only,
+            _ => initial,
+        }
+    }
+
+    /// Find the configured authors closest to some initials that weren't
+    /// found
+    ///
+    /// Authors are compared by the edit distance to both their initials and
+    /// the initials of their name, closest first
+    #[must_use]
+    pub fn suggestions(&self, initial: &str) -> Vec<(&str, &Author<'a>)> {
+        let lowercase = initial.to_lowercase();
+        let threshold = (lowercase.chars().count() / 2).max(1);
+        let mut scored: Vec<(usize, &str, &Author<'a>)> = self
+            .authors
+            .iter()
+            .map(|(key, author)| {
+                let from_name: String = author
+                    .name()
+                    .split_whitespace()
+                    .filter_map(|word| word.chars().next())
+                    .collect::<String>()
+                    .to_lowercase();
+                let distance = strsim::levenshtein(&lowercase, &key.to_lowercase())
+                    .min(strsim::levenshtein(&lowercase, &from_name));
+                (distance, key.as_str(), author)
+            })
+            .filter(|(distance, _, _)| *distance <= threshold)
+            .collect();
+        scored.sort_by_key(|(distance, key, _)| (*distance, *key));
+
+        scored
+            .into_iter()
+            .take(3)
+            .map(|(_, key, author)| (key, author))
             .collect()
     }
 
     /// Merge two lists of authors
     ///
     /// This is used if the user has an author config file, and the authors are
-    /// also saved in the vcs config
+    /// also saved in the vcs config. Authors in `authors` take precedence, and
+    /// when both sides know where an author came from, defining the same
+    /// initials differently is recorded as a conflict
     #[must_use]
     pub fn merge(&self, authors: &Self) -> Self {
+        let mut conflicts = self.conflicts.clone();
+        let mut sources = self.sources.clone();
+
+        for (initial, author) in &authors.authors {
+            let previous_source = sources.remove(initial);
+            let differs = self
+                .authors
+                .get(initial)
+                .is_some_and(|existing| existing != author);
+
+            if let (true, Some(previous), Some(next)) =
+                (differs, previous_source, authors.sources.get(initial))
+            {
+                let conflict = conflicts
+                    .entry(initial.clone())
+                    .or_insert_with(|| vec![previous.clone()]);
+                if !conflict.contains(&previous) {
+                    conflict.push(previous);
+                }
+                if !conflict.contains(next) {
+                    conflict.push(next.clone());
+                }
+            }
+        }
+
         let mut merged = self.authors.clone();
         merged.extend(authors.authors.clone());
-        Self { authors: merged }
+        let mut groups = self.groups.clone();
+        groups.extend(authors.groups.clone());
+        sources.extend(authors.sources.clone());
+        for (initial, sources) in &authors.conflicts {
+            conflicts.insert(initial.clone(), sources.clone());
+        }
+
+        Self {
+            authors: merged,
+            groups,
+            exact_initials: self.exact_initials || authors.exact_initials,
+            sources,
+            conflicts,
+        }
     }
 
     /// Generate an example authors list
@@ -89,13 +380,13 @@ impl<'a> TryFrom<&'a str> for Authors<'a> {
     type Error = DeserializeAuthorsError;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        serde_yaml::from_str(input)
+        serde_yaml::from_str::<AuthorsFile<'_>>(input)
             .or_else(|yaml_error| {
                 toml::from_str(input).map_err(|toml_error| {
                     DeserializeAuthorsError::new(input, &yaml_error, &toml_error)
                 })
             })
-            .map(Self::new)
+            .map(Self::from)
     }
 }
 
@@ -103,13 +394,13 @@ impl TryFrom<String> for Authors<'_> {
     type Error = DeserializeAuthorsError;
 
     fn try_from(input: String) -> Result<Self, Self::Error> {
-        serde_yaml::from_str(&input)
+        serde_yaml::from_str::<AuthorsFile<'_>>(&input)
             .or_else(|yaml_error| {
                 toml::from_str(&input).map_err(|toml_error| {
                     DeserializeAuthorsError::new(&input, &yaml_error, &toml_error)
                 })
             })
-            .map(Authors::new)
+            .map(Authors::from)
     }
 }
 
@@ -117,7 +408,11 @@ impl<'a> TryFrom<Authors<'a>> for String {
     type Error = SerializeAuthorsError;
 
     fn try_from(value: Authors<'a>) -> Result<Self, Self::Error> {
-        toml::to_string(&value.authors).map_err(SerializeAuthorsError)
+        toml::to_string(&AuthorsFile {
+            groups: value.groups,
+            authors: value.authors,
+        })
+        .map_err(SerializeAuthorsError)
     }
 }
 
@@ -285,6 +580,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merging_keeps_track_of_where_authors_came_from() {
+        let mut company = BTreeMap::new();
+        company.insert(
+            "bt".into(),
+            Author::new("Billie Thompson".into(), "billie@example.com".into(), None),
+        );
+        company.insert(
+            "se".into(),
+            Author::new("Someone Else".into(), "someone@example.com".into(), None),
+        );
+        let mut team = BTreeMap::new();
+        team.insert(
+            "bt".into(),
+            Author::new("Billie Thompson".into(), "bt@example.com".into(), None),
+        );
+        team.insert(
+            "se".into(),
+            Author::new("Someone Else".into(), "someone@example.com".into(), None),
+        );
+
+        let merged = Authors::new(company)
+            .with_source("company.toml")
+            .merge(&Authors::new(team).with_source("team.toml"));
+
+        assert_eq!(
+            merged.sources.get("bt").map(String::as_str),
+            Some("team.toml"),
+            "Expected the later source to win"
+        );
+        assert_eq!(
+            merged.conflicts.get("bt"),
+            Some(&vec!["company.toml".to_string(), "team.toml".to_string()]),
+            "Expected the different definitions to be a conflict"
+        );
+        assert!(
+            !merged.conflicts.contains_key("se"),
+            "Expected identical definitions not to be a conflict"
+        );
+    }
+
+    #[test]
+    fn authors_without_a_source_replace_the_source() {
+        let mut store = BTreeMap::new();
+        store.insert(
+            "bt".into(),
+            Author::new("Billie Thompson".into(), "bt@example.com".into(), None),
+        );
+
+        let merged = team().with_source("team.toml").merge(&Authors::new(store));
+
+        assert_eq!(
+            merged.sources.get("bt"),
+            None,
+            "Expected the source of the replaced author to be forgotten"
+        );
+        assert!(
+            merged.conflicts.is_empty(),
+            "Expected no conflict when a source isn't known"
+        );
+    }
+
     #[test]
     fn it_can_tell_me_if_initials_are_not_in() {
         let mut store = BTreeMap::new();
@@ -295,7 +652,7 @@ mod tests {
         let actual = Authors::new(store);
 
         assert_eq!(
-            actual.missing_initials(vec!["bt", "an"]),
+            actual.missing_initials(&["bt", "an"]),
             vec!["an"],
             "Expected only 'an' to be missing since 'bt' is configured"
         );
@@ -542,4 +899,308 @@ mod tests {
             "Expected an error for malformed config key"
         );
     }
+
+    fn team() -> Authors<'static> {
+        let mut store = BTreeMap::new();
+        store.insert(
+            "bt".into(),
+            Author::new("Billie Thompson".into(), "billie@example.com".into(), None),
+        );
+        store.insert(
+            "se".into(),
+            Author::new("Someone Else".into(), "someone@example.com".into(), None),
+        );
+        store.insert(
+            "ae".into(),
+            Author::new("Anyone Else".into(), "anyone@example.com".into(), None),
+        );
+        let mut groups = BTreeMap::new();
+        groups.insert(
+            "platform".into(),
+            vec!["se".to_string(), "ae".to_string(), "bt".to_string()],
+        );
+        Authors::new(store).with_groups(groups)
+    }
+
+    #[test]
+    fn groups_are_expanded_when_getting_authors() {
+        let authors = team();
+
+        assert_eq!(
+            authors
+                .get(&["bt", "platform"])
+                .into_iter()
+                .map(Author::name)
+                .collect::<Vec<_>>(),
+            vec!["Billie Thompson", "Someone Else", "Anyone Else"],
+            "Expected the group members in order, without repeating bt"
+        );
+    }
+
+    #[test]
+    fn unknown_group_members_are_missing() {
+        let mut authors = team();
+        authors
+            .groups
+            .insert("mob".into(), vec!["bt".to_string(), "zz".to_string()]);
+
+        assert_eq!(
+            authors.missing_initials(&["mob"]),
+            vec!["zz"],
+            "Expected the unconfigured member of the group to be missing"
+        );
+    }
+
+    #[test]
+    fn initials_can_be_given_in_any_case() {
+        let authors = team();
+
+        assert_eq!(
+            authors
+                .get(&["BT", "sE"])
+                .into_iter()
+                .map(Author::name)
+                .collect::<Vec<_>>(),
+            vec!["Billie Thompson", "Someone Else"],
+            "Expected initials to match ignoring case"
+        );
+        assert!(
+            authors.missing_initials(&["BT", "Platform"]).is_empty(),
+            "Expected no missing initials when only the case differs"
+        );
+    }
+
+    #[test]
+    fn a_unique_prefix_selects_the_author() {
+        let authors = team();
+
+        assert_eq!(
+            authors
+                .get(&["b", "plat"])
+                .into_iter()
+                .map(Author::name)
+                .collect::<Vec<_>>(),
+            vec!["Billie Thompson", "Someone Else", "Anyone Else"],
+            "Expected unique prefixes to select the author and the group"
+        );
+    }
+
+    #[test]
+    fn an_ambiguous_prefix_is_missing() {
+        let mut authors = team();
+        authors.authors.insert(
+            "bo".into(),
+            Author::new("Bo Ossman".into(), "bo@example.com".into(), None),
+        );
+
+        assert_eq!(
+            authors.missing_initials(&["b"]),
+            vec!["b"],
+            "Expected a prefix matching two authors to be missing"
+        );
+    }
+
+    #[test]
+    fn exact_initials_can_be_required() {
+        let authors = team().with_exact_initials(true);
+
+        assert_eq!(
+            authors.missing_initials(&["BT"]),
+            vec!["BT"],
+            "Expected initials in a different case to be missing"
+        );
+        assert!(
+            authors.get(&["b"]).is_empty(),
+            "Expected a prefix not to select anyone"
+        );
+    }
+
+    #[test]
+    fn exact_initials_survive_a_merge() {
+        let merged = team().merge(&Authors::default().with_exact_initials(true));
+
+        assert!(
+            merged.exact_initials,
+            "Expected exact initials to be required if either side requires them"
+        );
+    }
+
+    #[test]
+    fn it_suggests_the_closest_authors() {
+        let authors = team();
+
+        assert_eq!(
+            authors
+                .suggestions("bx")
+                .into_iter()
+                .map(|(initial, _)| initial)
+                .collect::<Vec<_>>(),
+            vec!["bt"],
+            "Expected the author one edit away to be suggested"
+        );
+        assert_eq!(
+            authors
+                .suggestions("st")
+                .into_iter()
+                .map(|(_, author)| author.name())
+                .collect::<Vec<_>>(),
+            vec!["Billie Thompson", "Someone Else"],
+            "Expected authors to be suggested closest first"
+        );
+        assert!(
+            authors.suggestions("zzz").is_empty(),
+            "Expected no suggestions when nothing is close"
+        );
+    }
+
+    #[test]
+    fn some_authors_can_be_listed_first() {
+        let authors = team();
+        let someone = Author::new("Someone Else".into(), "Someone@Example.com".into(), None);
+        let billie = Author::new("Billie Thompson".into(), "billie@example.com".into(), None);
+        let stranger = Author::new("Stranger".into(), "stranger@example.com".into(), None);
+
+        assert_eq!(
+            authors
+                .with_first(&[&someone, &stranger, &billie])
+                .into_iter()
+                .map(|(initial, _)| initial)
+                .collect::<Vec<_>>(),
+            vec!["se", "bt", "ae"],
+            "Expected the known authors in the order given, then the rest"
+        );
+    }
+
+    #[test]
+    fn initials_can_be_found_from_an_email() {
+        let authors = team();
+
+        assert_eq!(
+            authors.initial_for_email("Someone@Example.com"),
+            Some("se"),
+            "Expected the initials of the author with that email"
+        );
+        assert_eq!(
+            authors.initial_for_email("nobody@example.com"),
+            None,
+            "Expected no initials for an unknown email"
+        );
+    }
+
+    #[test]
+    fn authors_can_have_identities_for_other_repositories() {
+        let actual = Authors::try_from(indoc!(
+            "
+            [bt]
+            name = \"Billie Thompson\"
+            email = \"billie@example.com\"
+
+            [[bt.identities]]
+            remote = \"*github.com:company/*\"
+            email = \"billie@company.example.com\"
+            "
+        ))
+        .expect("Failed to parse toml");
+
+        assert_eq!(
+            actual.authors.get("bt").map(|author| author
+                .for_repository(None, &["git@github.com:company/app.git".into()])
+                .email()
+                .to_string()),
+            Some("billie@company.example.com".to_string()),
+            "Expected the identity to be read from the authors file"
+        );
+        assert_eq!(
+            actual.initial_for_email("billie@company.example.com"),
+            Some("bt"),
+            "Expected the author to be found by the email of an identity"
+        );
+    }
+
+    #[test]
+    fn toml_files_can_contain_groups() {
+        let actual = Authors::try_from(indoc!(
+            "
+            [groups]
+            pair = [\"bt\", \"se\"]
+
+            [bt]
+            name = \"Billie Thompson\"
+            email = \"billie@example.com\"
+            "
+        ))
+        .expect("Failed to parse toml");
+
+        assert_eq!(
+            actual.groups.get("pair"),
+            Some(&vec!["bt".to_string(), "se".to_string()]),
+            "Expected the group to be read from the groups table"
+        );
+        assert_eq!(
+            actual.authors.len(),
+            1,
+            "Expected the groups table not to be read as an author"
+        );
+    }
+
+    #[test]
+    fn yaml_files_can_contain_groups() {
+        let actual = Authors::try_from(indoc!(
+            "
+            ---
+            groups:
+              pair:
+                - bt
+                - se
+            bt:
+              name: Billie Thompson
+              email: billie@example.com
+            "
+        ))
+        .expect("Failed to parse yaml");
+
+        assert_eq!(
+            actual.groups.get("pair"),
+            Some(&vec!["bt".to_string(), "se".to_string()]),
+            "Expected the group to be read from the groups key"
+        );
+    }
+
+    #[test]
+    fn groups_are_included_in_the_toml() {
+        let actual: String = team().try_into().unwrap();
+
+        assert!(
+            actual.contains("[groups]\nplatform = [\"se\", \"ae\", \"bt\"]"),
+            "Expected the groups table in {actual}"
+        );
+    }
+
+    #[test]
+    fn an_author_with_the_initials_groups_is_reported() {
+        let yaml = Authors::try_from(indoc!(
+            "
+            ---
+            groups:
+              name: Grace Groups
+              email: grace@example.com
+            "
+        ));
+        let toml = Authors::try_from(indoc!(
+            "
+            [groups]
+            name = \"Grace Groups\"
+            email = \"grace@example.com\"
+            "
+        ));
+
+        assert!(
+            yaml.is_err_and(|error| error.yaml_message.contains("initials `groups`")),
+            "Expected the yaml author called groups to be reported"
+        );
+        assert!(
+            toml.is_err_and(|error| error.toml_message.contains("initials `groups`")),
+            "Expected the toml author called groups to be reported"
+        );
+    }
 }
@@ -0,0 +1,472 @@
+//! An authors file that can be changed without losing its formatting
+use std::fmt::{Display, Formatter};
+
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+use crate::mit::lib::{
+    author::Author,
+    authors::{Authors, is_yaml},
+    errors::DeserializeAuthorsError,
+};
+
+const FIELDS: [&str; 4] = ["name", "email", "signingkey", "signingformat"];
+
+/// An authors file, kept in the format it was written in
+///
+/// Comments, ordering and the layout of the authors that aren't changed are
+/// left as they were
+#[derive(Debug, Clone)]
+pub enum AuthorsDocument {
+    /// A TOML authors file
+    Toml(DocumentMut),
+    /// A YAML authors file, as its lines
+    Yaml(Vec<String>),
+}
+
+impl AuthorsDocument {
+    /// Read an authors file
+    ///
+    /// The format is detected the same way as when the authors are read,
+    /// YAML first, then TOML. An empty file uses YAML if `prefer_yaml` is
+    /// set, otherwise TOML.
+    ///
+    /// # Errors
+    ///
+    /// If the file isn't a valid authors file
+    pub fn parse(input: &str, prefer_yaml: bool) -> Result<Self, DeserializeAuthorsError> {
+        if input.trim().is_empty() {
+            return Ok(if prefer_yaml {
+                Self::Yaml(vec![])
+            } else {
+                Self::Toml(DocumentMut::new())
+            });
+        }
+
+        Authors::try_from(input)?;
+
+        if is_yaml(input) {
+            Ok(Self::Yaml(input.lines().map(String::from).collect()))
+        } else {
+            Ok(Self::Toml(input.parse().unwrap_or_default()))
+        }
+    }
+
+    /// Add an author, or update them if they're already in the file
+    pub fn set(&mut self, initial: &str, author: &Author<'_>) {
+        let signingformat = author.signingformat().map(|format| format.to_string());
+        let values = [author.name(), author.email()]
+            .into_iter()
+            .map(Some)
+            .chain([author.signingkey(), signingformat.as_deref()]);
+        let fields: Vec<(&str, Option<&str>)> = FIELDS.into_iter().zip(values).collect();
+
+        match self {
+            Self::Toml(document) => {
+                if let Some(table) = document.get_mut(initial).and_then(Item::as_table_like_mut) {
+                    for (field, value) in fields {
+                        set_toml_field(table, field, value);
+                    }
+                } else {
+                    let mut table = Table::new();
+                    for (field, value) in fields {
+                        set_toml_field(&mut table, field, value);
+                    }
+                    document.insert(initial, Item::Table(table));
+                }
+            }
+            Self::Yaml(lines) => set_yaml_author(lines, initial, &fields),
+        }
+    }
+
+    /// Remove an author, returning whether they were in the file
+    pub fn remove(&mut self, initial: &str) -> bool {
+        match self {
+            Self::Toml(document) => document.remove(initial).is_some(),
+            Self::Yaml(lines) => yaml_block(lines, initial).is_some_and(|(start, end)| {
+                lines.drain(start..end);
+                true
+            }),
+        }
+    }
+
+    /// The authors in the file as it is now
+    ///
+    /// YAML is edited line by line, so this is how to check an edit did
+    /// what was expected before the file is written.
+    ///
+    /// # Errors
+    ///
+    /// If the edited file is no longer a valid authors file
+    pub fn authors(&self) -> Result<Authors<'static>, DeserializeAuthorsError> {
+        let contents = self.to_string();
+        if contents
+            .lines()
+            .all(|line| is_yaml_filler(line) || line.trim() == "---")
+        {
+            return Ok(Authors::default());
+        }
+
+        Authors::try_from(contents)
+    }
+}
+
+impl Display for AuthorsDocument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Toml(document) => write!(f, "{document}"),
+            Self::Yaml(lines) => lines.iter().try_for_each(|line| writeln!(f, "{line}")),
+        }
+    }
+}
+
+fn set_toml_field(table: &mut dyn TableLike, field: &str, value: Option<&str>) {
+    match (table.get_mut(field), value) {
+        (Some(Item::Value(existing)), Some(value)) => {
+            let decor = existing.decor().clone();
+            *existing = Value::from(value);
+            *existing.decor_mut() = decor;
+        }
+        (_, Some(value)) => {
+            table.insert(field, toml_edit::value(value));
+        }
+        (_, None) => {
+            table.remove(field);
+        }
+    }
+}
+
+/// The key of a top level entry in a YAML mapping, and what follows it
+fn yaml_key(line: &str) -> Option<(String, &str)> {
+    if line.starts_with(char::is_whitespace) || line.starts_with(['#', '-', '.']) {
+        return None;
+    }
+
+    let (key, rest) = if let Some(quote @ ('"' | '\'')) = line.chars().next() {
+        let end = line[1..].find(quote)? + 1;
+        (line[1..end].to_string(), line[end + 1..].strip_prefix(':')?)
+    } else {
+        let colon = line
+            .match_indices(':')
+            .map(|(index, _)| index)
+            .find(|index| line[index + 1..].is_empty() || line[index + 1..].starts_with(' '))?;
+        (line[..colon].trim_end().to_string(), &line[colon + 1..])
+    };
+
+    Some((key, rest.trim()))
+}
+
+/// The range of lines an author takes up in a YAML file
+///
+/// Blank lines and comments after the author are left for whatever follows
+fn yaml_block(lines: &[String], initial: &str) -> Option<(usize, usize)> {
+    let start = lines
+        .iter()
+        .position(|line| yaml_key(line).is_some_and(|(key, _)| key == initial))?;
+    let mut end = lines[start + 1..]
+        .iter()
+        .position(|line| !line.trim().is_empty() && !line.starts_with(char::is_whitespace))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    while end > start + 1 && is_yaml_filler(&lines[end - 1]) {
+        end -= 1;
+    }
+
+    Some((start, end))
+}
+
+fn is_yaml_filler(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+fn yaml_scalar(value: &str) -> String {
+    serde_yaml::to_string(value).map_or_else(
+        |_| format!("{value:?}"),
+        |rendered| rendered.trim_end().to_string(),
+    )
+}
+
+fn yaml_entry(initial: &str, fields: &[(&str, Option<&str>)]) -> Vec<String> {
+    std::iter::once(format!("{}:", yaml_scalar(initial)))
+        .chain(fields.iter().filter_map(|(field, value)| {
+            value.map(|value| format!("  {field}: {}", yaml_scalar(value)))
+        }))
+        .collect()
+}
+
+fn set_yaml_author(lines: &mut Vec<String>, initial: &str, fields: &[(&str, Option<&str>)]) {
+    let Some((start, end)) = yaml_block(lines, initial) else {
+        if lines.is_empty() {
+            lines.push("---".into());
+        }
+        lines.extend(yaml_entry(initial, fields));
+        return;
+    };
+
+    // Authors written on one line, like `bt: { name: ... }`, are rewritten
+    if yaml_key(&lines[start]).is_some_and(|(_, rest)| !rest.is_empty() && !rest.starts_with('#')) {
+        lines.splice(start..end, yaml_entry(initial, fields));
+        return;
+    }
+
+    for (field, value) in fields {
+        set_yaml_field(lines, initial, field, *value);
+    }
+}
+
+fn set_yaml_field(lines: &mut Vec<String>, initial: &str, field: &str, value: Option<&str>) {
+    let Some((start, end)) = yaml_block(lines, initial) else {
+        return;
+    };
+    let indent: String = lines[start + 1..end]
+        .iter()
+        .find(|line| !is_yaml_filler(line))
+        .map_or_else(
+            || "  ".into(),
+            |line| line.chars().take_while(|c| c.is_whitespace()).collect(),
+        );
+    let existing = (start + 1..end).find(|index| {
+        lines[*index]
+            .strip_prefix(&indent)
+            .and_then(|rest| rest.strip_prefix(field))
+            .is_some_and(|rest| rest.starts_with(':'))
+    });
+
+    match (existing, value) {
+        (Some(index), Some(value)) => {
+            let old_value = lines[index][indent.len() + field.len() + 1..].trim_start();
+            let comment = if old_value.starts_with(['"', '\'']) {
+                ""
+            } else {
+                old_value.find(" #").map_or("", |at| &old_value[at..])
+            };
+            lines[index] = format!("{indent}{field}: {}{comment}", yaml_scalar(value));
+        }
+        (Some(index), None) => {
+            lines.remove(index);
+        }
+        (None, Some(value)) => {
+            let after = (start..end)
+                .rev()
+                .find(|index| !is_yaml_filler(&lines[*index]))
+                .unwrap_or(start);
+            lines.insert(
+                after + 1,
+                format!("{indent}{field}: {}", yaml_scalar(value)),
+            );
+        }
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::AuthorsDocument;
+    use crate::mit::{Author, Authors};
+
+    fn jane() -> Author<'static> {
+        Author::new("Jane Doe".into(), "jane@example.com".into(), None)
+    }
+
+    #[test]
+    fn toml_authors_are_updated_in_place() {
+        let mut document = AuthorsDocument::parse(
+            indoc!(
+                r#"
+                # Our team
+                [se]
+                name = "Someone Else" # pairs on Tuesdays
+                email = "someone@example.com"
+
+                [bt]
+                name = "Billie Thompson"
+                email = "billie@example.com"
+                signingkey = "0A46826A"
+                "#
+            ),
+            false,
+        )
+        .unwrap();
+
+        document.set(
+            "se",
+            &Author::new("Someone Else".into(), "se@example.com".into(), None),
+        );
+        document.set("jd", &jane());
+
+        assert_eq!(
+            document.to_string(),
+            indoc!(
+                r#"
+                # Our team
+                [se]
+                name = "Someone Else" # pairs on Tuesdays
+                email = "se@example.com"
+
+                [bt]
+                name = "Billie Thompson"
+                email = "billie@example.com"
+                signingkey = "0A46826A"
+
+                [jd]
+                name = "Jane Doe"
+                email = "jane@example.com"
+                "#
+            ),
+            "Expected comments and order to be kept, with the new author at the end"
+        );
+    }
+
+    #[test]
+    fn toml_authors_can_be_removed() {
+        let mut document = AuthorsDocument::parse(
+            indoc!(
+                r#"
+                [se]
+                name = "Someone Else"
+                email = "someone@example.com"
+
+                # Billie
+                [bt]
+                name = "Billie Thompson"
+                email = "billie@example.com"
+                "#
+            ),
+            false,
+        )
+        .unwrap();
+
+        assert!(document.remove("se"), "Expected the author to be found");
+        assert!(
+            !document.remove("zz"),
+            "Expected an unknown author not to be found"
+        );
+        assert_eq!(
+            document.to_string(),
+            "\n# Billie\n[bt]\nname = \"Billie Thompson\"\nemail = \"billie@example.com\"\n",
+            "Expected only the removed author to be gone"
+        );
+    }
+
+    #[test]
+    fn yaml_authors_are_updated_in_place() {
+        let mut document = AuthorsDocument::parse(
+            indoc!(
+                "
+                ---
+                # Our team
+                se:
+                    name: Someone Else # pairs on Tuesdays
+                    email: someone@example.com
+                    signingkey: ABC123
+
+                # Billie
+                bt:
+                    name: Billie Thompson
+                    email: billie@example.com
+                "
+            ),
+            false,
+        )
+        .unwrap();
+
+        document.set(
+            "se",
+            &Author::new("Someone Else".into(), "se@example.com".into(), None),
+        );
+        document.set(
+            "bt",
+            &Author::new(
+                "Billie Thompson".into(),
+                "billie@example.com".into(),
+                Some("0A46826A".into()),
+            ),
+        );
+        document.set("jd", &jane());
+
+        assert_eq!(
+            document.to_string(),
+            indoc!(
+                "
+                ---
+                # Our team
+                se:
+                    name: Someone Else # pairs on Tuesdays
+                    email: se@example.com
+
+                # Billie
+                bt:
+                    name: Billie Thompson
+                    email: billie@example.com
+                    signingkey: 0A46826A
+                jd:
+                  name: Jane Doe
+                  email: jane@example.com
+                "
+            ),
+            "Expected comments, indentation and order to be kept"
+        );
+    }
+
+    #[test]
+    fn yaml_authors_can_be_removed() {
+        let mut document = AuthorsDocument::parse(
+            indoc!(
+                "
+                ---
+                se:
+                  name: Someone Else
+                  email: someone@example.com
+
+                # Billie
+                \"b.t\": { name: Billie Thompson, email: billie@example.com }
+                "
+            ),
+            false,
+        )
+        .unwrap();
+
+        assert!(
+            document.remove("b.t"),
+            "Expected the quoted author to be found"
+        );
+        assert!(document.remove("se"), "Expected the author to be found");
+        assert_eq!(
+            document.to_string(),
+            indoc!(
+                "
+                ---
+
+                # Billie
+                "
+            ),
+            "Expected the authors to be removed, leaving the comments"
+        );
+    }
+
+    #[test]
+    fn the_written_file_can_still_be_read() {
+        for prefer_yaml in [true, false] {
+            let mut document = AuthorsDocument::parse("", prefer_yaml).unwrap();
+            document.set("b.t", &jane());
+
+            assert_eq!(
+                Authors::try_from(document.to_string())
+                    .unwrap()
+                    .get(&["b.t"]),
+                vec![&jane()],
+                "Expected the new author to be readable from an empty file"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_files_are_an_error() {
+        assert!(
+            AuthorsDocument::parse("Hello I am invalid yaml : : :", false).is_err(),
+            "Expected an error for a file that isn't an authors file"
+        );
+    }
+}
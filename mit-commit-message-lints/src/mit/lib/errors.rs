@@ -54,6 +54,43 @@ pub struct DeserializeRotationOptionError {
     pub(crate) src: String,
 }
 
+#[derive(Error, Debug, Diagnostic)]
+#[error("could not parse expiry mode configuration")]
+#[diagnostic(
+    code(mit_commit_message_lints::mit::lib::expiry_mode::DeserializeExpiryModeError),
+    help("valid values are: fixed, sliding")
+)]
+pub struct DeserializeExpiryModeError {
+    #[source_code]
+    pub(crate) src: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("could not parse signing format")]
+#[diagnostic(
+    code(mit_commit_message_lints::mit::lib::signing_format::DeserializeSigningFormatError),
+    help("valid values are: openpgp, ssh, x509")
+)]
+pub struct DeserializeSigningFormatError {
+    #[source_code]
+    pub(crate) src: String,
+}
+
+#[derive(Error, Debug, Diagnostic)]
+#[error("could not parse author")]
+#[diagnostic(
+    code(mit_commit_message_lints::mit::lib::author::parse_author_error),
+    help("authors that aren't configured can be written like \"Jane Doe <jane@example.com>\"")
+)]
+pub struct ParseAuthorError {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("{reason}")]
+    pub(crate) span: SourceSpan,
+
+    pub(crate) reason: String,
+}
+
 impl DeserializeAuthorsError {
     pub(crate) fn new(
         input: &str,
@@ -0,0 +1,72 @@
+//! How a session's expiry changes as commits are made
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use crate::mit::lib::errors::DeserializeExpiryModeError;
+
+/// Whether committing keeps a session going
+#[derive(
+    clap::ValueEnum, Serialize, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy, Default,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExpiryMode {
+    /// The session expires when it was set to, however much you commit
+    #[default]
+    Fixed,
+    /// Each commit pushes the expiry back by the original timeout
+    Sliding,
+}
+
+const FIXED_DISPLAY: &str = "fixed";
+const SLIDING_DISPLAY: &str = "sliding";
+
+impl FromStr for ExpiryMode {
+    type Err = DeserializeExpiryModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            FIXED_DISPLAY => Ok(Self::Fixed),
+            SLIDING_DISPLAY => Ok(Self::Sliding),
+            _ => Err(DeserializeExpiryModeError { src: s.into() }),
+        }
+    }
+}
+
+impl Display for ExpiryMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixed => write!(f, "{FIXED_DISPLAY}"),
+            Self::Sliding => write!(f, "{SLIDING_DISPLAY}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::ExpiryMode;
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        for original in [ExpiryMode::Fixed, ExpiryMode::Sliding] {
+            assert_eq!(
+                ExpiryMode::from_str(&original.to_string().to_uppercase()).unwrap(),
+                original,
+                "Expected display output to round-trip through from_str, ignoring case"
+            );
+        }
+    }
+
+    #[test]
+    fn from_str_rejects_unknown() {
+        assert!(
+            ExpiryMode::from_str("forever").is_err(),
+            "Expected parsing an unknown expiry mode to return an error"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::{borrow::Cow, path::Path};
+
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+
+use crate::mit::lib::signing_format::SigningFormat;
+
+/// Another name, email or signing key an author uses in some repositories
+///
+/// An identity is used when every rule it has matches the repository. One
+/// without any rules is never used.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+pub struct Identity<'a> {
+    /// A glob matched against the URLs of the repository's remotes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<Cow<'a, str>>,
+    /// A glob matched against the path of the repository, `~` being your
+    /// home directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<Cow<'a, str>>,
+    /// The name to use, if it's different to the author's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<Cow<'a, str>>,
+    /// The email to use
+    pub email: Cow<'a, str>,
+    /// The signing key to use, if it's different to the author's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signingkey: Option<Cow<'a, str>>,
+    /// The kind of key the identity's signing key is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signingformat: Option<SigningFormat>,
+}
+
+impl Identity<'_> {
+    /// Copy anything borrowed, so the identity can outlive where it was read
+    /// from
+    #[must_use]
+    pub fn into_owned(self) -> Identity<'static> {
+        let owned = |value: Option<Cow<'_, str>>| value.map(|value| Cow::Owned(value.into_owned()));
+
+        Identity {
+            remote: owned(self.remote),
+            path: owned(self.path),
+            name: owned(self.name),
+            email: Cow::Owned(self.email.into_owned()),
+            signingkey: owned(self.signingkey),
+            signingformat: self.signingformat,
+        }
+    }
+
+    /// Whether this identity should be used in a repository
+    #[must_use]
+    pub fn matches(&self, path: Option<&Path>, remotes: &[String]) -> bool {
+        let remote_matches = self.remote.as_deref().map(|remote| {
+            Pattern::new(remote).is_ok_and(|pattern| remotes.iter().any(|url| pattern.matches(url)))
+        });
+        let path_matches = self.path.as_deref().map(|glob| {
+            let glob = expand_home(glob);
+            Pattern::new(&glob).is_ok_and(|pattern| {
+                path.is_some_and(|path| {
+                    pattern.matches(path.to_string_lossy().trim_end_matches('/'))
+                })
+            })
+        });
+
+        match (remote_matches, path_matches) {
+            (None, None) => false,
+            (remote, path) => remote.unwrap_or(true) && path.unwrap_or(true),
+        }
+    }
+}
+
+fn expand_home(glob: &str) -> String {
+    match (glob.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{rest}", home.trim_end_matches('/')),
+        _ => glob.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::Identity;
+
+    fn identity(remote: Option<&'static str>, path: Option<&'static str>) -> Identity<'static> {
+        Identity {
+            remote: remote.map(Into::into),
+            path: path.map(Into::into),
+            name: None,
+            email: "billie@example.com".into(),
+            signingkey: None,
+            signingformat: None,
+        }
+    }
+
+    #[test]
+    fn identities_match_on_any_remote() {
+        let remotes = vec![
+            "git@github.com:me/fork.git".to_string(),
+            "git@github.com:company/project.git".to_string(),
+        ];
+
+        assert!(
+            identity(Some("*github.com:company/*"), None).matches(None, &remotes),
+            "Expected a matching remote to be enough"
+        );
+        assert!(
+            !identity(Some("*gitlab.com*"), None).matches(None, &remotes),
+            "Expected no match when no remote matches"
+        );
+    }
+
+    #[test]
+    fn identities_need_every_rule_to_match() {
+        let remotes = vec!["git@github.com:company/project.git".to_string()];
+        let path = Path::new("/work/project");
+
+        assert!(
+            identity(Some("*company*"), Some("/work/*")).matches(Some(path), &remotes),
+            "Expected a match when both rules match"
+        );
+        assert!(
+            !identity(Some("*company*"), Some("/oss/*")).matches(Some(path), &remotes),
+            "Expected no match when the path doesn't match"
+        );
+        assert!(
+            !identity(None, None).matches(Some(path), &remotes),
+            "Expected an identity without rules never to match"
+        );
+    }
+}
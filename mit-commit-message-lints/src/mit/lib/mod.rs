@@ -3,6 +3,12 @@
 pub(crate) mod author;
 pub(crate) mod author_state;
 pub(crate) mod authors;
+pub(crate) mod authors_document;
 mod errors;
+pub mod expiry_mode;
+pub(crate) mod identity;
 pub mod non_clean_behaviour;
+pub(crate) mod once_authors;
 pub mod rotation_option;
+pub(crate) mod session;
+pub mod signing_format;
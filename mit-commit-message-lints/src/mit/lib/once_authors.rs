@@ -0,0 +1,74 @@
+//! Authors for just the next commit
+use crate::mit::Author;
+
+/// Co-authors that replace the session's for just the next commit
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct OnceAuthors<'a> {
+    /// The co-authors of the next commit
+    pub authors: Vec<Author<'a>>,
+    /// The commit `HEAD` pointed at when they were set, if there was one
+    pub head: Option<String>,
+}
+
+impl OnceAuthors<'_> {
+    /// Whether the commit these authors were for has been made
+    ///
+    /// The post-commit hook clears them once the commit is made, this is for
+    /// when it isn't installed. Then it's as soon as `HEAD` has moved since
+    /// they were set, however it moved, so they're never used for more than
+    /// one commit. A commit that was aborted doesn't move `HEAD`, so they'll
+    /// still be used for the next attempt.
+    #[must_use]
+    pub fn is_committed(&self, head: Option<&str>) -> bool {
+        self.head.as_deref() != head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OnceAuthors;
+    use crate::mit::Author;
+
+    fn once() -> OnceAuthors<'static> {
+        OnceAuthors {
+            authors: vec![Author::new(
+                "Someone Else".into(),
+                "someone@example.com".into(),
+                None,
+            )],
+            head: Some("aaaa".into()),
+        }
+    }
+
+    #[test]
+    fn they_are_committed_once_head_moves() {
+        assert!(
+            once().is_committed(Some("bbbb")),
+            "Expected a new commit to count as committed"
+        );
+    }
+
+    #[test]
+    fn they_are_committed_even_if_the_commit_does_not_have_them() {
+        assert!(
+            once().is_committed(Some("cccc")),
+            "Expected HEAD moving without the trailers to still use them up"
+        );
+    }
+
+    #[test]
+    fn they_are_not_committed_if_head_has_not_moved() {
+        assert!(
+            !once().is_committed(Some("aaaa")),
+            "Expected the commit from before they were set not to count"
+        );
+        assert!(
+            !OnceAuthors {
+                head: None,
+                ..once()
+            }
+            .is_committed(None),
+            "Expected there still being no commits not to count"
+        );
+    }
+}
@@ -4,10 +4,13 @@ use std::{
     str::FromStr,
 };
 
+use serde::Serialize;
+
 use crate::mit::lib::errors::DeserializeRotationOptionError;
 
 /// How to rotate the primary author when pairing or mobbing
-#[derive(clap::ValueEnum, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy)]
+#[derive(clap::ValueEnum, Serialize, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
 pub enum RotationOption {
     /// Rotation is disabled
     Off,
@@ -0,0 +1,189 @@
+//! A summary of the current pairing session
+use std::collections::BTreeMap;
+
+use miette::{IntoDiagnostic, Result};
+use serde::Serialize;
+use tinytemplate::{TinyTemplate, format_unescaped};
+
+use crate::mit::{Author, Authors, RotationOption};
+
+/// Who commits will be made by, until when, and what they relate to
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+pub struct Session<'a> {
+    /// The author commits will be made as
+    pub author: Option<Author<'a>>,
+    /// Authors that will be added as `Co-authored-by` trailers
+    pub coauthors: Vec<Author<'a>>,
+    /// The branch the session is kept for, if it's not for the whole
+    /// repository
+    pub branch: Option<String>,
+    /// When the authors expire, as a unix timestamp
+    pub expires: Option<i64>,
+    /// Seconds until the authors expire, zero once they have
+    pub remaining: Option<i64>,
+    /// The value of the `Relates-to` trailer
+    pub relates_to: Option<String>,
+    /// How the primary author is rotated between commits
+    pub rotation: Option<RotationOption>,
+    /// Which hooks are installed, by hook name
+    pub hooks: BTreeMap<String, bool>,
+}
+
+#[derive(Serialize)]
+struct PromptContext {
+    initials: String,
+    remaining: i64,
+    relates_to: String,
+}
+
+impl<'a> Session<'a> {
+    /// The author and co-authors, while the session hasn't expired
+    #[must_use]
+    pub fn active_authors(&self) -> Vec<&Author<'a>> {
+        if self.remaining.is_some_and(|remaining| remaining > 0) {
+            self.author.iter().chain(&self.coauthors).collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Add the installation state of the hooks to the session
+    #[must_use]
+    pub fn with_hooks(self, hooks: BTreeMap<String, bool>) -> Self {
+        Self { hooks, ..self }
+    }
+
+    /// Fill in a template for a shell prompt
+    ///
+    /// `{initials}` is replaced with the initials of the author and
+    /// co-authors, found by looking up their emails in `authors`, falling back
+    /// to the email itself. `{remaining}` is replaced with the minutes until
+    /// the session expires, and `{relates_to}` with the relates-to value.
+    ///
+    /// A session that has expired, or was never started, renders as an empty
+    /// string.
+    ///
+    /// # Errors
+    ///
+    /// If the template isn't valid
+    pub fn prompt(&self, authors: &Authors<'_>, template: &str) -> Result<String> {
+        let Some(remaining) = self.remaining.filter(|remaining| *remaining > 0) else {
+            return Ok(String::new());
+        };
+
+        let initials = self
+            .author
+            .iter()
+            .chain(&self.coauthors)
+            .map(|author| {
+                authors
+                    .initial_for_email(author.email())
+                    .unwrap_or_else(|| author.email())
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut tt = TinyTemplate::new();
+        tt.set_default_formatter(&format_unescaped);
+        tt.add_template("prompt", template).into_diagnostic()?;
+        tt.render(
+            "prompt",
+            &PromptContext {
+                initials,
+                remaining: (remaining + 59) / 60,
+                relates_to: self.relates_to.clone().unwrap_or_default(),
+            },
+        )
+        .into_diagnostic()
+    }
+
+    /// Serialise the session as JSON
+    ///
+    /// # Errors
+    ///
+    /// If serialisation fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).into_diagnostic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Session;
+    use crate::mit::{Author, Authors};
+
+    fn session(remaining: Option<i64>) -> Session<'static> {
+        Session {
+            author: Some(Author::new(
+                "Billie Thompson".into(),
+                "billie@example.com".into(),
+                None,
+            )),
+            coauthors: vec![Author::new(
+                "Someone Else".into(),
+                "someone@example.com".into(),
+                None,
+            )],
+            branch: None,
+            expires: None,
+            remaining,
+            relates_to: Some("[#12345678]".into()),
+            rotation: None,
+            hooks: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn only_an_active_session_has_authors() {
+        assert_eq!(
+            session(Some(90))
+                .active_authors()
+                .into_iter()
+                .map(Author::name)
+                .collect::<Vec<_>>(),
+            vec!["Billie Thompson", "Someone Else"],
+            "Expected the author then the co-authors"
+        );
+        assert!(
+            session(Some(0)).active_authors().is_empty(),
+            "Expected no authors once the session has expired"
+        );
+    }
+
+    #[test]
+    fn the_prompt_has_the_initials_and_minutes_remaining() {
+        let mut store = BTreeMap::new();
+        store.insert(
+            "bt".into(),
+            Author::new("Billie Thompson".into(), "billie@example.com".into(), None),
+        );
+
+        assert_eq!(
+            session(Some(90))
+                .prompt(&Authors::new(store), "{initials} {remaining}m {relates_to}")
+                .unwrap(),
+            "bt someone@example.com 2m [#12345678]",
+            "Expected known emails as initials, unknown ones as emails, and minutes rounded up"
+        );
+    }
+
+    #[test]
+    fn the_prompt_is_empty_when_the_session_has_expired() {
+        assert_eq!(
+            session(Some(0))
+                .prompt(&Authors::default(), "{initials}")
+                .unwrap(),
+            "",
+            "Expected an expired session to have an empty prompt"
+        );
+        assert_eq!(
+            session(None)
+                .prompt(&Authors::default(), "{initials}")
+                .unwrap(),
+            "",
+            "Expected no session to have an empty prompt"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! The kind of key an author signs their commits with
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::mit::lib::errors::DeserializeSigningFormatError;
+
+/// The format of a signing key, as git's `gpg.format` has it
+#[derive(
+    clap::ValueEnum, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Copy,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningFormat {
+    /// A GPG key
+    Openpgp,
+    /// An SSH key
+    Ssh,
+    /// An X.509 certificate, used with gpgsm
+    X509,
+}
+
+const OPENPGP_DISPLAY: &str = "openpgp";
+const SSH_DISPLAY: &str = "ssh";
+const X509_DISPLAY: &str = "x509";
+
+impl SigningFormat {
+    /// Guess the format from the key, for authors that don't say
+    ///
+    /// Only SSH keys can be told apart, by being a public key, a path to one,
+    /// or git's `key::` prefix
+    #[must_use]
+    pub fn detect(signingkey: &str) -> Option<Self> {
+        let key = signingkey.trim();
+
+        if key.starts_with("ssh-")
+            || key.starts_with("ecdsa-sha2-")
+            || key.starts_with("sk-")
+            || key.starts_with("key::")
+            || key
+                .rsplit_once('.')
+                .is_some_and(|(_, extension)| extension == "pub")
+        {
+            Some(Self::Ssh)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for SigningFormat {
+    type Err = DeserializeSigningFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            OPENPGP_DISPLAY => Ok(Self::Openpgp),
+            SSH_DISPLAY => Ok(Self::Ssh),
+            X509_DISPLAY => Ok(Self::X509),
+            _ => Err(DeserializeSigningFormatError { src: s.into() }),
+        }
+    }
+}
+
+impl Display for SigningFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Openpgp => write!(f, "{OPENPGP_DISPLAY}"),
+            Self::Ssh => write!(f, "{SSH_DISPLAY}"),
+            Self::X509 => write!(f, "{X509_DISPLAY}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::SigningFormat;
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        for original in [
+            SigningFormat::Openpgp,
+            SigningFormat::Ssh,
+            SigningFormat::X509,
+        ] {
+            assert_eq!(
+                SigningFormat::from_str(&original.to_string()).unwrap(),
+                original,
+                "Expected display output to round-trip through from_str"
+            );
+        }
+    }
+
+    #[test]
+    fn ssh_keys_are_detected() {
+        for key in [
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIB billie@example.com",
+            "key::ssh-rsa AAAAB3NzaC1yc2E",
+            "~/.ssh/id_ed25519.pub",
+        ] {
+            assert_eq!(
+                SigningFormat::detect(key),
+                Some(SigningFormat::Ssh),
+                "Expected {key} to be detected as an SSH key"
+            );
+        }
+        assert_eq!(
+            SigningFormat::detect("0A46826A"),
+            None,
+            "Expected a GPG key id to be left for git to decide"
+        );
+    }
+}
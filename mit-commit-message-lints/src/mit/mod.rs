@@ -1,16 +1,32 @@
 //! Interactions relating to reading and setting authors
 
 pub use cmd::{
+    clear_commit_authors::clear_commit_authors,
+    extend_session::extend_session,
+    file_authors::{remove_file_authors, set_file_authors},
     get_authors::{AuthorArgs, GenericArgs, get_authors},
     get_commit_coauthor_configuration::get_commit_coauthor_configuration,
+    get_config_exact_initials::get_config_exact_initials,
+    get_config_expiry_mode::{get_config_expiry_mode, get_config_relates_to_expiry_mode},
     get_config_rotation::get_config_rotation,
+    get_session::get_session,
+    once_authors::{clear_once_authors, get_once_authors, set_once_authors},
+    previous_identity::{
+        restore_previous_identity, restore_previous_identity_if_expired, save_previous_identity,
+    },
+    remove_config_authors::{remove_config_authors, rename_config_authors},
     rotate_authors::rotate_authors,
+    session_author::restore_session_author,
     set_commit_authors::set_commit_authors,
     set_config_authors::set_config_authors,
+    set_config_expiry_mode::{set_config_expiry_mode, set_config_relates_to_expiry_mode},
+    set_config_group::{get_config_groups, set_config_group},
     set_config_rotation::set_config_rotation,
 };
 pub use lib::{
-    author::Author, author_state::AuthorState, authors::Authors, rotation_option::RotationOption,
+    author::Author, author_state::AuthorState, authors::Authors, authors_document::AuthorsDocument,
+    expiry_mode::ExpiryMode, identity::Identity, once_authors::OnceAuthors,
+    rotation_option::RotationOption, session::Session, signing_format::SigningFormat,
 };
 
 pub mod cmd;
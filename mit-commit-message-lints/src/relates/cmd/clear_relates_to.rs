@@ -0,0 +1,49 @@
+use miette::{Result, WrapErr};
+
+use crate::{
+    external::Vcs,
+    mit::cmd::{CONFIG_KEY_RELATE_EXPIRES, CONFIG_KEY_RELATE_EXPIRES_TIMEOUT},
+};
+
+const CONFIG_KEY_RELATE_TO: &str = "mit.relate.to";
+
+/// Stop adding a relates-to trailer to commits
+///
+/// # Errors
+///
+/// If reading or writing the VCS config fails
+pub fn clear_relates_to(config: &mut dyn Vcs) -> Result<()> {
+    [
+        CONFIG_KEY_RELATE_TO,
+        CONFIG_KEY_RELATE_EXPIRES,
+        CONFIG_KEY_RELATE_EXPIRES_TIMEOUT,
+    ]
+    .iter()
+    .try_for_each(|key| config.remove_if_set(key))
+    .wrap_err("failed to remove the relates-to")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{external::InMemory, relates::clear_relates_to};
+
+    #[test]
+    fn clearing_removes_the_relates_to_and_expiry() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.relate.to".into(), "[#12345678]".into());
+        buffer.insert("mit.relate.expires".into(), "1700000000".into());
+        buffer.insert("mit.relate.expires-timeout".into(), "end-of-day".into());
+        buffer.insert("mit.relate.template".into(), "JIRA-{ value }".into());
+        let mut vcs = InMemory::new(&mut buffer);
+
+        clear_relates_to(&mut vcs).unwrap();
+
+        assert_eq!(
+            buffer.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["mit.relate.template"],
+            "Expected only the template to be left"
+        );
+    }
+}
@@ -94,6 +94,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn the_branch_relate_to_is_preferred() {
+        let expires = format!("{}", epoch_with_offset(add_10_seconds));
+        let mut buffer = BTreeMap::from([
+            ("mit.relate.expires".to_string(), expires.clone()),
+            ("mit.relate.to".to_string(), "[#1]".to_string()),
+            ("mit.branch.feature.relate.expires".to_string(), expires),
+            (
+                "mit.branch.feature.relate.to".to_string(),
+                "[#2]".to_string(),
+            ),
+        ]);
+
+        assert_eq!(
+            (
+                get_relate_to_configuration(&InMemory::new(&mut buffer).with_branch("feature"))
+                    .unwrap()
+                    .map(|relates| relates.to().to_string()),
+                get_relate_to_configuration(&InMemory::new(&mut buffer).with_branch("main"))
+                    .unwrap()
+                    .map(|relates| relates.to().to_string()),
+            ),
+            (Some("[#2]".to_string()), Some("[#1]".to_string())),
+            "Expected the branch's relates-to on that branch, and the repository's elsewhere"
+        );
+    }
+
     fn add_10_seconds(x: Duration) -> Duration {
         x.add(Duration::from_secs(10))
     }
@@ -1,3 +1,4 @@
+pub mod clear_relates_to;
 pub mod errors;
 pub mod get_relate_to_configuration;
 pub mod set_relates_to;
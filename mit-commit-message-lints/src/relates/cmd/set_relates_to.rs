@@ -1,10 +1,14 @@
-use std::{convert::TryInto, time::Duration};
+use std::convert::TryInto;
 
 use miette::{Result, WrapErr, miette};
 use time::OffsetDateTime;
 
-use crate::{external::Vcs, relates::RelateTo};
-const CONFIG_KEY_EXPIRES: &str = "mit.relate.expires";
+use crate::{
+    external::Vcs,
+    mit::cmd::{CONFIG_KEY_RELATE_EXPIRES, CONFIG_KEY_RELATE_EXPIRES_TIMEOUT},
+    relates::RelateTo,
+    timeout::{Timeout, get_config_workday},
+};
 
 /// # Errors
 ///
@@ -12,10 +16,10 @@ const CONFIG_KEY_EXPIRES: &str = "mit.relate.expires";
 pub fn set_relates_to(
     config: &mut dyn Vcs,
     relates: &RelateTo<'_>,
-    expires_in: Duration,
+    timeout: &Timeout,
 ) -> Result<()> {
     set_vcs_relates_to(config, relates)?;
-    set_vcs_expires_time(config, expires_in)?;
+    set_vcs_expires_time(config, timeout)?;
 
     Ok(())
 }
@@ -25,9 +29,10 @@ fn set_vcs_relates_to(config: &mut dyn Vcs, relates: &RelateTo<'_>) -> Result<()
     Ok(())
 }
 
-fn set_vcs_expires_time(config: &mut dyn Vcs, expires_in: Duration) -> Result<()> {
+fn set_vcs_expires_time(config: &mut dyn Vcs, timeout: &Timeout) -> Result<()> {
     let now = OffsetDateTime::now_utc().unix_timestamp();
-    let expires_in_secs: i64 = expires_in
+    let expires_in_secs: i64 = timeout
+        .duration(&get_config_workday(config)?)
         .as_secs()
         .try_into()
         .map_err(|_| miette!("Expiration time exceeds maximum supported value"))?;
@@ -37,11 +42,16 @@ fn set_vcs_expires_time(config: &mut dyn Vcs, expires_in: Duration) -> Result<()
         .ok_or_else(|| miette!("Expiration time overflow"))?;
 
     config
-        .set_i64(CONFIG_KEY_EXPIRES, expiry_time)
-        .wrap_err("failed to update the expiry time mit-relates-to")
+        .set_i64(CONFIG_KEY_RELATE_EXPIRES, expiry_time)
+        .wrap_err("failed to update the expiry time mit-relates-to")?;
+    // Kept so a sliding expiry can be worked out again after each commit
+    config
+        .set_str(CONFIG_KEY_RELATE_EXPIRES_TIMEOUT, &timeout.to_string())
+        .wrap_err("failed to update the expiry timeout mit-relates-to")
 }
 
 #[cfg(test)]
+#[allow(clippy::uninlined_format_args)]
 mod tests {
     use std::{
         collections::BTreeMap,
@@ -54,6 +64,7 @@ mod tests {
     use crate::{
         external::InMemory,
         relates::{RelateTo, set_relates_to},
+        timeout::Timeout,
     };
 
     #[test]
@@ -63,7 +74,11 @@ mod tests {
         let mut vcs_config = InMemory::new(&mut buffer);
 
         let relates_to = RelateTo::from("[#12345678]");
-        let actual = set_relates_to(&mut vcs_config, &relates_to, Duration::from_hours(1));
+        let actual = set_relates_to(
+            &mut vcs_config,
+            &relates_to,
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
         assert_eq!(
@@ -79,7 +94,11 @@ mod tests {
         let mut vcs_config = InMemory::new(&mut buffer);
 
         let relates = RelateTo::from("[#12345678]");
-        let actual = set_relates_to(&mut vcs_config, &relates, Duration::from_hours(1));
+        let actual = set_relates_to(
+            &mut vcs_config,
+            &relates,
+            &Timeout::In(Duration::from_hours(1)),
+        );
 
         actual.unwrap();
 
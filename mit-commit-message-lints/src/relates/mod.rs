@@ -7,6 +7,7 @@ mod lib;
 
 pub use self::{
     cmd::{
+        clear_relates_to::clear_relates_to,
         get_relate_to_configuration::get_relate_to_configuration, set_relates_to::set_relates_to,
     },
     lib::relates_to::RelateTo,
@@ -1,25 +1,133 @@
 //! A module representing the scope
 
+use std::{ffi::OsStr, path::PathBuf};
+
+use clap::{
+    Arg, Command,
+    builder::{PossibleValue, TypedValueParser, ValueParserFactory},
+    error::{ContextKind, ContextValue, ErrorKind},
+};
+
 /// The scopes we might read the config for
-#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Clone, clap::ValueEnum, Copy)]
+///
+/// Reads still see every level git would, changes are saved to the one
+/// picked.
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Clone)]
 pub enum Scope {
     /// The home directory
     Global,
     /// The local folder
     Local,
+    /// The current worktree, if the repository has more than one
+    Worktree,
+    /// Everyone on this machine
+    System,
+    /// A config file, like one included with `include.path`
+    File(PathBuf),
 }
+
 impl Scope {
     /// If this scope is global or not
     #[must_use]
     pub fn is_global(&self) -> bool {
         &Self::Global == self
     }
+
+    /// If this scope is the config of a single repository
+    #[must_use]
+    pub const fn is_repository(&self) -> bool {
+        matches!(self, Self::Local | Self::Worktree)
+    }
+}
+
+impl ValueParserFactory for Scope {
+    type Parser = ScopeParser;
+
+    fn value_parser() -> Self::Parser {
+        ScopeParser
+    }
+}
+
+/// Parse a scope from the command line, a file being given as `file:<PATH>`
+#[derive(Clone, Copy, Debug)]
+pub struct ScopeParser;
+
+impl TypedValueParser for ScopeParser {
+    type Value = Scope;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value.to_string_lossy();
+
+        match value.as_ref() {
+            "global" => Ok(Scope::Global),
+            "local" => Ok(Scope::Local),
+            "worktree" => Ok(Scope::Worktree),
+            "system" => Ok(Scope::System),
+            _ => match value.strip_prefix("file:") {
+                Some(path) if !path.is_empty() => Ok(Scope::File(PathBuf::from(path))),
+                _ => {
+                    let mut error = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
+                    if let Some(arg) = arg {
+                        error.insert(
+                            ContextKind::InvalidArg,
+                            ContextValue::String(arg.to_string()),
+                        );
+                    }
+                    error.insert(
+                        ContextKind::InvalidValue,
+                        ContextValue::String(value.to_string()),
+                    );
+                    error.insert(
+                        ContextKind::ValidValue,
+                        ContextValue::Strings(
+                            self.possible_values()
+                                .into_iter()
+                                .flatten()
+                                .map(|possible| possible.get_name().to_string())
+                                .collect(),
+                        ),
+                    );
+                    Err(error)
+                }
+            },
+        }
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            [
+                PossibleValue::new("global").help("The home directory"),
+                PossibleValue::new("local").help("The local folder"),
+                PossibleValue::new("worktree")
+                    .help("The current worktree, if the repository has more than one"),
+                PossibleValue::new("system").help("Everyone on this machine"),
+                PossibleValue::new("file:<PATH>")
+                    .help("A config file, like one included with `include.path`"),
+            ]
+            .into_iter(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use clap::Parser;
+
     use super::Scope;
 
+    #[derive(Parser)]
+    struct Args {
+        #[clap(long, value_parser, default_value = "local")]
+        scope: Scope,
+    }
+
     #[test]
     fn global_scope_is_global() {
         assert!(
@@ -35,4 +143,27 @@ mod tests {
             "Expected the Local scope to not report as global"
         );
     }
+
+    #[test]
+    fn files_are_given_with_a_prefix() {
+        let args = Args::try_parse_from(["test", "--scope", "file:/etc/git-mit.config"]).unwrap();
+
+        assert_eq!(
+            args.scope,
+            Scope::File(PathBuf::from("/etc/git-mit.config")),
+            "Expected the path after the prefix to be the file"
+        );
+    }
+
+    #[test]
+    fn unknown_scopes_are_an_error() {
+        assert!(
+            Args::try_parse_from(["test", "--scope", "everywhere"]).is_err(),
+            "Expected an unknown scope to be rejected"
+        );
+        assert!(
+            Args::try_parse_from(["test", "--scope", "file:"]).is_err(),
+            "Expected a file scope without a path to be rejected"
+        );
+    }
 }
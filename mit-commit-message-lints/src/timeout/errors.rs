@@ -0,0 +1,40 @@
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+/// A timeout that isn't a length of time, a time of day or a keyword
+#[derive(Error, Debug, Diagnostic)]
+#[error("could not parse timeout")]
+#[diagnostic(
+    url(docsrs),
+    code(mit_commit_message_lints::timeout::parse_timeout_error),
+    help(
+        "timeouts can be minutes like `90`, a length like `90m`, `2h` or `1h30m`, a time like `17:30`, or `end-of-day` or `end-of-week`"
+    )
+)]
+pub struct ParseTimeoutError {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("{reason}")]
+    pub(crate) span: SourceSpan,
+
+    pub(crate) reason: String,
+}
+
+/// Workday configuration that can't be understood
+#[derive(Error, Debug, Diagnostic)]
+#[error("could not parse {key}")]
+#[diagnostic(
+    url(docsrs),
+    code(mit_commit_message_lints::timeout::parse_workday_error),
+    help("{help}")
+)]
+pub struct ParseWorkdayError {
+    #[source_code]
+    pub(crate) src: String,
+    #[label("{reason}")]
+    pub(crate) span: SourceSpan,
+
+    pub(crate) key: String,
+    pub(crate) reason: String,
+    pub(crate) help: String,
+}
@@ -0,0 +1,321 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
+use time::{OffsetDateTime, Time};
+
+use crate::timeout::{ParseTimeoutError, Workday, workday::parse_time};
+
+/// When something should expire, as it was asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timeout {
+    /// A length of time from now
+    In(Duration),
+    /// The next time the clock shows this time
+    At(Time),
+    /// When the workday ends, or midnight if it already has
+    EndOfDay,
+    /// When the last workday of the week ends, or the end of the week if it
+    /// already has
+    EndOfWeek,
+}
+
+const UNITS: [(&[&str], u64); 4] = [
+    (&["s", "sec", "secs", "second", "seconds"], 1),
+    (&["m", "min", "mins", "minute", "minutes"], 60),
+    (&["h", "hr", "hrs", "hour", "hours"], 60 * 60),
+    (&["d", "day", "days"], 24 * 60 * 60),
+];
+
+impl FromStr for Timeout {
+    type Err = ParseTimeoutError;
+
+    /// Parse a timeout
+    ///
+    /// A number on its own is minutes, so older configuration still works
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |offset: usize, length: usize, reason: &str| ParseTimeoutError {
+            src: s.into(),
+            span: (offset, length).into(),
+            reason: reason.into(),
+        };
+        let input = s.trim();
+
+        match input.to_ascii_lowercase().as_str() {
+            "end-of-day" | "eod" => return Ok(Self::EndOfDay),
+            "end-of-week" | "eow" => return Ok(Self::EndOfWeek),
+            "" => return Err(error(0, s.len(), "empty")),
+            _ => {}
+        }
+
+        if input.contains(':') {
+            return parse_time(input)
+                .map(Self::At)
+                .ok_or_else(|| error(0, s.len(), "not a time of day"));
+        }
+
+        if let Ok(minutes) = input.parse::<u64>() {
+            return minutes
+                .checked_mul(60)
+                .map(|seconds| Self::In(Duration::from_secs(seconds)))
+                .ok_or_else(|| error(0, s.len(), "too long"));
+        }
+
+        let start = s.len() - s.trim_start().len();
+        let mut seconds: u64 = 0;
+        let mut rest = input;
+        while !rest.is_empty() {
+            let offset = start + input.len() - rest.len();
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            let Ok(amount) = rest[..digits].parse::<u64>() else {
+                return Err(error(offset, rest.len(), "expected a number"));
+            };
+            rest = rest[digits..].trim_start();
+
+            let letters = rest.len()
+                - rest
+                    .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+                    .len();
+            let unit = rest[..letters].to_ascii_lowercase();
+            let Some((_, multiplier)) = UNITS
+                .iter()
+                .find(|(names, _)| names.contains(&unit.as_str()))
+            else {
+                return Err(error(
+                    offset,
+                    (digits + letters).max(1).min(s.len() - offset),
+                    "expected a unit of s, m, h or d",
+                ));
+            };
+            seconds = amount
+                .checked_mul(*multiplier)
+                .and_then(|amount| seconds.checked_add(amount))
+                .ok_or_else(|| error(offset, digits + letters, "too long"))?;
+            rest = rest[letters..].trim_start();
+        }
+
+        Ok(Self::In(Duration::from_secs(seconds)))
+    }
+}
+
+/// The timeout written so it can be parsed again, so it can be worked out
+/// afresh later
+impl Display for Timeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::In(duration) => write!(f, "{}s", duration.as_secs()),
+            Self::At(time) => write!(f, "{:02}:{:02}", time.hour(), time.minute()),
+            Self::EndOfDay => write!(f, "end-of-day"),
+            Self::EndOfWeek => write!(f, "end-of-week"),
+        }
+    }
+}
+
+impl Timeout {
+    /// How long there is from now until the timeout
+    #[must_use]
+    pub fn duration(&self, workday: &Workday) -> Duration {
+        self.duration_from(OffsetDateTime::now_utc(), workday)
+    }
+
+    /// How long there is from a moment until the timeout
+    #[must_use]
+    pub fn duration_from(&self, now: OffsetDateTime, workday: &Workday) -> Duration {
+        let now = now.to_offset(workday.offset);
+        let midnight = now.replace_time(Time::MIDNIGHT);
+        let today_end = now.replace_time(workday.end);
+
+        let expires = match self {
+            Self::In(duration) => return *duration,
+            Self::At(time) => {
+                let at = now.replace_time(*time);
+                if at > now {
+                    at
+                } else {
+                    at + time::Duration::DAY
+                }
+            }
+            Self::EndOfDay => {
+                if today_end > now {
+                    today_end
+                } else {
+                    midnight + time::Duration::DAY
+                }
+            }
+            Self::EndOfWeek => {
+                let today = i64::from(now.weekday().number_days_from_monday());
+                let last_workday = workday
+                    .days
+                    .iter()
+                    .map(|day| i64::from(day.number_days_from_monday()))
+                    .max()
+                    .unwrap_or(6);
+                let week_end = today_end + time::Duration::days(last_workday - today);
+
+                if week_end > now {
+                    week_end
+                } else {
+                    midnight + time::Duration::days(7 - today)
+                }
+            }
+        };
+
+        Duration::try_from(expires - now).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use time::{Date, Month, OffsetDateTime, Time, UtcOffset, Weekday};
+
+    use super::Timeout;
+    use crate::timeout::Workday;
+
+    fn utc(day: u8, hour: u8) -> OffsetDateTime {
+        Date::from_calendar_date(2024, Month::March, day)
+            .unwrap()
+            .with_hms(hour, 0, 0)
+            .unwrap()
+            .assume_utc()
+    }
+
+    fn workday() -> Workday {
+        Workday {
+            end: Time::from_hms(17, 0, 0).unwrap(),
+            days: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+            ],
+            offset: UtcOffset::from_hms(1, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn lengths_of_time_are_parsed() {
+        for (input, expected) in [
+            ("60", 60 * 60),
+            ("90m", 90 * 60),
+            ("2h", 2 * 60 * 60),
+            ("1h 30m", 90 * 60),
+            ("1d", 24 * 60 * 60),
+        ] {
+            assert_eq!(
+                Timeout::from_str(input).unwrap(),
+                Timeout::In(Duration::from_secs(expected)),
+                "Expected {input} to be {expected} seconds"
+            );
+        }
+    }
+
+    #[test]
+    fn times_and_keywords_are_parsed() {
+        assert_eq!(
+            (
+                Timeout::from_str("17:30").unwrap(),
+                Timeout::from_str("End-Of-Day").unwrap(),
+                Timeout::from_str("end-of-week").unwrap()
+            ),
+            (
+                Timeout::At(Time::from_hms(17, 30, 0).unwrap()),
+                Timeout::EndOfDay,
+                Timeout::EndOfWeek
+            ),
+            "Expected the time and keywords to be recognised"
+        );
+    }
+
+    #[test]
+    fn timeouts_can_be_written_and_read_back() {
+        for timeout in [
+            Timeout::In(Duration::from_mins(90)),
+            Timeout::At(Time::from_hms(9, 5, 0).unwrap()),
+            Timeout::EndOfDay,
+            Timeout::EndOfWeek,
+        ] {
+            assert_eq!(
+                Timeout::from_str(&timeout.to_string()).unwrap(),
+                timeout,
+                "Expected {timeout} to be read back as itself"
+            );
+        }
+    }
+
+    #[test]
+    fn lengths_of_time_too_long_to_store_are_errors() {
+        for input in [u64::MAX.to_string(), format!("{}d", u64::MAX / 60)] {
+            assert_eq!(
+                Timeout::from_str(&input).map_err(|error| error.reason),
+                Err("too long".to_string()),
+                "Expected {input} to be too long"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_units_are_labelled() {
+        let error = Timeout::from_str("2h 5y").unwrap_err();
+
+        assert_eq!(
+            (error.span.offset(), error.span.len()),
+            (3, 2),
+            "Expected the part with an unknown unit to be labelled"
+        );
+        assert!(
+            Timeout::from_str("25:00").is_err(),
+            "Expected an impossible time to be an error"
+        );
+    }
+
+    #[test]
+    fn times_are_the_next_time_the_clock_shows_them() {
+        // 16:00 in the workday's time zone
+        let now = utc(6, 15);
+
+        assert_eq!(
+            Timeout::At(Time::from_hms(17, 30, 0).unwrap()).duration_from(now, &workday()),
+            Duration::from_mins(90),
+            "Expected a time later today to be today"
+        );
+        assert_eq!(
+            Timeout::At(Time::from_hms(9, 0, 0).unwrap()).duration_from(now, &workday()),
+            Duration::from_hours(17),
+            "Expected a time that has passed to be tomorrow"
+        );
+    }
+
+    #[test]
+    fn end_of_day_and_week_follow_the_workday() {
+        // Wednesday, 16:00 in the workday's time zone
+        let wednesday = utc(6, 15);
+        // Friday, 18:00 in the workday's time zone
+        let friday_evening = utc(8, 17);
+
+        assert_eq!(
+            Timeout::EndOfDay.duration_from(wednesday, &workday()),
+            Duration::from_hours(1),
+            "Expected the end of the day to be when the workday ends"
+        );
+        assert_eq!(
+            Timeout::EndOfDay.duration_from(friday_evening, &workday()),
+            Duration::from_hours(6),
+            "Expected working late to last until midnight"
+        );
+        assert_eq!(
+            Timeout::EndOfWeek.duration_from(wednesday, &workday()),
+            Duration::from_hours(2 * 24 + 1),
+            "Expected the end of the week to be when Friday's workday ends"
+        );
+        assert_eq!(
+            Timeout::EndOfWeek.duration_from(friday_evening, &workday()),
+            Duration::from_hours(2 * 24 + 6),
+            "Expected working late on Friday to last until the end of Sunday"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! How long a session or relates-to lasts before it expires
+
+pub use self::{
+    errors::{ParseTimeoutError, ParseWorkdayError},
+    expiry::Timeout,
+    workday::{Workday, get_config_workday},
+};
+
+mod errors;
+mod expiry;
+mod workday;
@@ -0,0 +1,244 @@
+use miette::Result;
+use time::{Time, UtcOffset, Weekday};
+
+use crate::{external::Vcs, timeout::ParseWorkdayError};
+
+const CONFIG_KEY_END: &str = "mit.workday.end";
+const CONFIG_KEY_DAYS: &str = "mit.workday.days";
+const CONFIG_KEY_TIMEZONE: &str = "mit.workday.timezone";
+
+/// When you stop working, for timeouts like `end-of-day`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workday {
+    /// The time the workday ends
+    pub end: Time,
+    /// The days of the week you work
+    pub days: Vec<Weekday>,
+    /// The time zone times are given in
+    pub offset: UtcOffset,
+}
+
+impl Default for Workday {
+    /// Monday to Friday, finishing at 17:00 in the local time zone
+    fn default() -> Self {
+        Self {
+            end: Time::from_hms(17, 0, 0).unwrap_or(Time::MIDNIGHT),
+            days: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+            ],
+            offset: local_offset(),
+        }
+    }
+}
+
+/// Read the workday from `mit.workday.end`, `mit.workday.days` and
+/// `mit.workday.timezone`, using the default for anything that isn't set
+///
+/// # Errors
+///
+/// If the config can't be read, or is set to something that can't be parsed
+pub fn get_config_workday(config: &dyn Vcs) -> Result<Workday> {
+    let default = Workday::default();
+
+    Ok(Workday {
+        end: config
+            .get_str(CONFIG_KEY_END)?
+            .map(parse_end)
+            .transpose()?
+            .unwrap_or(default.end),
+        days: config
+            .get_str(CONFIG_KEY_DAYS)?
+            .map(parse_days)
+            .transpose()?
+            .unwrap_or(default.days),
+        offset: config
+            .get_str(CONFIG_KEY_TIMEZONE)?
+            .map(parse_offset)
+            .transpose()?
+            .unwrap_or(default.offset),
+    })
+}
+
+fn local_offset() -> UtcOffset {
+    UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)
+}
+
+/// Parse a 24 hour time like `17:30`
+pub fn parse_time(input: &str) -> Option<Time> {
+    let (hour, minute) = input.trim().split_once(':')?;
+    if minute.len() != 2 {
+        return None;
+    }
+
+    Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()
+}
+
+fn error(
+    key: &str,
+    input: &str,
+    span: (usize, usize),
+    reason: &str,
+    help: &str,
+) -> ParseWorkdayError {
+    ParseWorkdayError {
+        src: input.into(),
+        span: span.into(),
+        key: key.into(),
+        reason: reason.into(),
+        help: help.into(),
+    }
+}
+
+fn parse_end(input: &str) -> Result<Time, ParseWorkdayError> {
+    parse_time(input).ok_or_else(|| {
+        error(
+            CONFIG_KEY_END,
+            input,
+            (0, input.len()),
+            "not a time",
+            "the end of the workday is a 24 hour time like `17:30`",
+        )
+    })
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    let input = input.trim().to_ascii_lowercase();
+    let days = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    days.into_iter().find(|day| {
+        let name = day.to_string().to_ascii_lowercase();
+        input.len() >= 3 && name.starts_with(&input)
+    })
+}
+
+fn parse_days(input: &str) -> Result<Vec<Weekday>, ParseWorkdayError> {
+    let mut days = vec![];
+    let mut offset = 0;
+
+    for part in input.split(',') {
+        let invalid = || {
+            error(
+                CONFIG_KEY_DAYS,
+                input,
+                (offset, part.len()),
+                "not a day of the week, or a range of them",
+                "workdays are days like `mon,tue,thu` or ranges like `mon-fri`",
+            )
+        };
+
+        let (first, last) = part.split_once('-').unwrap_or((part, part));
+        let first = parse_weekday(first).ok_or_else(invalid)?;
+        let last = parse_weekday(last).ok_or_else(invalid)?;
+
+        let mut day = first;
+        loop {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+            if day == last {
+                break;
+            }
+            day = day.next();
+        }
+
+        offset += part.len() + 1;
+    }
+
+    Ok(days)
+}
+
+fn parse_offset(input: &str) -> Result<UtcOffset, ParseWorkdayError> {
+    let trimmed = input.trim();
+    match trimmed.to_ascii_lowercase().as_str() {
+        "local" => return Ok(local_offset()),
+        "utc" | "z" => return Ok(UtcOffset::UTC),
+        _ => {}
+    }
+
+    let invalid = || {
+        error(
+            CONFIG_KEY_TIMEZONE,
+            input,
+            (0, input.len()),
+            "not an offset from UTC",
+            "the time zone is `local`, `UTC` or an offset like `+01:00` or `-05:30`",
+        )
+    };
+
+    let (sign, rest) = match trimmed.split_at_checked(1) {
+        Some(("+", rest)) => (1, rest),
+        Some(("-", rest)) => (-1, rest),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours: i8 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i8 = minutes.parse().map_err(|_| invalid())?;
+
+    UtcOffset::from_hms(sign * hours, sign * minutes, 0).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use time::{Time, UtcOffset, Weekday};
+
+    use super::get_config_workday;
+    use crate::external::InMemory;
+
+    #[test]
+    fn the_workday_can_be_configured() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.workday.end".into(), "18:30".into());
+        buffer.insert("mit.workday.days".into(), "sun-tue,thursday".into());
+        buffer.insert("mit.workday.timezone".into(), "-05:30".into());
+        let vcs = InMemory::new(&mut buffer);
+
+        let workday = get_config_workday(&vcs).unwrap();
+
+        assert_eq!(
+            (workday.end, workday.days, workday.offset),
+            (
+                Time::from_hms(18, 30, 0).unwrap(),
+                vec![
+                    Weekday::Sunday,
+                    Weekday::Monday,
+                    Weekday::Tuesday,
+                    Weekday::Thursday
+                ],
+                UtcOffset::from_hms(-5, -30, 0).unwrap()
+            ),
+            "Expected the end, days and time zone to be read from the config"
+        );
+    }
+
+    #[test]
+    fn invalid_workdays_are_an_error() {
+        let mut buffer = BTreeMap::new();
+        buffer.insert("mit.workday.days".into(), "mon-fri,funday".into());
+        let vcs = InMemory::new(&mut buffer);
+
+        let error = get_config_workday(&vcs).unwrap_err();
+        let error = error
+            .downcast_ref::<crate::timeout::ParseWorkdayError>()
+            .unwrap();
+
+        assert_eq!(
+            (error.span.offset(), error.span.len()),
+            (8, 6),
+            "Expected the day that couldn't be read to be labelled"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use clap_complete::Shell;
+use mit_commit_message_lints::console::output::OutputFormat;
 
 #[derive(Parser, Clone, Eq, PartialEq)]
 #[clap(author, version, about)]
@@ -24,6 +25,16 @@ pub struct Args {
     )]
     pub copy_message_to_clipboard: bool,
 
+    /// How to print the problems found in the commit message
+    #[clap(
+        long,
+        value_enum,
+        value_parser,
+        env = "GIT_MIT_OUTPUT_FORMAT",
+        default_value = "human"
+    )]
+    pub output_format: OutputFormat,
+
     #[clap(long, value_enum, value_parser)]
     pub completion: Option<Shell>,
 }
@@ -1,18 +1,18 @@
 //! Errors relating to the commit-msg hook
 use std::fmt::Display;
 
-use miette::{Diagnostic, LabeledSpan, Result, Severity, SourceCode};
-use mit_lint::Problem;
+use miette::{Diagnostic, LabeledSpan, Report, Result, Severity, SourceCode};
+use mit_commit_message_lints::lints::LintProblem;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 #[error("multiple lint problems")]
-pub struct AggregateProblem(Vec<Problem>);
+pub struct AggregateProblem(Vec<LintProblem>);
 
 impl AggregateProblem {
-    pub(crate) fn to(problems: Vec<Problem>) -> Result<()> {
+    pub(crate) fn to(mut problems: Vec<LintProblem>) -> Result<()> {
         if problems.len() == 1 {
-            Err(problems.first().cloned().unwrap().into())
+            Err(Report::new_boxed(problems.remove(0)))
         } else if problems.is_empty() {
             Ok(())
         } else {
@@ -26,7 +26,7 @@ impl Diagnostic for AggregateProblem {
         let collection = self
             .0
             .iter()
-            .filter_map(|x| (x as &dyn Diagnostic).code().map(|x| x.to_string()))
+            .filter_map(|x| x.code().map(|x| x.to_string()))
             .collect::<Vec<String>>();
 
         if collection.is_empty() {
@@ -40,7 +40,7 @@ impl Diagnostic for AggregateProblem {
         let collection = self
             .0
             .iter()
-            .filter_map(Diagnostic::severity)
+            .filter_map(|x| x.severity())
             .collect::<Vec<Severity>>();
 
         if collection.is_empty() {
@@ -58,7 +58,7 @@ impl Diagnostic for AggregateProblem {
         let collection = self
             .0
             .iter()
-            .filter_map(|x| (x as &dyn Diagnostic).help().map(|x| x.to_string()))
+            .filter_map(|x| x.help().map(|x| x.to_string()))
             .collect::<Vec<String>>();
 
         if collection.is_empty() {
@@ -72,7 +72,7 @@ impl Diagnostic for AggregateProblem {
         let collection = self
             .0
             .iter()
-            .filter_map(|x| (x as &dyn Diagnostic).url().map(|x| x.to_string()))
+            .filter_map(|x| x.url().map(|x| x.to_string()))
             .collect::<Vec<String>>();
 
         if collection.is_empty() {
@@ -83,14 +83,14 @@ impl Diagnostic for AggregateProblem {
     }
 
     fn source_code(&self) -> Option<&dyn SourceCode> {
-        self.0.first().and_then(Diagnostic::source_code)
+        self.0.first().and_then(|x| x.source_code())
     }
 
     fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
         let collection = self
             .0
             .iter()
-            .filter_map(Diagnostic::labels)
+            .filter_map(|x| x.labels())
             .flatten()
             .collect::<Vec<LabeledSpan>>();
 
@@ -105,7 +105,7 @@ impl Diagnostic for AggregateProblem {
         let collection = self
             .0
             .iter()
-            .filter_map(Diagnostic::related)
+            .filter_map(|x| x.related())
             .flatten()
             .collect::<Vec<&dyn Diagnostic>>();
 
@@ -27,9 +27,14 @@ use clap_complete::generate;
 use miette::{IntoDiagnostic, Result};
 use mit_commit::CommitMessage;
 use mit_commit_message_lints::{
-    console::error_handling::miette_install, external, lints::read_from_toml_or_else_vcs,
+    console::{
+        error_handling::miette_install,
+        output::{self, OutputFormat},
+        style,
+    },
+    external,
+    lints::read_from_toml_or_else_vcs,
 };
-use mit_lint::async_lint;
 
 use crate::{cli::Args, errors::AggregateProblem};
 
@@ -51,16 +56,53 @@ async fn main() -> Result<()> {
     let current_dir = env::current_dir().into_diagnostic()?;
     let commit_file_path =
         external::resolve_commit_message_path(cli_args.commit_file_path, &current_dir)?;
+    let path = commit_file_path.to_string_lossy().to_string();
     let commit_message = CommitMessage::try_from(commit_file_path).into_diagnostic()?;
     let toml = external::read_toml(current_dir.clone())?;
     let git_config = external::Git2::try_from(current_dir)?;
     let lint_config = read_from_toml_or_else_vcs(&toml, &git_config)?;
+    style::untrusted_plugins(lint_config.plugins());
 
-    let lint_problems = async_lint(&commit_message, &lint_config).await;
-    if lint_problems.is_empty() {
+    let report = lint_config.async_lint(&commit_message).await?;
+
+    if cli_args.output_format != OutputFormat::Human {
+        let problems = output::problems(&report, &String::from(&commit_message));
+        match cli_args.output_format {
+            OutputFormat::Json => style::to_be_piped(&output::to_json(&problems)?),
+            OutputFormat::Sarif => style::to_be_piped(&output::to_sarif(&problems, &path)?),
+            OutputFormat::GithubAnnotations if !problems.is_empty() => {
+                style::to_be_piped(&output::to_github_annotations(&problems, &path));
+            }
+            OutputFormat::GithubAnnotations | OutputFormat::Human => {}
+        }
+
+        if report.errors().is_empty() {
+            return Ok(());
+        }
+
+        copy_to_clipboard(&commit_message)?;
+        std::process::exit(1);
+    }
+
+    let (errors, warnings) = report.into_parts();
+
+    for warning in warnings {
+        style::warning(
+            &warning.to_string(),
+            warning.help().map(|help| help.to_string()).as_deref(),
+        );
+    }
+
+    if errors.is_empty() {
         return Ok(());
     }
 
+    copy_to_clipboard(&commit_message)?;
+
+    AggregateProblem::to(errors)
+}
+
+fn copy_to_clipboard(commit_message: &CommitMessage<'_>) -> Result<()> {
     if let Ok(mut clipboard) = Clipboard::new() {
         let trimmed_commit = vec![
             commit_message.get_subject().to_string(),
@@ -73,7 +115,7 @@ async fn main() -> Result<()> {
         clipboard.set_text(trimmed_commit).into_diagnostic()?;
     }
 
-    AggregateProblem::to(lint_problems)
+    Ok(())
 }
 
 mod cli;
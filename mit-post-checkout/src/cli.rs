@@ -0,0 +1,20 @@
+use clap::Parser;
+use clap_complete::Shell;
+
+#[derive(Parser, Clone, Eq, PartialEq)]
+#[clap(author, version, about)]
+#[clap(bin_name = "mit-post-checkout")]
+pub struct Args {
+    /// The commit that was checked out before
+    #[clap(index = 1)]
+    pub previous_head: Option<String>,
+    /// The commit that is checked out now
+    #[clap(index = 2)]
+    pub new_head: Option<String>,
+    /// 1 if a branch was checked out, 0 if it was only files
+    #[clap(index = 3)]
+    pub branch_checkout: Option<String>,
+
+    #[clap(long, value_enum, value_parser)]
+    pub completion: Option<Shell>,
+}
@@ -0,0 +1,88 @@
+//! The post-checkout binary
+
+#![warn(clippy::nursery)]
+#![deny(
+    unused,
+    nonstandard_style,
+    future_incompatible,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    clippy::cargo,
+    clippy::complexity,
+    clippy::correctness,
+    clippy::perf,
+    clippy::style,
+    clippy::suspicious,
+    clippy::pedantic,
+    non_fmt_panics
+)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::{convert::TryFrom, env, io::stdout};
+
+use clap::{CommandFactory, Parser};
+use clap_complete::generate;
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{
+    console::{error_handling::miette_install, style},
+    external::Git2,
+    mit::{Author, get_session, restore_session_author},
+};
+
+use crate::cli::Args;
+
+fn main() -> Result<()> {
+    miette_install();
+
+    let cli_args = Args::parse();
+
+    // Simply print and exit if completion option is given.
+    if let Some(completion) = cli_args.completion {
+        let mut cmd = Args::command();
+        let name = cmd.get_name().to_string();
+        generate(completion, &mut cmd, name, &mut stdout());
+
+        std::process::exit(0);
+    }
+
+    // Checking out files doesn't change the branch
+    if cli_args.branch_checkout.as_deref() == Some("0") {
+        return Ok(());
+    }
+
+    let mut git_config = Git2::try_from(env::current_dir().into_diagnostic()?)?;
+    restore_session_author(&mut git_config)?;
+
+    let session = get_session(&git_config)?;
+    let authors = session.active_authors();
+
+    if authors.is_empty() {
+        style::warning(
+            "No pairing session is active",
+            Some("You can start one with `git mit` and the initials of whoever is coding"),
+        );
+
+        return Ok(());
+    }
+
+    let kept_for = session.branch.as_ref().map_or_else(
+        || "all branches".to_string(),
+        |branch| format!("the {branch} branch"),
+    );
+    style::success(
+        &format!(
+            "Committing as {}",
+            authors
+                .iter()
+                .map(|author| Author::name(author))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        &format!("This session is for {kept_for}"),
+    );
+
+    Ok(())
+}
+
+mod cli;
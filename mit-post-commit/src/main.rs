@@ -0,0 +1,56 @@
+//! The post-commit binary
+
+#![warn(clippy::nursery)]
+#![deny(
+    unused,
+    nonstandard_style,
+    future_incompatible,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    clippy::cargo,
+    clippy::complexity,
+    clippy::correctness,
+    clippy::perf,
+    clippy::style,
+    clippy::suspicious,
+    clippy::pedantic,
+    non_fmt_panics
+)]
+#![allow(clippy::multiple_crate_versions)]
+
+use std::{convert::TryFrom, env, io::stdout};
+
+use clap::{CommandFactory, Parser};
+use clap_complete::generate;
+use miette::{IntoDiagnostic, Result};
+use mit_commit_message_lints::{
+    console::error_handling::miette_install,
+    external::Git2,
+    mit::{clear_once_authors, extend_session},
+};
+
+use crate::cli::Args;
+
+fn main() -> Result<()> {
+    miette_install();
+
+    let cli_args = Args::parse();
+
+    // Simply print and exit if completion option is given.
+    if let Some(completion) = cli_args.completion {
+        let mut cmd = Args::command();
+        let name = cmd.get_name().to_string();
+        generate(completion, &mut cmd, name, &mut stdout());
+
+        std::process::exit(0);
+    }
+
+    // The commit has been made, so the authors for just that commit are done
+    // with, and a sliding session can be pushed back
+    let mut git_config = Git2::try_from(env::current_dir().into_diagnostic()?)?;
+    clear_once_authors(&mut git_config)?;
+    extend_session(&mut git_config)
+}
+
+mod cli;
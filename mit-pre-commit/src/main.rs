@@ -27,7 +27,7 @@ use miette::{IntoDiagnostic, Result};
 use mit_commit_message_lints::{
     console::error_handling::miette_install,
     external::Git2,
-    mit::{AuthorState, get_commit_coauthor_configuration},
+    mit::{AuthorState, get_commit_coauthor_configuration, restore_previous_identity_if_expired},
 };
 
 use crate::{
@@ -50,10 +50,12 @@ fn main() -> Result<()> {
     }
 
     let current_dir = env::current_dir().into_diagnostic()?;
-    let git_config = Git2::try_from(current_dir)?;
+    let mut git_config = Git2::try_from(current_dir)?;
+
     let co_author_configuration = get_commit_coauthor_configuration(&git_config)?;
 
     if let AuthorState::Timeout(time) = co_author_configuration {
+        restore_previous_identity_if_expired(&mut git_config)?;
         return Err(StaleAuthorError::new(time).into());
     }
 
@@ -23,7 +23,6 @@ use std::{
     fs::File,
     io::{Write, stdout},
     path::PathBuf,
-    process::{Command, Stdio},
 };
 
 use clap::{CommandFactory, Parser};
@@ -34,12 +33,12 @@ use mit_commit_message_lints::{
     console::error_handling::miette_install,
     external::{self, Git2, RepoState, Vcs},
     mit::{
-        Author, AuthorState,
+        Author, AuthorState, clear_once_authors,
         cmd::{
             get_config_non_clean_behaviour::get_config_non_clean_behaviour,
             get_config_rotation::get_config_rotation, rotate_authors::rotate_authors,
         },
-        get_commit_coauthor_configuration,
+        get_commit_coauthor_configuration, get_once_authors,
         lib::non_clean_behaviour::BehaviourOption,
     },
     relates::{RelateTo, get_relate_to_configuration},
@@ -104,9 +103,32 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    if let AuthorState::Some(authors) = get_commit_coauthor_configuration(&git_config)? {
-        append_coauthors_to_commit_message(commit_message_path.clone(), &authors)?;
+    // The post-commit hook clears the authors for one commit once it's made,
+    // without it we can only tell by HEAD moving
+    let post_commit_installed = external::installed_hooks(&current_dir)
+        .is_ok_and(|hooks| hooks.get("post-commit").copied().unwrap_or(false));
+    let head = external::read_head_commit(current_dir.clone())?;
+    let once_authors = match get_once_authors(&git_config)? {
+        Some(once)
+            if !post_commit_installed
+                && once.is_committed(head.as_ref().map(|commit| commit.id.as_str())) =>
+        {
+            clear_once_authors(&mut Git2::try_from(current_dir.clone())?)?;
+            None
+        }
+        once => once,
+    };
 
+    let co_author_configuration = get_commit_coauthor_configuration(&git_config)?;
+
+    if let Some(once) = &once_authors {
+        append_coauthors_to_commit_message(commit_message_path.clone(), &once.authors)?;
+    } else if let AuthorState::Some(authors) = &co_author_configuration {
+        append_coauthors_to_commit_message(commit_message_path.clone(), authors)?;
+    }
+
+    // The authors for one commit don't take a turn in the rotation
+    if once_authors.is_none() && co_author_configuration.is_some() {
         // Rotate primary author for the next commit if rotation is enabled
         let rotation = get_config_rotation(&git_config)?;
         if let Some(strategy) = rotation {
@@ -220,16 +242,12 @@ fn add_trailer_if_not_existing(
 }
 
 fn get_relates_to_from_exec(command: &str) -> Result<RelateTo<'_>> {
-    let commandline = shell_words::split(command).into_diagnostic()?;
-    let output = Command::new(commandline.first().unwrap_or(&String::new()))
-        .stderr(Stdio::inherit())
-        .args(commandline.iter().skip(1))
-        .output()
-        .into_diagnostic()?;
+    let current_dir = env::current_dir().into_diagnostic()?;
+    let output = external::run_exec(command, &current_dir, Some("prepare-commit-msg"))?;
 
-    if !output.status.success() {
+    if !output.success() {
         return Err(MitPrepareCommitMessageError::RelatesToExecFailed {
-            exit_code: output.status.code().unwrap_or(-1),
+            exit_code: output.exit_code.unwrap_or(-1),
         }
         .into());
     }